@@ -0,0 +1,349 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// An ADC specific error.
+///
+/// This error type contains errors specific to ADC (Analog-to-Digital Converter) peripherals. Also
+/// it has an `Impl` kind to pass through implementation specific errors occurring while trying to
+/// use an ADC peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum AdcError {
+    /// A new conversion result overwrote the previous one before it was read
+    Overrun,
+    /// The input signal exceeded the measurable range and the result is clipped
+    Saturated,
+    /// The ADC clock is missing or outside of the supported frequency range
+    ClockError,
+    /// The requested input channel does not exist or is not routed to this ADC
+    ChannelUnavailable,
+    /// The self-calibration of the ADC did not complete successfully
+    CalibrationFailed,
+    /// The conversion sequence contains more channels than the peripheral supports
+    SequenceTooLong,
+    /// The reference voltage is missing or outside of the specified range
+    ReferenceOutOfRange,
+    /// The operation cannot be performed while a conversion is ongoing
+    ConversionInProgress,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl AdcError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `Overrun` and `ConversionInProgress` are transient and thus retryable. `Impl` errors are
+    /// retryable if the wrapped [`ImplError`] is. All other kinds are hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            AdcError::Overrun | AdcError::ConversionInProgress => true,
+            AdcError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`]. `Overrun` and
+    /// `ConversionInProgress` suggest 1 ms, all other kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            AdcError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`AdcError::Impl`] and `false` for the ADC specific kinds, e.g.
+    /// [`AdcError::Overrun`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, AdcError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`AdcError::Impl`] with `f`.
+    ///
+    /// The ADC specific kinds, e.g. [`AdcError::Overrun`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            AdcError::Impl(e) => AdcError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// ADC errors use the high byte `0x08` and the index of the error kind as low byte, e.g.
+    /// `0x0801` for `Overrun`. See the [crate level documentation](crate#error-codes) for details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            AdcError::Overrun => 0x0801,
+            AdcError::Saturated => 0x0802,
+            AdcError::ClockError => 0x0803,
+            AdcError::ChannelUnavailable => 0x0804,
+            AdcError::CalibrationFailed => 0x0805,
+            AdcError::SequenceTooLong => 0x0806,
+            AdcError::ReferenceOutOfRange => 0x0807,
+            AdcError::ConversionInProgress => 0x0808,
+            AdcError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`AdcError::to_code`], e.g. `0x0801` to [`AdcError::Overrun`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the ADC error kinds nor to an
+    /// [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x0801 => Some(AdcError::Overrun),
+            0x0802 => Some(AdcError::Saturated),
+            0x0803 => Some(AdcError::ClockError),
+            0x0804 => Some(AdcError::ChannelUnavailable),
+            0x0805 => Some(AdcError::CalibrationFailed),
+            0x0806 => Some(AdcError::SequenceTooLong),
+            0x0807 => Some(AdcError::ReferenceOutOfRange),
+            0x0808 => Some(AdcError::ConversionInProgress),
+            0x0000..=0x00ff => ImplError::from_code(code).map(AdcError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. `ChannelUnavailable` and `SequenceTooLong` are
+    /// [`Severity::Fatal`]. All other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            AdcError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            AdcError::ChannelUnavailable | AdcError::SequenceTooLong => Severity::Fatal,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"adc"`, the stable lowercase name of the ADC peripheral, for all kinds including
+    /// `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "adc"
+    }
+
+    /// Returns the name of the error kind, e.g. `"Overrun"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            AdcError::Overrun => "Overrun",
+            AdcError::Saturated => "Saturated",
+            AdcError::ClockError => "ClockError",
+            AdcError::ChannelUnavailable => "ChannelUnavailable",
+            AdcError::CalibrationFailed => "CalibrationFailed",
+            AdcError::SequenceTooLong => "SequenceTooLong",
+            AdcError::ReferenceOutOfRange => "ReferenceOutOfRange",
+            AdcError::ConversionInProgress => "ConversionInProgress",
+            AdcError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `ConversionInProgress` maps to
+    /// `-EAGAIN`. `ChannelUnavailable` maps to `-ENODEV`. `Saturated` and `ReferenceOutOfRange` map
+    /// to `-ERANGE`. `Overrun` maps to `-EOVERFLOW`. `SequenceTooLong` maps to `-EMSGSIZE`. All
+    /// other kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            AdcError::Impl(e) => e.to_errno(),
+            AdcError::ConversionInProgress => -errno::EAGAIN,
+            AdcError::ChannelUnavailable => -errno::ENODEV,
+            AdcError::Saturated | AdcError::ReferenceOutOfRange => -errno::ERANGE,
+            AdcError::Overrun => -errno::EOVERFLOW,
+            AdcError::SequenceTooLong => -errno::EMSGSIZE,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for AdcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AdcError::Overrun => f.write_str("conversion result overrun"),
+            AdcError::Saturated => f.write_str("conversion result saturated"),
+            AdcError::ClockError => f.write_str("ADC clock error"),
+            AdcError::ChannelUnavailable => f.write_str("channel unavailable"),
+            AdcError::CalibrationFailed => f.write_str("calibration failed"),
+            AdcError::SequenceTooLong => f.write_str("conversion sequence too long"),
+            AdcError::ReferenceOutOfRange => f.write_str("reference voltage out of range"),
+            AdcError::ConversionInProgress => f.write_str("conversion in progress"),
+            AdcError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for AdcError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            AdcError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(AdcError, impl_error);
+
+impl PeripheralError for AdcError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a AdcError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a AdcError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[AdcError] = &[
+        AdcError::Overrun,
+        AdcError::Saturated,
+        AdcError::ClockError,
+        AdcError::ChannelUnavailable,
+        AdcError::CalibrationFailed,
+        AdcError::SequenceTooLong,
+        AdcError::ReferenceOutOfRange,
+        AdcError::ConversionInProgress,
+        AdcError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(AdcError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (AdcError::Overrun, "conversion result overrun"),
+            (AdcError::Saturated, "conversion result saturated"),
+            (AdcError::ClockError, "ADC clock error"),
+            (AdcError::ChannelUnavailable, "channel unavailable"),
+            (AdcError::CalibrationFailed, "calibration failed"),
+            (AdcError::SequenceTooLong, "conversion sequence too long"),
+            (
+                AdcError::ReferenceOutOfRange,
+                "reference voltage out of range",
+            ),
+            (AdcError::ConversionInProgress, "conversion in progress"),
+            (
+                AdcError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(
+            retryable,
+            [&AdcError::Overrun, &AdcError::ConversionInProgress]
+        );
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(
+            codes,
+            [0x0801, 0x0802, 0x0803, 0x0804, 0x0805, 0x0806, 0x0807, 0x0808, 0x0001,]
+        );
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Fatal,
+                Severity::Degraded,
+                Severity::Fatal,
+                Severity::Degraded,
+                Severity::Recoverable,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "adc");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(
+            millis,
+            [Some(1), None, None, None, None, None, None, Some(1), None,]
+        );
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::EOVERFLOW,
+                -errno::ERANGE,
+                -errno::EIO,
+                -errno::ENODEV,
+                -errno::EIO,
+                -errno::EMSGSIZE,
+                -errno::ERANGE,
+                -errno::EAGAIN,
+                -errno::EIO,
+            ]
+        );
+    }
+}
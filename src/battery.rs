@@ -0,0 +1,305 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// A battery specific error.
+///
+/// This error type contains errors specific to battery fuel gauge and charger peripherals. Also it
+/// has an `Impl` kind to pass through implementation specific errors occurring while trying to use
+/// a battery peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum BatteryError {
+    /// The battery voltage exceeds the safe limit
+    OverVoltage,
+    /// The battery voltage dropped below the safe limit
+    UnderVoltage,
+    /// The battery temperature is outside of the safe range
+    OverTemperature,
+    /// No battery is connected
+    NotDetected,
+    /// The calibration of the fuel gauge failed
+    CalibrationFailed,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl BatteryError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `Impl` errors are retryable if the wrapped [`ImplError`] is. All other kinds are hard
+    /// faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            BatteryError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`], the battery specific kinds are
+    /// not retryable and return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            BatteryError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`BatteryError::Impl`] and `false` for the battery specific kinds, e.g.
+    /// [`BatteryError::OverVoltage`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, BatteryError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`BatteryError::Impl`] with `f`.
+    ///
+    /// The battery specific kinds, e.g. [`BatteryError::OverVoltage`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            BatteryError::Impl(e) => BatteryError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// Battery errors use the high byte `0x2e` and the index of the error kind as low byte, e.g.
+    /// `0x2e01` for `OverVoltage`. See the [crate level documentation](crate#error-codes) for
+    /// details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            BatteryError::OverVoltage => 0x2e01,
+            BatteryError::UnderVoltage => 0x2e02,
+            BatteryError::OverTemperature => 0x2e03,
+            BatteryError::NotDetected => 0x2e04,
+            BatteryError::CalibrationFailed => 0x2e05,
+            BatteryError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`BatteryError::to_code`], e.g. `0x2e01` to
+    /// [`BatteryError::OverVoltage`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the battery error kinds nor to
+    /// an [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x2e01 => Some(BatteryError::OverVoltage),
+            0x2e02 => Some(BatteryError::UnderVoltage),
+            0x2e03 => Some(BatteryError::OverTemperature),
+            0x2e04 => Some(BatteryError::NotDetected),
+            0x2e05 => Some(BatteryError::CalibrationFailed),
+            0x0000..=0x00ff => ImplError::from_code(code).map(BatteryError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. `NotDetected` is [`Severity::Fatal`]. All other kinds
+    /// are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            BatteryError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            BatteryError::NotDetected => Severity::Fatal,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"battery"`, the stable lowercase name of the battery peripheral, for all kinds
+    /// including `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "battery"
+    }
+
+    /// Returns the name of the error kind, e.g. `"OverVoltage"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            BatteryError::OverVoltage => "OverVoltage",
+            BatteryError::UnderVoltage => "UnderVoltage",
+            BatteryError::OverTemperature => "OverTemperature",
+            BatteryError::NotDetected => "NotDetected",
+            BatteryError::CalibrationFailed => "CalibrationFailed",
+            BatteryError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `NotDetected` maps to
+    /// `-ENODEV`. `OverVoltage`, `UnderVoltage` and `OverTemperature` map to `-ERANGE`. All other
+    /// kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            BatteryError::Impl(e) => e.to_errno(),
+            BatteryError::NotDetected => -errno::ENODEV,
+            BatteryError::OverVoltage
+            | BatteryError::UnderVoltage
+            | BatteryError::OverTemperature => -errno::ERANGE,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for BatteryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BatteryError::OverVoltage => f.write_str("battery over voltage"),
+            BatteryError::UnderVoltage => f.write_str("battery under voltage"),
+            BatteryError::OverTemperature => f.write_str("battery over temperature"),
+            BatteryError::NotDetected => f.write_str("no battery detected"),
+            BatteryError::CalibrationFailed => f.write_str("calibration failed"),
+            BatteryError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for BatteryError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            BatteryError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(BatteryError, impl_error);
+
+impl PeripheralError for BatteryError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a BatteryError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a BatteryError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[BatteryError] = &[
+        BatteryError::OverVoltage,
+        BatteryError::UnderVoltage,
+        BatteryError::OverTemperature,
+        BatteryError::NotDetected,
+        BatteryError::CalibrationFailed,
+        BatteryError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(BatteryError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (BatteryError::OverVoltage, "battery over voltage"),
+            (BatteryError::UnderVoltage, "battery under voltage"),
+            (BatteryError::OverTemperature, "battery over temperature"),
+            (BatteryError::NotDetected, "no battery detected"),
+            (BatteryError::CalibrationFailed, "calibration failed"),
+            (
+                BatteryError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        assert!(!KINDS.iter().any(|e| e.is_retryable()));
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(codes, [0x2e01, 0x2e02, 0x2e03, 0x2e04, 0x2e05, 0x0001,]);
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Fatal,
+                Severity::Degraded,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "battery");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(millis, [None, None, None, None, None, None,]);
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::ERANGE,
+                -errno::ERANGE,
+                -errno::ERANGE,
+                -errno::ENODEV,
+                -errno::EIO,
+                -errno::EIO,
+            ]
+        );
+    }
+}
@@ -0,0 +1,325 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// A Bluetooth/BLE specific error.
+///
+/// This error type contains errors specific to Bluetooth/BLE (Bluetooth Low Energy) controller
+/// peripherals. Also it has an `Impl` kind to pass through implementation specific errors occurring
+/// while trying to use a Bluetooth/BLE peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum BleError {
+    /// The controller rejected or failed to execute an HCI command
+    HciCommandFailed,
+    /// The connection could not be established or was lost due to a supervision timeout
+    ConnectionTimeout,
+    /// Advertising could not be started or was aborted
+    AdvertisingFailed,
+    /// A packet did not fit into the controller or host buffers
+    BufferOverflow,
+    /// The connection or attribute handle is unknown to the controller
+    InvalidHandle,
+    /// The controller is busy with another operation
+    Busy,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl BleError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `ConnectionTimeout`, `AdvertisingFailed` and `Busy` are transient and thus retryable. `Impl`
+    /// errors are retryable if the wrapped [`ImplError`] is. All other kinds are hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            BleError::ConnectionTimeout | BleError::AdvertisingFailed | BleError::Busy => true,
+            BleError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`]. `ConnectionTimeout`,
+    /// `AdvertisingFailed` and `Busy` suggest 1 ms, all other kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            BleError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`BleError::Impl`] and `false` for the BLE specific kinds, e.g.
+    /// [`BleError::HciCommandFailed`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, BleError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`BleError::Impl`] with `f`.
+    ///
+    /// The BLE specific kinds, e.g. [`BleError::HciCommandFailed`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            BleError::Impl(e) => BleError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// BLE errors use the high byte `0x28` and the index of the error kind as low byte, e.g.
+    /// `0x2801` for `HciCommandFailed`. See the [crate level documentation](crate#error-codes) for
+    /// details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            BleError::HciCommandFailed => 0x2801,
+            BleError::ConnectionTimeout => 0x2802,
+            BleError::AdvertisingFailed => 0x2803,
+            BleError::BufferOverflow => 0x2804,
+            BleError::InvalidHandle => 0x2805,
+            BleError::Busy => 0x2806,
+            BleError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`BleError::to_code`], e.g. `0x2801` to
+    /// [`BleError::HciCommandFailed`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the BLE error kinds nor to an
+    /// [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x2801 => Some(BleError::HciCommandFailed),
+            0x2802 => Some(BleError::ConnectionTimeout),
+            0x2803 => Some(BleError::AdvertisingFailed),
+            0x2804 => Some(BleError::BufferOverflow),
+            0x2805 => Some(BleError::InvalidHandle),
+            0x2806 => Some(BleError::Busy),
+            0x0000..=0x00ff => ImplError::from_code(code).map(BleError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. All other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            BleError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"ble"`, the stable lowercase name of the BLE peripheral, for all kinds including
+    /// `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "ble"
+    }
+
+    /// Returns the name of the error kind, e.g. `"HciCommandFailed"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            BleError::HciCommandFailed => "HciCommandFailed",
+            BleError::ConnectionTimeout => "ConnectionTimeout",
+            BleError::AdvertisingFailed => "AdvertisingFailed",
+            BleError::BufferOverflow => "BufferOverflow",
+            BleError::InvalidHandle => "InvalidHandle",
+            BleError::Busy => "Busy",
+            BleError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `Busy` maps to `-EBUSY`.
+    /// `InvalidHandle` maps to `-EINVAL`. `BufferOverflow` maps to `-ENOBUFS`. `ConnectionTimeout`
+    /// maps to `-ETIMEDOUT`. All other kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            BleError::Impl(e) => e.to_errno(),
+            BleError::Busy => -errno::EBUSY,
+            BleError::InvalidHandle => -errno::EINVAL,
+            BleError::BufferOverflow => -errno::ENOBUFS,
+            BleError::ConnectionTimeout => -errno::ETIMEDOUT,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for BleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BleError::HciCommandFailed => f.write_str("HCI command failed"),
+            BleError::ConnectionTimeout => f.write_str("connection timed out"),
+            BleError::AdvertisingFailed => f.write_str("advertising failed"),
+            BleError::BufferOverflow => f.write_str("buffer overflow"),
+            BleError::InvalidHandle => f.write_str("invalid handle"),
+            BleError::Busy => f.write_str("controller busy"),
+            BleError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for BleError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            BleError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(BleError, impl_error);
+
+impl PeripheralError for BleError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a BleError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a BleError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[BleError] = &[
+        BleError::HciCommandFailed,
+        BleError::ConnectionTimeout,
+        BleError::AdvertisingFailed,
+        BleError::BufferOverflow,
+        BleError::InvalidHandle,
+        BleError::Busy,
+        BleError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(BleError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (BleError::HciCommandFailed, "HCI command failed"),
+            (BleError::ConnectionTimeout, "connection timed out"),
+            (BleError::AdvertisingFailed, "advertising failed"),
+            (BleError::BufferOverflow, "buffer overflow"),
+            (BleError::InvalidHandle, "invalid handle"),
+            (BleError::Busy, "controller busy"),
+            (
+                BleError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(
+            retryable,
+            [
+                &BleError::ConnectionTimeout,
+                &BleError::AdvertisingFailed,
+                &BleError::Busy
+            ]
+        );
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(
+            codes,
+            [0x2801, 0x2802, 0x2803, 0x2804, 0x2805, 0x2806, 0x0001,]
+        );
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Degraded,
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Recoverable,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "ble");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(millis, [None, Some(1), Some(1), None, None, Some(1), None,]);
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::EIO,
+                -errno::ETIMEDOUT,
+                -errno::EIO,
+                -errno::ENOBUFS,
+                -errno::EINVAL,
+                -errno::EBUSY,
+                -errno::EIO,
+            ]
+        );
+    }
+}
@@ -0,0 +1,669 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// A CAN specific error.
+///
+/// This error type contains errors specific to CAN (Controller Area Network) peripherals. Also it
+/// has an `Impl` kind to pass through implementation specific errors occurring while trying to use
+/// a CAN peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum CanError {
+    /// More than five consecutive bits of equal level were detected in a stuffed bit stream
+    Stuff,
+    /// A fixed-format bit field contained one or more illegal bits
+    Form,
+    /// A transmitted frame was not acknowledged by any other node on the bus
+    Acknowledge,
+    /// A recessive bit was sent but the bus was monitored as dominant
+    BitRecessive,
+    /// A dominant bit was sent but the bus was monitored as recessive
+    BitDominant,
+    /// The CRC of a received frame does not match the received data
+    Crc,
+    /// The error counters exceeded the bus-off limit and the node stopped participating on the bus
+    BusOff,
+    /// The error counters exceeded the error passive limit, the node may only send passive error
+    /// flags
+    ErrorPassive,
+    /// The error counters reached the warning limit
+    ErrorWarning,
+    /// All transmit mailboxes or buffers are occupied
+    TxBufferFull,
+    /// A frame was received while the receive FIFO was full and has been lost
+    RxFifoOverrun,
+    /// CAN FD specific error
+    Fd(CanFdError),
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl CanError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `Stuff`, `Form`, `Acknowledge`, `BitRecessive`, `BitDominant`, `Crc`, `ErrorWarning` and
+    /// `TxBufferFull` are transient and thus retryable. `Fd` errors are retryable if the wrapped
+    /// [`CanFdError`] is. `Impl` errors are retryable if the wrapped [`ImplError`] is. All other
+    /// kinds are hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            CanError::Stuff
+            | CanError::Form
+            | CanError::Acknowledge
+            | CanError::BitRecessive
+            | CanError::BitDominant
+            | CanError::Crc
+            | CanError::ErrorWarning
+            | CanError::TxBufferFull => true,
+            CanError::Fd(e) => e.is_retryable(),
+            CanError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors and the nested `Fd` kinds return the hint of the wrapped error. `Stuff`,
+    /// `Form`, `Acknowledge`, `BitRecessive`, `BitDominant`, `Crc`, `ErrorWarning` and
+    /// `TxBufferFull` suggest 1 ms, all other kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            CanError::Fd(e) => e.retry_after(),
+            CanError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`CanError::Impl`] and `false` for the CAN specific kinds, e.g.
+    /// [`CanError::Stuff`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, CanError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`CanError::Impl`] with `f`.
+    ///
+    /// The CAN specific kinds, e.g. [`CanError::Stuff`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            CanError::Impl(e) => CanError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// CAN errors use the high byte `0x07` and the index of the error kind as low byte, e.g.
+    /// `0x0701` for `Stuff`. See the [crate level documentation](crate#error-codes) for details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            CanError::Stuff => 0x0701,
+            CanError::Form => 0x0702,
+            CanError::Acknowledge => 0x0703,
+            CanError::BitRecessive => 0x0704,
+            CanError::BitDominant => 0x0705,
+            CanError::Crc => 0x0706,
+            CanError::BusOff => 0x0707,
+            CanError::ErrorPassive => 0x0708,
+            CanError::ErrorWarning => 0x0709,
+            CanError::TxBufferFull => 0x070a,
+            CanError::RxFifoOverrun => 0x070b,
+            CanError::Fd(CanFdError::ProtocolExceptionEvent) => 0x070c,
+            CanError::Fd(CanFdError::BitRateSwitch) => 0x070d,
+            CanError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`CanError::to_code`], e.g. `0x0701` to [`CanError::Stuff`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the CAN error kinds nor to an
+    /// [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x0701 => Some(CanError::Stuff),
+            0x0702 => Some(CanError::Form),
+            0x0703 => Some(CanError::Acknowledge),
+            0x0704 => Some(CanError::BitRecessive),
+            0x0705 => Some(CanError::BitDominant),
+            0x0706 => Some(CanError::Crc),
+            0x0707 => Some(CanError::BusOff),
+            0x0708 => Some(CanError::ErrorPassive),
+            0x0709 => Some(CanError::ErrorWarning),
+            0x070a => Some(CanError::TxBufferFull),
+            0x070b => Some(CanError::RxFifoOverrun),
+            0x070c => Some(CanError::Fd(CanFdError::ProtocolExceptionEvent)),
+            0x070d => Some(CanError::Fd(CanFdError::BitRateSwitch)),
+            0x0000..=0x00ff => ImplError::from_code(code).map(CanError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Fd` errors are classified by the wrapped [`CanFdError`]. `Impl` errors are classified by
+    /// the wrapped [`ImplError`]. [Retryable](Self::is_retryable) kinds are
+    /// [`Severity::Recoverable`]. All other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            CanError::Fd(e) => e.severity(),
+            CanError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"can"`, the stable lowercase name of the CAN peripheral, for all kinds including
+    /// `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "can"
+    }
+
+    /// Returns the name of the error kind, e.g. `"Stuff"`.
+    ///
+    /// The data carried by `Fd` is not included. `Impl` errors return `"Impl"` instead of the name
+    /// of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            CanError::Stuff => "Stuff",
+            CanError::Form => "Form",
+            CanError::Acknowledge => "Acknowledge",
+            CanError::BitRecessive => "BitRecessive",
+            CanError::BitDominant => "BitDominant",
+            CanError::Crc => "Crc",
+            CanError::BusOff => "BusOff",
+            CanError::ErrorPassive => "ErrorPassive",
+            CanError::ErrorWarning => "ErrorWarning",
+            CanError::TxBufferFull => "TxBufferFull",
+            CanError::RxFifoOverrun => "RxFifoOverrun",
+            CanError::Fd(_) => "Fd",
+            CanError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors and the nested `Fd` kinds return the value of the wrapped error. `Stuff`,
+    /// `Form`, `Acknowledge`, `BitRecessive` and `BitDominant` map to `-EPROTO`. `RxFifoOverrun`
+    /// maps to `-EOVERFLOW`. `Crc` maps to `-EILSEQ`. `BusOff` maps to `-ENETDOWN`. `TxBufferFull`
+    /// maps to `-ENOBUFS`. All other kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            CanError::Fd(e) => e.to_errno(),
+            CanError::Impl(e) => e.to_errno(),
+            CanError::Stuff
+            | CanError::Form
+            | CanError::Acknowledge
+            | CanError::BitRecessive
+            | CanError::BitDominant => -errno::EPROTO,
+            CanError::RxFifoOverrun => -errno::EOVERFLOW,
+            CanError::Crc => -errno::EILSEQ,
+            CanError::BusOff => -errno::ENETDOWN,
+            CanError::TxBufferFull => -errno::ENOBUFS,
+            _ => -errno::EIO,
+        }
+    }
+
+    /// Returns whether the error reports that the node went bus-off.
+    ///
+    /// Only true for `BusOff`. A bus-off node has to go through the bus-off recovery sequence
+    /// before it can participate on the bus again.
+    pub fn is_bus_off(&self) -> bool {
+        matches!(self, CanError::BusOff)
+    }
+
+    /// Returns whether the error reports that the node went error passive.
+    ///
+    /// Only true for `ErrorPassive`. `ErrorWarning` precedes and `BusOff` follows this state but
+    /// neither is reported as error passive.
+    pub fn is_error_passive(&self) -> bool {
+        matches!(self, CanError::ErrorPassive)
+    }
+}
+
+impl fmt::Display for CanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CanError::Stuff => f.write_str("bit stuffing error"),
+            CanError::Form => f.write_str("form error"),
+            CanError::Acknowledge => f.write_str("frame not acknowledged"),
+            CanError::BitRecessive => f.write_str("bit error, recessive bit read as dominant"),
+            CanError::BitDominant => f.write_str("bit error, dominant bit read as recessive"),
+            CanError::Crc => f.write_str("CRC does not match the received data"),
+            CanError::BusOff => f.write_str("node is bus-off"),
+            CanError::ErrorPassive => f.write_str("node is error passive"),
+            CanError::ErrorWarning => f.write_str("error counters reached warning limit"),
+            CanError::TxBufferFull => f.write_str("transmit buffer full"),
+            CanError::RxFifoOverrun => f.write_str("receive FIFO overrun"),
+            CanError::Fd(e) => fmt::Display::fmt(e, f),
+            CanError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for CanError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            CanError::Fd(e) => Some(e),
+            CanError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(CanError, impl_error);
+
+impl PeripheralError for CanError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a CanError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a CanError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+/// A CAN FD specific error.
+///
+/// These error kinds are only raised by peripherals supporting CAN FD (Flexible Data-Rate) frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum CanFdError {
+    /// A protocol exception event occurred, e.g. a classic CAN node received a frame with a
+    /// reserved bit set
+    ProtocolExceptionEvent,
+    /// Switching to or from the data phase bit rate failed
+    BitRateSwitch,
+}
+
+impl CanFdError {
+    /// All error kinds, e.g. for checking that formatting or a classification is total.
+    ///
+    /// The peripheral specific errors do not provide this list since their `Impl` kind can wrap any
+    /// [`ImplError`], which provides its own [`ImplError::ALL`].
+    pub const ALL: &'static [Self] = &[
+        CanFdError::ProtocolExceptionEvent,
+        CanFdError::BitRateSwitch,
+    ];
+
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// None of the kinds are transient, so this always returns `false`.
+    pub fn is_retryable(&self) -> bool {
+        false
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// The [`CanFdError`] kinds are not retryable and return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        if self.is_retryable() {
+            Some(Duration::from_millis(1))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// [Retryable](Self::is_retryable) kinds are [`Severity::Recoverable`]. All other kinds are
+    /// [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        if self.is_retryable() {
+            Severity::Recoverable
+        } else {
+            Severity::Degraded
+        }
+    }
+
+    /// Returns `"can"`, the stable lowercase name of the CAN peripheral, like the wrapping
+    /// [`CanError`].
+    pub fn peripheral(&self) -> &'static str {
+        "can"
+    }
+
+    /// Returns the name of the error kind, e.g. `"ProtocolExceptionEvent"`.
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            CanFdError::ProtocolExceptionEvent => "ProtocolExceptionEvent",
+            CanFdError::BitRateSwitch => "BitRateSwitch",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `ProtocolExceptionEvent` and `BitRateSwitch` map to `-EPROTO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            CanFdError::ProtocolExceptionEvent | CanFdError::BitRateSwitch => -errno::EPROTO,
+        }
+    }
+}
+
+impl fmt::Display for CanFdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CanFdError::ProtocolExceptionEvent => f.write_str("protocol exception event"),
+            CanFdError::BitRateSwitch => f.write_str("bit rate switch failed"),
+        }
+    }
+}
+
+impl core::error::Error for CanFdError {}
+
+impl PeripheralError for CanFdError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        None
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::error::Error;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[CanError] = &[
+        CanError::Stuff,
+        CanError::Form,
+        CanError::Acknowledge,
+        CanError::BitRecessive,
+        CanError::BitDominant,
+        CanError::Crc,
+        CanError::BusOff,
+        CanError::ErrorPassive,
+        CanError::ErrorWarning,
+        CanError::TxBufferFull,
+        CanError::RxFifoOverrun,
+        CanError::Fd(CanFdError::ProtocolExceptionEvent),
+        CanError::Fd(CanFdError::BitRateSwitch),
+        CanError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(CanError, KINDS, CanError::Fd(_));
+
+    #[test]
+    fn display() {
+        let cases = [
+            (CanError::Stuff, "bit stuffing error"),
+            (CanError::Form, "form error"),
+            (CanError::Acknowledge, "frame not acknowledged"),
+            (
+                CanError::BitRecessive,
+                "bit error, recessive bit read as dominant",
+            ),
+            (
+                CanError::BitDominant,
+                "bit error, dominant bit read as recessive",
+            ),
+            (CanError::Crc, "CRC does not match the received data"),
+            (CanError::BusOff, "node is bus-off"),
+            (CanError::ErrorPassive, "node is error passive"),
+            (
+                CanError::ErrorWarning,
+                "error counters reached warning limit",
+            ),
+            (CanError::TxBufferFull, "transmit buffer full"),
+            (CanError::RxFifoOverrun, "receive FIFO overrun"),
+            (
+                CanError::Fd(CanFdError::ProtocolExceptionEvent),
+                "protocol exception event",
+            ),
+            (
+                CanError::Fd(CanFdError::BitRateSwitch),
+                "bit rate switch failed",
+            ),
+            (
+                CanError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(
+            retryable,
+            [
+                &CanError::Stuff,
+                &CanError::Form,
+                &CanError::Acknowledge,
+                &CanError::BitRecessive,
+                &CanError::BitDominant,
+                &CanError::Crc,
+                &CanError::ErrorWarning,
+                &CanError::TxBufferFull
+            ]
+        );
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(
+            codes,
+            [
+                0x0701, 0x0702, 0x0703, 0x0704, 0x0705, 0x0706, 0x0707, 0x0708, 0x0709, 0x070a,
+                0x070b, 0x070c, 0x070d, 0x0001,
+            ]
+        );
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "can");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(
+            millis,
+            [
+                Some(1),
+                Some(1),
+                Some(1),
+                Some(1),
+                Some(1),
+                Some(1),
+                None,
+                None,
+                Some(1),
+                Some(1),
+                None,
+                None,
+                None,
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::EPROTO,
+                -errno::EPROTO,
+                -errno::EPROTO,
+                -errno::EPROTO,
+                -errno::EPROTO,
+                -errno::EILSEQ,
+                -errno::ENETDOWN,
+                -errno::EIO,
+                -errno::EIO,
+                -errno::ENOBUFS,
+                -errno::EOVERFLOW,
+                -errno::EPROTO,
+                -errno::EPROTO,
+                -errno::EIO,
+            ]
+        );
+    }
+
+    #[test]
+    fn source_chain() {
+        let cases = [
+            (
+                CanError::Fd(CanFdError::ProtocolExceptionEvent),
+                CanFdError::ProtocolExceptionEvent.to_string(),
+            ),
+            (
+                CanError::Fd(CanFdError::BitRateSwitch),
+                CanFdError::BitRateSwitch.to_string(),
+            ),
+        ];
+        for (e, source) in cases.iter() {
+            assert_eq!(e.source().unwrap().to_string(), *source);
+        }
+    }
+
+    #[test]
+    fn bus_state() {
+        let bus_off: Vec<_> = KINDS.iter().filter(|e| e.is_bus_off()).collect();
+        assert_eq!(bus_off, [&CanError::BusOff]);
+        let error_passive: Vec<_> = KINDS.iter().filter(|e| e.is_error_passive()).collect();
+        assert_eq!(error_passive, [&CanError::ErrorPassive]);
+    }
+
+    mod can_fd_error {
+        use super::*;
+
+        const KINDS: &[CanFdError] = &[
+            CanFdError::ProtocolExceptionEvent,
+            CanFdError::BitRateSwitch,
+        ];
+
+        crate::tests::error_tests!(CanFdError, KINDS);
+
+        #[test]
+        fn display() {
+            let cases = [
+                (
+                    CanFdError::ProtocolExceptionEvent,
+                    "protocol exception event",
+                ),
+                (CanFdError::BitRateSwitch, "bit rate switch failed"),
+            ];
+            for (e, display) in cases.iter() {
+                assert_eq!(e.to_string(), *display);
+            }
+        }
+
+        #[test]
+        fn is_retryable() {
+            assert!(!KINDS.iter().any(|e| e.is_retryable()));
+        }
+
+        #[test]
+        fn severity() {
+            let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+            assert_eq!(severities, [Severity::Degraded, Severity::Degraded,]);
+        }
+
+        #[test]
+        fn peripheral() {
+            for e in KINDS {
+                assert_eq!(e.peripheral(), "can");
+            }
+        }
+
+        #[test]
+        fn retry_after() {
+            let millis: Vec<_> = KINDS
+                .iter()
+                .map(|e| e.retry_after().map(|d| d.as_millis()))
+                .collect();
+            assert_eq!(millis, [None, None,]);
+        }
+
+        #[test]
+        fn to_errno() {
+            let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+            assert_eq!(errnos, [-errno::EPROTO, -errno::EPROTO,]);
+        }
+
+        #[test]
+        fn all() {
+            assert_eq!(CanFdError::ALL, KINDS);
+        }
+    }
+}
@@ -0,0 +1,315 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// A CEC specific error.
+///
+/// This error type contains errors specific to HDMI-CEC (Consumer Electronics Control) peripherals.
+/// Also it has an `Impl` kind to pass through implementation specific errors occurring while trying
+/// to use a CEC peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum CecError {
+    /// Another initiator won the arbitration
+    ArbitrationLost,
+    /// A bit with an invalid timing was detected on the line
+    BitError,
+    /// The follower did not acknowledge the message
+    Nack,
+    /// Transmitting the message failed
+    TxError,
+    /// A new byte was received before the previous byte was read
+    RxOverrun,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl CecError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `ArbitrationLost`, `BitError`, `Nack` and `TxError` are transient and thus retryable. `Impl`
+    /// errors are retryable if the wrapped [`ImplError`] is. All other kinds are hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            CecError::ArbitrationLost | CecError::BitError | CecError::Nack | CecError::TxError => {
+                true
+            }
+            CecError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`]. `ArbitrationLost`, `BitError`,
+    /// `Nack` and `TxError` suggest 1 ms, all other kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            CecError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`CecError::Impl`] and `false` for the HDMI-CEC specific kinds, e.g.
+    /// [`CecError::ArbitrationLost`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, CecError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`CecError::Impl`] with `f`.
+    ///
+    /// The HDMI-CEC specific kinds, e.g. [`CecError::ArbitrationLost`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            CecError::Impl(e) => CecError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// HDMI-CEC errors use the high byte `0x21` and the index of the error kind as low byte, e.g.
+    /// `0x2101` for `ArbitrationLost`. See the [crate level documentation](crate#error-codes) for
+    /// details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            CecError::ArbitrationLost => 0x2101,
+            CecError::BitError => 0x2102,
+            CecError::Nack => 0x2103,
+            CecError::TxError => 0x2104,
+            CecError::RxOverrun => 0x2105,
+            CecError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`CecError::to_code`], e.g. `0x2101` to
+    /// [`CecError::ArbitrationLost`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the HDMI-CEC error kinds nor
+    /// to an [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x2101 => Some(CecError::ArbitrationLost),
+            0x2102 => Some(CecError::BitError),
+            0x2103 => Some(CecError::Nack),
+            0x2104 => Some(CecError::TxError),
+            0x2105 => Some(CecError::RxOverrun),
+            0x0000..=0x00ff => ImplError::from_code(code).map(CecError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. All other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            CecError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"cec"`, the stable lowercase name of the HDMI-CEC peripheral, for all kinds
+    /// including `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "cec"
+    }
+
+    /// Returns the name of the error kind, e.g. `"ArbitrationLost"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            CecError::ArbitrationLost => "ArbitrationLost",
+            CecError::BitError => "BitError",
+            CecError::Nack => "Nack",
+            CecError::TxError => "TxError",
+            CecError::RxOverrun => "RxOverrun",
+            CecError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `Nack` maps to `-ENXIO`.
+    /// `ArbitrationLost` maps to `-EAGAIN`. `BitError` maps to `-EPROTO`. `RxOverrun` maps to
+    /// `-EOVERFLOW`. All other kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            CecError::Impl(e) => e.to_errno(),
+            CecError::Nack => -errno::ENXIO,
+            CecError::ArbitrationLost => -errno::EAGAIN,
+            CecError::BitError => -errno::EPROTO,
+            CecError::RxOverrun => -errno::EOVERFLOW,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for CecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CecError::ArbitrationLost => f.write_str("arbitration lost"),
+            CecError::BitError => f.write_str("bit error"),
+            CecError::Nack => f.write_str("message not acknowledged"),
+            CecError::TxError => f.write_str("transmit error"),
+            CecError::RxOverrun => f.write_str("receive overrun"),
+            CecError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for CecError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            CecError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(CecError, impl_error);
+
+impl PeripheralError for CecError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a CecError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a CecError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[CecError] = &[
+        CecError::ArbitrationLost,
+        CecError::BitError,
+        CecError::Nack,
+        CecError::TxError,
+        CecError::RxOverrun,
+        CecError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(CecError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (CecError::ArbitrationLost, "arbitration lost"),
+            (CecError::BitError, "bit error"),
+            (CecError::Nack, "message not acknowledged"),
+            (CecError::TxError, "transmit error"),
+            (CecError::RxOverrun, "receive overrun"),
+            (
+                CecError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(
+            retryable,
+            [
+                &CecError::ArbitrationLost,
+                &CecError::BitError,
+                &CecError::Nack,
+                &CecError::TxError
+            ]
+        );
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(codes, [0x2101, 0x2102, 0x2103, 0x2104, 0x2105, 0x0001,]);
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "cec");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(millis, [Some(1), Some(1), Some(1), Some(1), None, None,]);
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::EAGAIN,
+                -errno::EPROTO,
+                -errno::ENXIO,
+                -errno::EIO,
+                -errno::EOVERFLOW,
+                -errno::EIO,
+            ]
+        );
+    }
+}
@@ -0,0 +1,324 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// A clock specific error.
+///
+/// This error type contains errors specific to clock tree (RCC) peripherals. Also it has an `Impl`
+/// kind to pass through implementation specific errors occurring while trying to use a clock
+/// peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum ClockError {
+    /// The PLL did not lock in time
+    PllLockTimeout,
+    /// The oscillator did not start or stabilise in time
+    OscillatorNotReady,
+    /// The requested frequency cannot be derived from the available sources
+    FrequencyUnachievable,
+    /// The requested clock source is not available
+    SourceUnavailable,
+    /// The required prescaler or divider value is not supported
+    PrescalerOutOfRange,
+    /// Switching the system clock to the requested source failed
+    SwitchFailed,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl ClockError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `PllLockTimeout` and `OscillatorNotReady` are transient and thus retryable. `Impl` errors
+    /// are retryable if the wrapped [`ImplError`] is. All other kinds are hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ClockError::PllLockTimeout | ClockError::OscillatorNotReady => true,
+            ClockError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`]. `PllLockTimeout` and
+    /// `OscillatorNotReady` suggest 1 ms, all other kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            ClockError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`ClockError::Impl`] and `false` for the clock specific kinds, e.g.
+    /// [`ClockError::PllLockTimeout`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, ClockError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`ClockError::Impl`] with `f`.
+    ///
+    /// The clock specific kinds, e.g. [`ClockError::PllLockTimeout`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            ClockError::Impl(e) => ClockError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// Clock errors use the high byte `0x16` and the index of the error kind as low byte, e.g.
+    /// `0x1601` for `PllLockTimeout`. See the [crate level documentation](crate#error-codes) for
+    /// details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            ClockError::PllLockTimeout => 0x1601,
+            ClockError::OscillatorNotReady => 0x1602,
+            ClockError::FrequencyUnachievable => 0x1603,
+            ClockError::SourceUnavailable => 0x1604,
+            ClockError::PrescalerOutOfRange => 0x1605,
+            ClockError::SwitchFailed => 0x1606,
+            ClockError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`ClockError::to_code`], e.g. `0x1601` to
+    /// [`ClockError::PllLockTimeout`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the clock error kinds nor to
+    /// an [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x1601 => Some(ClockError::PllLockTimeout),
+            0x1602 => Some(ClockError::OscillatorNotReady),
+            0x1603 => Some(ClockError::FrequencyUnachievable),
+            0x1604 => Some(ClockError::SourceUnavailable),
+            0x1605 => Some(ClockError::PrescalerOutOfRange),
+            0x1606 => Some(ClockError::SwitchFailed),
+            0x0000..=0x00ff => ImplError::from_code(code).map(ClockError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. `FrequencyUnachievable` and `SourceUnavailable` are
+    /// [`Severity::Fatal`]. All other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            ClockError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            ClockError::FrequencyUnachievable | ClockError::SourceUnavailable => Severity::Fatal,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"clock"`, the stable lowercase name of the clock peripheral, for all kinds
+    /// including `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "clock"
+    }
+
+    /// Returns the name of the error kind, e.g. `"PllLockTimeout"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            ClockError::PllLockTimeout => "PllLockTimeout",
+            ClockError::OscillatorNotReady => "OscillatorNotReady",
+            ClockError::FrequencyUnachievable => "FrequencyUnachievable",
+            ClockError::SourceUnavailable => "SourceUnavailable",
+            ClockError::PrescalerOutOfRange => "PrescalerOutOfRange",
+            ClockError::SwitchFailed => "SwitchFailed",
+            ClockError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `OscillatorNotReady` maps to
+    /// `-EAGAIN`. `SourceUnavailable` maps to `-ENODEV`. `FrequencyUnachievable` and
+    /// `PrescalerOutOfRange` map to `-ERANGE`. `PllLockTimeout` maps to `-ETIMEDOUT`. All other
+    /// kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            ClockError::Impl(e) => e.to_errno(),
+            ClockError::OscillatorNotReady => -errno::EAGAIN,
+            ClockError::SourceUnavailable => -errno::ENODEV,
+            ClockError::FrequencyUnachievable | ClockError::PrescalerOutOfRange => -errno::ERANGE,
+            ClockError::PllLockTimeout => -errno::ETIMEDOUT,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for ClockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClockError::PllLockTimeout => f.write_str("PLL lock timeout"),
+            ClockError::OscillatorNotReady => f.write_str("oscillator not ready"),
+            ClockError::FrequencyUnachievable => f.write_str("frequency unachievable"),
+            ClockError::SourceUnavailable => f.write_str("clock source unavailable"),
+            ClockError::PrescalerOutOfRange => f.write_str("prescaler out of range"),
+            ClockError::SwitchFailed => f.write_str("clock switch failed"),
+            ClockError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for ClockError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            ClockError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(ClockError, impl_error);
+
+impl PeripheralError for ClockError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a ClockError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a ClockError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[ClockError] = &[
+        ClockError::PllLockTimeout,
+        ClockError::OscillatorNotReady,
+        ClockError::FrequencyUnachievable,
+        ClockError::SourceUnavailable,
+        ClockError::PrescalerOutOfRange,
+        ClockError::SwitchFailed,
+        ClockError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(ClockError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (ClockError::PllLockTimeout, "PLL lock timeout"),
+            (ClockError::OscillatorNotReady, "oscillator not ready"),
+            (ClockError::FrequencyUnachievable, "frequency unachievable"),
+            (ClockError::SourceUnavailable, "clock source unavailable"),
+            (ClockError::PrescalerOutOfRange, "prescaler out of range"),
+            (ClockError::SwitchFailed, "clock switch failed"),
+            (
+                ClockError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(
+            retryable,
+            [&ClockError::PllLockTimeout, &ClockError::OscillatorNotReady]
+        );
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(
+            codes,
+            [0x1601, 0x1602, 0x1603, 0x1604, 0x1605, 0x1606, 0x0001,]
+        );
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Fatal,
+                Severity::Fatal,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "clock");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(millis, [Some(1), Some(1), None, None, None, None, None,]);
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::ETIMEDOUT,
+                -errno::EAGAIN,
+                -errno::ERANGE,
+                -errno::ENODEV,
+                -errno::ERANGE,
+                -errno::EIO,
+                -errno::EIO,
+            ]
+        );
+    }
+}
@@ -0,0 +1,303 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// A comparator specific error.
+///
+/// This error type contains errors specific to analog comparator peripherals. Also it has an `Impl`
+/// kind to pass through implementation specific errors occurring while trying to use a comparator
+/// peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum ComparatorError {
+    /// The requested threshold cannot be generated by the reference
+    InvalidThreshold,
+    /// The requested hysteresis is not supported
+    HysteresisUnsupported,
+    /// The requested output polarity is not supported
+    OutputPolarityUnsupported,
+    /// The requested input cannot be connected to the comparator
+    InputUnavailable,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl ComparatorError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `Impl` errors are retryable if the wrapped [`ImplError`] is. All other kinds are hard
+    /// faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ComparatorError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`], the comparator specific kinds
+    /// are not retryable and return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            ComparatorError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`ComparatorError::Impl`] and `false` for the comparator specific kinds,
+    /// e.g. [`ComparatorError::InvalidThreshold`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, ComparatorError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`ComparatorError::Impl`] with `f`.
+    ///
+    /// The comparator specific kinds, e.g. [`ComparatorError::InvalidThreshold`], are returned
+    /// unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            ComparatorError::Impl(e) => ComparatorError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// Comparator errors use the high byte `0x15` and the index of the error kind as low byte, e.g.
+    /// `0x1501` for `InvalidThreshold`. See the [crate level documentation](crate#error-codes) for
+    /// details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            ComparatorError::InvalidThreshold => 0x1501,
+            ComparatorError::HysteresisUnsupported => 0x1502,
+            ComparatorError::OutputPolarityUnsupported => 0x1503,
+            ComparatorError::InputUnavailable => 0x1504,
+            ComparatorError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`ComparatorError::to_code`], e.g. `0x1501` to
+    /// [`ComparatorError::InvalidThreshold`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the comparator error kinds nor
+    /// to an [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x1501 => Some(ComparatorError::InvalidThreshold),
+            0x1502 => Some(ComparatorError::HysteresisUnsupported),
+            0x1503 => Some(ComparatorError::OutputPolarityUnsupported),
+            0x1504 => Some(ComparatorError::InputUnavailable),
+            0x0000..=0x00ff => ImplError::from_code(code).map(ComparatorError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. `HysteresisUnsupported`, `OutputPolarityUnsupported`
+    /// and `InputUnavailable` are [`Severity::Fatal`]. All other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            ComparatorError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            ComparatorError::HysteresisUnsupported
+            | ComparatorError::OutputPolarityUnsupported
+            | ComparatorError::InputUnavailable => Severity::Fatal,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"comparator"`, the stable lowercase name of the comparator peripheral, for all
+    /// kinds including `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "comparator"
+    }
+
+    /// Returns the name of the error kind, e.g. `"InvalidThreshold"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            ComparatorError::InvalidThreshold => "InvalidThreshold",
+            ComparatorError::HysteresisUnsupported => "HysteresisUnsupported",
+            ComparatorError::OutputPolarityUnsupported => "OutputPolarityUnsupported",
+            ComparatorError::InputUnavailable => "InputUnavailable",
+            ComparatorError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `InputUnavailable` maps to
+    /// `-ENODEV`. `InvalidThreshold` maps to `-EINVAL`. All other kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            ComparatorError::Impl(e) => e.to_errno(),
+            ComparatorError::InputUnavailable => -errno::ENODEV,
+            ComparatorError::InvalidThreshold => -errno::EINVAL,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for ComparatorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ComparatorError::InvalidThreshold => f.write_str("invalid threshold"),
+            ComparatorError::HysteresisUnsupported => f.write_str("hysteresis unsupported"),
+            ComparatorError::OutputPolarityUnsupported => {
+                f.write_str("output polarity unsupported")
+            }
+            ComparatorError::InputUnavailable => f.write_str("input unavailable"),
+            ComparatorError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for ComparatorError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            ComparatorError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(ComparatorError, impl_error);
+
+impl PeripheralError for ComparatorError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a ComparatorError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a ComparatorError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[ComparatorError] = &[
+        ComparatorError::InvalidThreshold,
+        ComparatorError::HysteresisUnsupported,
+        ComparatorError::OutputPolarityUnsupported,
+        ComparatorError::InputUnavailable,
+        ComparatorError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(ComparatorError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (ComparatorError::InvalidThreshold, "invalid threshold"),
+            (
+                ComparatorError::HysteresisUnsupported,
+                "hysteresis unsupported",
+            ),
+            (
+                ComparatorError::OutputPolarityUnsupported,
+                "output polarity unsupported",
+            ),
+            (ComparatorError::InputUnavailable, "input unavailable"),
+            (
+                ComparatorError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        assert!(!KINDS.iter().any(|e| e.is_retryable()));
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(codes, [0x1501, 0x1502, 0x1503, 0x1504, 0x0001,]);
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Degraded,
+                Severity::Fatal,
+                Severity::Fatal,
+                Severity::Fatal,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "comparator");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(millis, [None, None, None, None, None,]);
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::EINVAL,
+                -errno::EIO,
+                -errno::EIO,
+                -errno::ENODEV,
+                -errno::EIO,
+            ]
+        );
+    }
+}
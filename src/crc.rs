@@ -0,0 +1,287 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// A CRC unit specific error.
+///
+/// This error type contains errors specific to CRC calculation unit peripherals. Also it has an
+/// `Impl` kind to pass through implementation specific errors occurring while trying to use a CRC
+/// unit peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum CrcUnitError {
+    /// The requested polynomial is not supported by the calculation unit
+    UnsupportedPolynomial,
+    /// The requested CRC width is not supported by the calculation unit
+    UnsupportedWidth,
+    /// The calculation unit has not been configured
+    NotConfigured,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl CrcUnitError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `Impl` errors are retryable if the wrapped [`ImplError`] is. All other kinds are hard
+    /// faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            CrcUnitError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`], the CRC specific kinds are not
+    /// retryable and return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            CrcUnitError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`CrcUnitError::Impl`] and `false` for the CRC specific kinds, e.g.
+    /// [`CrcUnitError::UnsupportedPolynomial`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, CrcUnitError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`CrcUnitError::Impl`] with `f`.
+    ///
+    /// The CRC specific kinds, e.g. [`CrcUnitError::UnsupportedPolynomial`], are returned
+    /// unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            CrcUnitError::Impl(e) => CrcUnitError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// CRC errors use the high byte `0x1f` and the index of the error kind as low byte, e.g.
+    /// `0x1f01` for `UnsupportedPolynomial`. See the [crate level documentation](crate#error-codes)
+    /// for details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            CrcUnitError::UnsupportedPolynomial => 0x1f01,
+            CrcUnitError::UnsupportedWidth => 0x1f02,
+            CrcUnitError::NotConfigured => 0x1f03,
+            CrcUnitError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`CrcUnitError::to_code`], e.g. `0x1f01` to
+    /// [`CrcUnitError::UnsupportedPolynomial`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the CRC error kinds nor to an
+    /// [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x1f01 => Some(CrcUnitError::UnsupportedPolynomial),
+            0x1f02 => Some(CrcUnitError::UnsupportedWidth),
+            0x1f03 => Some(CrcUnitError::NotConfigured),
+            0x0000..=0x00ff => ImplError::from_code(code).map(CrcUnitError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. `UnsupportedPolynomial` and `UnsupportedWidth` are
+    /// [`Severity::Fatal`]. All other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            CrcUnitError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            CrcUnitError::UnsupportedPolynomial | CrcUnitError::UnsupportedWidth => Severity::Fatal,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"crc"`, the stable lowercase name of the CRC peripheral, for all kinds including
+    /// `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "crc"
+    }
+
+    /// Returns the name of the error kind, e.g. `"UnsupportedPolynomial"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            CrcUnitError::UnsupportedPolynomial => "UnsupportedPolynomial",
+            CrcUnitError::UnsupportedWidth => "UnsupportedWidth",
+            CrcUnitError::NotConfigured => "NotConfigured",
+            CrcUnitError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `NotConfigured` maps to
+    /// `-EINVAL`. `UnsupportedPolynomial` and `UnsupportedWidth` map to `-EOPNOTSUPP`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            CrcUnitError::Impl(e) => e.to_errno(),
+            CrcUnitError::NotConfigured => -errno::EINVAL,
+            CrcUnitError::UnsupportedPolynomial | CrcUnitError::UnsupportedWidth => {
+                -errno::EOPNOTSUPP
+            }
+        }
+    }
+}
+
+impl fmt::Display for CrcUnitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CrcUnitError::UnsupportedPolynomial => f.write_str("unsupported polynomial"),
+            CrcUnitError::UnsupportedWidth => f.write_str("unsupported width"),
+            CrcUnitError::NotConfigured => f.write_str("not configured"),
+            CrcUnitError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for CrcUnitError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            CrcUnitError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(CrcUnitError, impl_error);
+
+impl PeripheralError for CrcUnitError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a CrcUnitError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a CrcUnitError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[CrcUnitError] = &[
+        CrcUnitError::UnsupportedPolynomial,
+        CrcUnitError::UnsupportedWidth,
+        CrcUnitError::NotConfigured,
+        CrcUnitError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(CrcUnitError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (
+                CrcUnitError::UnsupportedPolynomial,
+                "unsupported polynomial",
+            ),
+            (CrcUnitError::UnsupportedWidth, "unsupported width"),
+            (CrcUnitError::NotConfigured, "not configured"),
+            (
+                CrcUnitError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        assert!(!KINDS.iter().any(|e| e.is_retryable()));
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(codes, [0x1f01, 0x1f02, 0x1f03, 0x0001,]);
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Fatal,
+                Severity::Fatal,
+                Severity::Degraded,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "crc");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(millis, [None, None, None, None,]);
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::EOPNOTSUPP,
+                -errno::EOPNOTSUPP,
+                -errno::EINVAL,
+                -errno::EIO,
+            ]
+        );
+    }
+}
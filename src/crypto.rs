@@ -0,0 +1,323 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// A crypto specific error.
+///
+/// This error type contains errors specific to crypto accelerator (AES/HASH/PKA) peripherals. Also
+/// it has an `Impl` kind to pass through implementation specific errors occurring while trying to
+/// use a crypto peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum CryptoError {
+    /// The key length is not supported by the algorithm
+    InvalidKeyLength,
+    /// The initialisation vector length is not supported by the algorithm
+    InvalidIvLength,
+    /// The data length is not a multiple of the algorithm block size
+    DataNotBlockAligned,
+    /// The computed authentication tag does not match the expected tag
+    AuthenticationFailed,
+    /// The algorithm or mode is not supported by the accelerator
+    UnsupportedAlgorithm,
+    /// The accelerator is busy with another operation
+    Busy,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl CryptoError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `Busy` is transient and thus retryable. `Impl` errors are retryable if the wrapped
+    /// [`ImplError`] is. All other kinds are hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            CryptoError::Busy => true,
+            CryptoError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`]. `Busy` suggests 1 ms, all other
+    /// kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            CryptoError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`CryptoError::Impl`] and `false` for the crypto specific kinds, e.g.
+    /// [`CryptoError::InvalidKeyLength`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, CryptoError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`CryptoError::Impl`] with `f`.
+    ///
+    /// The crypto specific kinds, e.g. [`CryptoError::InvalidKeyLength`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            CryptoError::Impl(e) => CryptoError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// Crypto errors use the high byte `0x14` and the index of the error kind as low byte, e.g.
+    /// `0x1401` for `InvalidKeyLength`. See the [crate level documentation](crate#error-codes) for
+    /// details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            CryptoError::InvalidKeyLength => 0x1401,
+            CryptoError::InvalidIvLength => 0x1402,
+            CryptoError::DataNotBlockAligned => 0x1403,
+            CryptoError::AuthenticationFailed => 0x1404,
+            CryptoError::UnsupportedAlgorithm => 0x1405,
+            CryptoError::Busy => 0x1406,
+            CryptoError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`CryptoError::to_code`], e.g. `0x1401` to
+    /// [`CryptoError::InvalidKeyLength`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the crypto error kinds nor to
+    /// an [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x1401 => Some(CryptoError::InvalidKeyLength),
+            0x1402 => Some(CryptoError::InvalidIvLength),
+            0x1403 => Some(CryptoError::DataNotBlockAligned),
+            0x1404 => Some(CryptoError::AuthenticationFailed),
+            0x1405 => Some(CryptoError::UnsupportedAlgorithm),
+            0x1406 => Some(CryptoError::Busy),
+            0x0000..=0x00ff => ImplError::from_code(code).map(CryptoError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. `InvalidKeyLength`, `InvalidIvLength` and
+    /// `UnsupportedAlgorithm` are [`Severity::Fatal`]. All other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            CryptoError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            CryptoError::InvalidKeyLength
+            | CryptoError::InvalidIvLength
+            | CryptoError::UnsupportedAlgorithm => Severity::Fatal,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"crypto"`, the stable lowercase name of the crypto peripheral, for all kinds
+    /// including `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "crypto"
+    }
+
+    /// Returns the name of the error kind, e.g. `"InvalidKeyLength"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            CryptoError::InvalidKeyLength => "InvalidKeyLength",
+            CryptoError::InvalidIvLength => "InvalidIvLength",
+            CryptoError::DataNotBlockAligned => "DataNotBlockAligned",
+            CryptoError::AuthenticationFailed => "AuthenticationFailed",
+            CryptoError::UnsupportedAlgorithm => "UnsupportedAlgorithm",
+            CryptoError::Busy => "Busy",
+            CryptoError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `Busy` maps to `-EBUSY`.
+    /// `InvalidKeyLength`, `InvalidIvLength` and `DataNotBlockAligned` map to `-EINVAL`.
+    /// `AuthenticationFailed` maps to `-EBADMSG`. `UnsupportedAlgorithm` maps to `-EOPNOTSUPP`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            CryptoError::Impl(e) => e.to_errno(),
+            CryptoError::Busy => -errno::EBUSY,
+            CryptoError::InvalidKeyLength
+            | CryptoError::InvalidIvLength
+            | CryptoError::DataNotBlockAligned => -errno::EINVAL,
+            CryptoError::AuthenticationFailed => -errno::EBADMSG,
+            CryptoError::UnsupportedAlgorithm => -errno::EOPNOTSUPP,
+        }
+    }
+}
+
+impl fmt::Display for CryptoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CryptoError::InvalidKeyLength => f.write_str("invalid key length"),
+            CryptoError::InvalidIvLength => f.write_str("invalid IV length"),
+            CryptoError::DataNotBlockAligned => f.write_str("data not block aligned"),
+            CryptoError::AuthenticationFailed => f.write_str("authentication failed"),
+            CryptoError::UnsupportedAlgorithm => f.write_str("unsupported algorithm"),
+            CryptoError::Busy => f.write_str("accelerator busy"),
+            CryptoError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for CryptoError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            CryptoError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(CryptoError, impl_error);
+
+impl PeripheralError for CryptoError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a CryptoError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a CryptoError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[CryptoError] = &[
+        CryptoError::InvalidKeyLength,
+        CryptoError::InvalidIvLength,
+        CryptoError::DataNotBlockAligned,
+        CryptoError::AuthenticationFailed,
+        CryptoError::UnsupportedAlgorithm,
+        CryptoError::Busy,
+        CryptoError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(CryptoError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (CryptoError::InvalidKeyLength, "invalid key length"),
+            (CryptoError::InvalidIvLength, "invalid IV length"),
+            (CryptoError::DataNotBlockAligned, "data not block aligned"),
+            (CryptoError::AuthenticationFailed, "authentication failed"),
+            (CryptoError::UnsupportedAlgorithm, "unsupported algorithm"),
+            (CryptoError::Busy, "accelerator busy"),
+            (
+                CryptoError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(retryable, [&CryptoError::Busy]);
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(
+            codes,
+            [0x1401, 0x1402, 0x1403, 0x1404, 0x1405, 0x1406, 0x0001,]
+        );
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Fatal,
+                Severity::Fatal,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Fatal,
+                Severity::Recoverable,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "crypto");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(millis, [None, None, None, None, None, Some(1), None,]);
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::EINVAL,
+                -errno::EINVAL,
+                -errno::EINVAL,
+                -errno::EBADMSG,
+                -errno::EOPNOTSUPP,
+                -errno::EBUSY,
+                -errno::EIO,
+            ]
+        );
+    }
+}
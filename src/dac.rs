@@ -0,0 +1,322 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// A DAC specific error.
+///
+/// This error type contains errors specific to DAC (Digital-to-Analog Converter) peripherals. Also
+/// it has an `Impl` kind to pass through implementation specific errors occurring while trying to
+/// use a DAC peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum DacError {
+    /// The output buffer required to drive the load is disabled
+    OutputBufferDisabled,
+    /// The requested output value exceeds the resolution of the converter
+    ValueOutOfRange,
+    /// A triggered conversion was requested without a configured trigger source
+    TriggerNotConfigured,
+    /// The DMA did not provide new data in time for the next conversion
+    DmaUnderrun,
+    /// The requested wave generation mode is not supported by the peripheral
+    WaveGenerationUnsupported,
+    /// The requested output channel does not exist or is not available
+    ChannelUnavailable,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl DacError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `DmaUnderrun` is transient and thus retryable. `Impl` errors are retryable if the wrapped
+    /// [`ImplError`] is. All other kinds are hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            DacError::DmaUnderrun => true,
+            DacError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`]. `DmaUnderrun` suggests 1 ms, all
+    /// other kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            DacError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`DacError::Impl`] and `false` for the DAC specific kinds, e.g.
+    /// [`DacError::OutputBufferDisabled`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, DacError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`DacError::Impl`] with `f`.
+    ///
+    /// The DAC specific kinds, e.g. [`DacError::OutputBufferDisabled`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            DacError::Impl(e) => DacError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// DAC errors use the high byte `0x09` and the index of the error kind as low byte, e.g.
+    /// `0x0901` for `OutputBufferDisabled`. See the [crate level documentation](crate#error-codes)
+    /// for details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            DacError::OutputBufferDisabled => 0x0901,
+            DacError::ValueOutOfRange => 0x0902,
+            DacError::TriggerNotConfigured => 0x0903,
+            DacError::DmaUnderrun => 0x0904,
+            DacError::WaveGenerationUnsupported => 0x0905,
+            DacError::ChannelUnavailable => 0x0906,
+            DacError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`DacError::to_code`], e.g. `0x0901` to
+    /// [`DacError::OutputBufferDisabled`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the DAC error kinds nor to an
+    /// [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x0901 => Some(DacError::OutputBufferDisabled),
+            0x0902 => Some(DacError::ValueOutOfRange),
+            0x0903 => Some(DacError::TriggerNotConfigured),
+            0x0904 => Some(DacError::DmaUnderrun),
+            0x0905 => Some(DacError::WaveGenerationUnsupported),
+            0x0906 => Some(DacError::ChannelUnavailable),
+            0x0000..=0x00ff => ImplError::from_code(code).map(DacError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. `WaveGenerationUnsupported` and `ChannelUnavailable`
+    /// are [`Severity::Fatal`]. All other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            DacError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            DacError::WaveGenerationUnsupported | DacError::ChannelUnavailable => Severity::Fatal,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"dac"`, the stable lowercase name of the DAC peripheral, for all kinds including
+    /// `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "dac"
+    }
+
+    /// Returns the name of the error kind, e.g. `"OutputBufferDisabled"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            DacError::OutputBufferDisabled => "OutputBufferDisabled",
+            DacError::ValueOutOfRange => "ValueOutOfRange",
+            DacError::TriggerNotConfigured => "TriggerNotConfigured",
+            DacError::DmaUnderrun => "DmaUnderrun",
+            DacError::WaveGenerationUnsupported => "WaveGenerationUnsupported",
+            DacError::ChannelUnavailable => "ChannelUnavailable",
+            DacError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `ChannelUnavailable` maps to
+    /// `-ENODEV`. `TriggerNotConfigured` maps to `-EINVAL`. `ValueOutOfRange` maps to `-ERANGE`.
+    /// All other kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            DacError::Impl(e) => e.to_errno(),
+            DacError::ChannelUnavailable => -errno::ENODEV,
+            DacError::TriggerNotConfigured => -errno::EINVAL,
+            DacError::ValueOutOfRange => -errno::ERANGE,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for DacError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DacError::OutputBufferDisabled => f.write_str("output buffer disabled"),
+            DacError::ValueOutOfRange => f.write_str("value out of range"),
+            DacError::TriggerNotConfigured => f.write_str("trigger not configured"),
+            DacError::DmaUnderrun => f.write_str("DMA underrun"),
+            DacError::WaveGenerationUnsupported => f.write_str("wave generation unsupported"),
+            DacError::ChannelUnavailable => f.write_str("channel unavailable"),
+            DacError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for DacError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            DacError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(DacError, impl_error);
+
+impl PeripheralError for DacError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a DacError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a DacError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[DacError] = &[
+        DacError::OutputBufferDisabled,
+        DacError::ValueOutOfRange,
+        DacError::TriggerNotConfigured,
+        DacError::DmaUnderrun,
+        DacError::WaveGenerationUnsupported,
+        DacError::ChannelUnavailable,
+        DacError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(DacError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (DacError::OutputBufferDisabled, "output buffer disabled"),
+            (DacError::ValueOutOfRange, "value out of range"),
+            (DacError::TriggerNotConfigured, "trigger not configured"),
+            (DacError::DmaUnderrun, "DMA underrun"),
+            (
+                DacError::WaveGenerationUnsupported,
+                "wave generation unsupported",
+            ),
+            (DacError::ChannelUnavailable, "channel unavailable"),
+            (
+                DacError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(retryable, [&DacError::DmaUnderrun]);
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(
+            codes,
+            [0x0901, 0x0902, 0x0903, 0x0904, 0x0905, 0x0906, 0x0001,]
+        );
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Recoverable,
+                Severity::Fatal,
+                Severity::Fatal,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "dac");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(millis, [None, None, None, Some(1), None, None, None,]);
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::EIO,
+                -errno::ERANGE,
+                -errno::EINVAL,
+                -errno::EIO,
+                -errno::EIO,
+                -errno::ENODEV,
+                -errno::EIO,
+            ]
+        );
+    }
+}
@@ -0,0 +1,309 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// A display specific error.
+///
+/// This error type contains errors specific to display controller peripherals. Also it has an
+/// `Impl` kind to pass through implementation specific errors occurring while trying to use a
+/// display peripheral.
+///
+/// Unlike the errors of the underlying bus peripherals (e.g. `SpiError` or `I2cError`) these kinds
+/// model faults of the display controller itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum DisplayError {
+    /// The requested pixel or area is outside of the display area
+    OutOfBounds,
+    /// The provided frame buffer is too small for the requested area
+    BufferTooSmall,
+    /// The pixel or color format is not supported by the controller
+    InvalidFormat,
+    /// Writing data or commands to the controller via the underlying bus failed
+    BusWriteError,
+    /// The controller rejected or failed to execute a command
+    CommandError,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl DisplayError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `BusWriteError` is transient and thus retryable. `Impl` errors are retryable if the wrapped
+    /// [`ImplError`] is. All other kinds are hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            DisplayError::BusWriteError => true,
+            DisplayError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`]. `BusWriteError` suggests 1 ms,
+    /// all other kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            DisplayError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`DisplayError::Impl`] and `false` for the display specific kinds, e.g.
+    /// [`DisplayError::OutOfBounds`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, DisplayError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`DisplayError::Impl`] with `f`.
+    ///
+    /// The display specific kinds, e.g. [`DisplayError::OutOfBounds`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            DisplayError::Impl(e) => DisplayError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// Display errors use the high byte `0x27` and the index of the error kind as low byte, e.g.
+    /// `0x2701` for `OutOfBounds`. See the [crate level documentation](crate#error-codes) for
+    /// details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            DisplayError::OutOfBounds => 0x2701,
+            DisplayError::BufferTooSmall => 0x2702,
+            DisplayError::InvalidFormat => 0x2703,
+            DisplayError::BusWriteError => 0x2704,
+            DisplayError::CommandError => 0x2705,
+            DisplayError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`DisplayError::to_code`], e.g. `0x2701` to
+    /// [`DisplayError::OutOfBounds`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the display error kinds nor to
+    /// an [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x2701 => Some(DisplayError::OutOfBounds),
+            0x2702 => Some(DisplayError::BufferTooSmall),
+            0x2703 => Some(DisplayError::InvalidFormat),
+            0x2704 => Some(DisplayError::BusWriteError),
+            0x2705 => Some(DisplayError::CommandError),
+            0x0000..=0x00ff => ImplError::from_code(code).map(DisplayError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. `InvalidFormat` is [`Severity::Fatal`]. All other kinds
+    /// are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            DisplayError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            DisplayError::InvalidFormat => Severity::Fatal,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"display"`, the stable lowercase name of the display peripheral, for all kinds
+    /// including `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "display"
+    }
+
+    /// Returns the name of the error kind, e.g. `"OutOfBounds"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            DisplayError::OutOfBounds => "OutOfBounds",
+            DisplayError::BufferTooSmall => "BufferTooSmall",
+            DisplayError::InvalidFormat => "InvalidFormat",
+            DisplayError::BusWriteError => "BusWriteError",
+            DisplayError::CommandError => "CommandError",
+            DisplayError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `InvalidFormat` maps to
+    /// `-EINVAL`. `OutOfBounds` maps to `-ERANGE`. `BufferTooSmall` maps to `-ENOBUFS`. All other
+    /// kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            DisplayError::Impl(e) => e.to_errno(),
+            DisplayError::InvalidFormat => -errno::EINVAL,
+            DisplayError::OutOfBounds => -errno::ERANGE,
+            DisplayError::BufferTooSmall => -errno::ENOBUFS,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for DisplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DisplayError::OutOfBounds => f.write_str("coordinates out of bounds"),
+            DisplayError::BufferTooSmall => f.write_str("buffer too small"),
+            DisplayError::InvalidFormat => f.write_str("invalid pixel format"),
+            DisplayError::BusWriteError => f.write_str("bus write error"),
+            DisplayError::CommandError => f.write_str("command error"),
+            DisplayError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for DisplayError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            DisplayError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(DisplayError, impl_error);
+
+impl PeripheralError for DisplayError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a DisplayError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a DisplayError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[DisplayError] = &[
+        DisplayError::OutOfBounds,
+        DisplayError::BufferTooSmall,
+        DisplayError::InvalidFormat,
+        DisplayError::BusWriteError,
+        DisplayError::CommandError,
+        DisplayError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(DisplayError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (DisplayError::OutOfBounds, "coordinates out of bounds"),
+            (DisplayError::BufferTooSmall, "buffer too small"),
+            (DisplayError::InvalidFormat, "invalid pixel format"),
+            (DisplayError::BusWriteError, "bus write error"),
+            (DisplayError::CommandError, "command error"),
+            (
+                DisplayError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(retryable, [&DisplayError::BusWriteError]);
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(codes, [0x2701, 0x2702, 0x2703, 0x2704, 0x2705, 0x0001,]);
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Fatal,
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "display");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(millis, [None, None, None, Some(1), None, None,]);
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::ERANGE,
+                -errno::ENOBUFS,
+                -errno::EINVAL,
+                -errno::EIO,
+                -errno::EIO,
+                -errno::EIO,
+            ]
+        );
+    }
+}
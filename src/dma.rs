@@ -0,0 +1,333 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// A DMA specific error.
+///
+/// This error type contains errors specific to DMA (Direct Memory Access) peripherals. Also it has
+/// an `Impl` kind to pass through implementation specific errors occurring while trying to use a
+/// DMA peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum DmaError {
+    /// A bus error occurred during a transfer, e.g. due to an invalid source or destination address
+    TransferError,
+    /// The FIFO overran or underran during a transfer
+    FifoError,
+    /// A request occurred before the previous data was transferred in direct mode
+    DirectModeError,
+    /// The stream or channel configuration is inconsistent
+    ConfigurationError,
+    /// The channel is currently used by another transfer
+    ChannelBusy,
+    /// The requested channel does not exist or cannot serve the requested peripheral
+    ChannelUnavailable,
+    /// The buffer exceeds the maximum number of data items of a single transfer
+    BufferTooLong,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl DmaError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `FifoError` and `ChannelBusy` are transient and thus retryable. `Impl` errors are retryable
+    /// if the wrapped [`ImplError`] is. All other kinds are hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            DmaError::FifoError | DmaError::ChannelBusy => true,
+            DmaError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`]. `FifoError` and `ChannelBusy`
+    /// suggest 1 ms, all other kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            DmaError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`DmaError::Impl`] and `false` for the DMA specific kinds, e.g.
+    /// [`DmaError::TransferError`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, DmaError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`DmaError::Impl`] with `f`.
+    ///
+    /// The DMA specific kinds, e.g. [`DmaError::TransferError`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            DmaError::Impl(e) => DmaError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// DMA errors use the high byte `0x0f` and the index of the error kind as low byte, e.g.
+    /// `0x0f01` for `TransferError`. See the [crate level documentation](crate#error-codes) for
+    /// details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            DmaError::TransferError => 0x0f01,
+            DmaError::FifoError => 0x0f02,
+            DmaError::DirectModeError => 0x0f03,
+            DmaError::ConfigurationError => 0x0f04,
+            DmaError::ChannelBusy => 0x0f05,
+            DmaError::ChannelUnavailable => 0x0f06,
+            DmaError::BufferTooLong => 0x0f07,
+            DmaError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`DmaError::to_code`], e.g. `0x0f01` to
+    /// [`DmaError::TransferError`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the DMA error kinds nor to an
+    /// [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x0f01 => Some(DmaError::TransferError),
+            0x0f02 => Some(DmaError::FifoError),
+            0x0f03 => Some(DmaError::DirectModeError),
+            0x0f04 => Some(DmaError::ConfigurationError),
+            0x0f05 => Some(DmaError::ChannelBusy),
+            0x0f06 => Some(DmaError::ChannelUnavailable),
+            0x0f07 => Some(DmaError::BufferTooLong),
+            0x0000..=0x00ff => ImplError::from_code(code).map(DmaError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. `ChannelUnavailable` is [`Severity::Fatal`]. All other
+    /// kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            DmaError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            DmaError::ChannelUnavailable => Severity::Fatal,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"dma"`, the stable lowercase name of the DMA peripheral, for all kinds including
+    /// `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "dma"
+    }
+
+    /// Returns the name of the error kind, e.g. `"TransferError"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            DmaError::TransferError => "TransferError",
+            DmaError::FifoError => "FifoError",
+            DmaError::DirectModeError => "DirectModeError",
+            DmaError::ConfigurationError => "ConfigurationError",
+            DmaError::ChannelBusy => "ChannelBusy",
+            DmaError::ChannelUnavailable => "ChannelUnavailable",
+            DmaError::BufferTooLong => "BufferTooLong",
+            DmaError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `ChannelBusy` maps to `-EBUSY`.
+    /// `ChannelUnavailable` maps to `-ENODEV`. `ConfigurationError` maps to `-EINVAL`.
+    /// `BufferTooLong` maps to `-EMSGSIZE`. All other kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            DmaError::Impl(e) => e.to_errno(),
+            DmaError::ChannelBusy => -errno::EBUSY,
+            DmaError::ChannelUnavailable => -errno::ENODEV,
+            DmaError::ConfigurationError => -errno::EINVAL,
+            DmaError::BufferTooLong => -errno::EMSGSIZE,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for DmaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DmaError::TransferError => f.write_str("transfer error"),
+            DmaError::FifoError => f.write_str("FIFO error"),
+            DmaError::DirectModeError => f.write_str("direct mode error"),
+            DmaError::ConfigurationError => f.write_str("configuration error"),
+            DmaError::ChannelBusy => f.write_str("channel busy"),
+            DmaError::ChannelUnavailable => f.write_str("channel unavailable"),
+            DmaError::BufferTooLong => f.write_str("buffer too long"),
+            DmaError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for DmaError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            DmaError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(DmaError, impl_error);
+
+impl PeripheralError for DmaError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a DmaError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a DmaError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[DmaError] = &[
+        DmaError::TransferError,
+        DmaError::FifoError,
+        DmaError::DirectModeError,
+        DmaError::ConfigurationError,
+        DmaError::ChannelBusy,
+        DmaError::ChannelUnavailable,
+        DmaError::BufferTooLong,
+        DmaError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(DmaError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (DmaError::TransferError, "transfer error"),
+            (DmaError::FifoError, "FIFO error"),
+            (DmaError::DirectModeError, "direct mode error"),
+            (DmaError::ConfigurationError, "configuration error"),
+            (DmaError::ChannelBusy, "channel busy"),
+            (DmaError::ChannelUnavailable, "channel unavailable"),
+            (DmaError::BufferTooLong, "buffer too long"),
+            (
+                DmaError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(retryable, [&DmaError::FifoError, &DmaError::ChannelBusy]);
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(
+            codes,
+            [0x0f01, 0x0f02, 0x0f03, 0x0f04, 0x0f05, 0x0f06, 0x0f07, 0x0001,]
+        );
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Degraded,
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Recoverable,
+                Severity::Fatal,
+                Severity::Degraded,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "dma");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(
+            millis,
+            [None, Some(1), None, None, Some(1), None, None, None,]
+        );
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::EIO,
+                -errno::EIO,
+                -errno::EIO,
+                -errno::EINVAL,
+                -errno::EBUSY,
+                -errno::ENODEV,
+                -errno::EMSGSIZE,
+                -errno::EIO,
+            ]
+        );
+    }
+}
@@ -0,0 +1,294 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// An encoder specific error.
+///
+/// This error type contains errors specific to quadrature encoder peripherals. Also it has an
+/// `Impl` kind to pass through implementation specific errors occurring while trying to use an
+/// encoder peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum EncoderError {
+    /// The position counter overflowed or underflowed between two reads
+    CountOverflow,
+    /// The direction could not be determined, e.g. because both signals changed at once
+    DirectionAmbiguous,
+    /// The index pulse was not detected within one revolution
+    IndexPulseMissing,
+    /// The requested encoder input or timer channel is not available
+    ChannelUnavailable,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl EncoderError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `DirectionAmbiguous` is transient and thus retryable. `Impl` errors are retryable if the
+    /// wrapped [`ImplError`] is. All other kinds are hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            EncoderError::DirectionAmbiguous => true,
+            EncoderError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`]. `DirectionAmbiguous` suggests 1
+    /// ms, all other kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            EncoderError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`EncoderError::Impl`] and `false` for the encoder specific kinds, e.g.
+    /// [`EncoderError::CountOverflow`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, EncoderError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`EncoderError::Impl`] with `f`.
+    ///
+    /// The encoder specific kinds, e.g. [`EncoderError::CountOverflow`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            EncoderError::Impl(e) => EncoderError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// Encoder errors use the high byte `0x1b` and the index of the error kind as low byte, e.g.
+    /// `0x1b01` for `CountOverflow`. See the [crate level documentation](crate#error-codes) for
+    /// details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            EncoderError::CountOverflow => 0x1b01,
+            EncoderError::DirectionAmbiguous => 0x1b02,
+            EncoderError::IndexPulseMissing => 0x1b03,
+            EncoderError::ChannelUnavailable => 0x1b04,
+            EncoderError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`EncoderError::to_code`], e.g. `0x1b01` to
+    /// [`EncoderError::CountOverflow`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the encoder error kinds nor to
+    /// an [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x1b01 => Some(EncoderError::CountOverflow),
+            0x1b02 => Some(EncoderError::DirectionAmbiguous),
+            0x1b03 => Some(EncoderError::IndexPulseMissing),
+            0x1b04 => Some(EncoderError::ChannelUnavailable),
+            0x0000..=0x00ff => ImplError::from_code(code).map(EncoderError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. `ChannelUnavailable` is [`Severity::Fatal`]. All other
+    /// kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            EncoderError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            EncoderError::ChannelUnavailable => Severity::Fatal,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"encoder"`, the stable lowercase name of the encoder peripheral, for all kinds
+    /// including `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "encoder"
+    }
+
+    /// Returns the name of the error kind, e.g. `"CountOverflow"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            EncoderError::CountOverflow => "CountOverflow",
+            EncoderError::DirectionAmbiguous => "DirectionAmbiguous",
+            EncoderError::IndexPulseMissing => "IndexPulseMissing",
+            EncoderError::ChannelUnavailable => "ChannelUnavailable",
+            EncoderError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `ChannelUnavailable` maps to
+    /// `-ENODEV`. `CountOverflow` maps to `-EOVERFLOW`. All other kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            EncoderError::Impl(e) => e.to_errno(),
+            EncoderError::ChannelUnavailable => -errno::ENODEV,
+            EncoderError::CountOverflow => -errno::EOVERFLOW,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for EncoderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncoderError::CountOverflow => f.write_str("count overflow"),
+            EncoderError::DirectionAmbiguous => f.write_str("direction ambiguous"),
+            EncoderError::IndexPulseMissing => f.write_str("index pulse missing"),
+            EncoderError::ChannelUnavailable => f.write_str("channel unavailable"),
+            EncoderError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for EncoderError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            EncoderError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(EncoderError, impl_error);
+
+impl PeripheralError for EncoderError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a EncoderError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a EncoderError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[EncoderError] = &[
+        EncoderError::CountOverflow,
+        EncoderError::DirectionAmbiguous,
+        EncoderError::IndexPulseMissing,
+        EncoderError::ChannelUnavailable,
+        EncoderError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(EncoderError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (EncoderError::CountOverflow, "count overflow"),
+            (EncoderError::DirectionAmbiguous, "direction ambiguous"),
+            (EncoderError::IndexPulseMissing, "index pulse missing"),
+            (EncoderError::ChannelUnavailable, "channel unavailable"),
+            (
+                EncoderError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(retryable, [&EncoderError::DirectionAmbiguous]);
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(codes, [0x1b01, 0x1b02, 0x1b03, 0x1b04, 0x0001,]);
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Degraded,
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Fatal,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "encoder");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(millis, [None, Some(1), None, None, None,]);
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::EOVERFLOW,
+                -errno::EIO,
+                -errno::EIO,
+                -errno::ENODEV,
+                -errno::EIO,
+            ]
+        );
+    }
+}
@@ -0,0 +1,310 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// An environmental sensor specific error.
+///
+/// This error type contains errors specific to environmental (humidity/temperature/pressure) sensor
+/// peripherals. Also it has an `Impl` kind to pass through implementation specific errors occurring
+/// while trying to use an environmental sensor peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum EnvSensorError {
+    /// The calibration data has not been read or is invalid
+    NotCalibrated,
+    /// The measurement did not complete in time
+    MeasurementTimeout,
+    /// The measured value is outside of the range supported by the sensor
+    ValueOutOfRange,
+    /// The built-in self test of the sensor failed
+    SelfTestFailed,
+    /// The sensor is busy with a measurement
+    Busy,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl EnvSensorError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `MeasurementTimeout` and `Busy` are transient and thus retryable. `Impl` errors are
+    /// retryable if the wrapped [`ImplError`] is. All other kinds are hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            EnvSensorError::MeasurementTimeout | EnvSensorError::Busy => true,
+            EnvSensorError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`]. `MeasurementTimeout` and `Busy`
+    /// suggest 1 ms, all other kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            EnvSensorError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`EnvSensorError::Impl`] and `false` for the environmental sensor
+    /// specific kinds, e.g. [`EnvSensorError::NotCalibrated`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, EnvSensorError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`EnvSensorError::Impl`] with `f`.
+    ///
+    /// The environmental sensor specific kinds, e.g. [`EnvSensorError::NotCalibrated`], are
+    /// returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            EnvSensorError::Impl(e) => EnvSensorError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// Environmental sensor errors use the high byte `0x25` and the index of the error kind as low
+    /// byte, e.g. `0x2501` for `NotCalibrated`. See the [crate level
+    /// documentation](crate#error-codes) for details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            EnvSensorError::NotCalibrated => 0x2501,
+            EnvSensorError::MeasurementTimeout => 0x2502,
+            EnvSensorError::ValueOutOfRange => 0x2503,
+            EnvSensorError::SelfTestFailed => 0x2504,
+            EnvSensorError::Busy => 0x2505,
+            EnvSensorError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`EnvSensorError::to_code`], e.g. `0x2501` to
+    /// [`EnvSensorError::NotCalibrated`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the environmental sensor error
+    /// kinds nor to an [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x2501 => Some(EnvSensorError::NotCalibrated),
+            0x2502 => Some(EnvSensorError::MeasurementTimeout),
+            0x2503 => Some(EnvSensorError::ValueOutOfRange),
+            0x2504 => Some(EnvSensorError::SelfTestFailed),
+            0x2505 => Some(EnvSensorError::Busy),
+            0x0000..=0x00ff => ImplError::from_code(code).map(EnvSensorError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. `SelfTestFailed` is [`Severity::Fatal`]. All other
+    /// kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            EnvSensorError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            EnvSensorError::SelfTestFailed => Severity::Fatal,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"env_sensor"`, the stable lowercase name of the environmental sensor peripheral,
+    /// for all kinds including `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "env_sensor"
+    }
+
+    /// Returns the name of the error kind, e.g. `"NotCalibrated"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            EnvSensorError::NotCalibrated => "NotCalibrated",
+            EnvSensorError::MeasurementTimeout => "MeasurementTimeout",
+            EnvSensorError::ValueOutOfRange => "ValueOutOfRange",
+            EnvSensorError::SelfTestFailed => "SelfTestFailed",
+            EnvSensorError::Busy => "Busy",
+            EnvSensorError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `Busy` maps to `-EBUSY`.
+    /// `ValueOutOfRange` maps to `-ERANGE`. `MeasurementTimeout` maps to `-ETIMEDOUT`. All other
+    /// kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            EnvSensorError::Impl(e) => e.to_errno(),
+            EnvSensorError::Busy => -errno::EBUSY,
+            EnvSensorError::ValueOutOfRange => -errno::ERANGE,
+            EnvSensorError::MeasurementTimeout => -errno::ETIMEDOUT,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for EnvSensorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnvSensorError::NotCalibrated => f.write_str("sensor not calibrated"),
+            EnvSensorError::MeasurementTimeout => f.write_str("measurement timed out"),
+            EnvSensorError::ValueOutOfRange => f.write_str("value out of range"),
+            EnvSensorError::SelfTestFailed => f.write_str("self test failed"),
+            EnvSensorError::Busy => f.write_str("sensor busy"),
+            EnvSensorError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for EnvSensorError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            EnvSensorError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(EnvSensorError, impl_error);
+
+impl PeripheralError for EnvSensorError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a EnvSensorError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a EnvSensorError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[EnvSensorError] = &[
+        EnvSensorError::NotCalibrated,
+        EnvSensorError::MeasurementTimeout,
+        EnvSensorError::ValueOutOfRange,
+        EnvSensorError::SelfTestFailed,
+        EnvSensorError::Busy,
+        EnvSensorError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(EnvSensorError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (EnvSensorError::NotCalibrated, "sensor not calibrated"),
+            (EnvSensorError::MeasurementTimeout, "measurement timed out"),
+            (EnvSensorError::ValueOutOfRange, "value out of range"),
+            (EnvSensorError::SelfTestFailed, "self test failed"),
+            (EnvSensorError::Busy, "sensor busy"),
+            (
+                EnvSensorError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(
+            retryable,
+            [&EnvSensorError::MeasurementTimeout, &EnvSensorError::Busy]
+        );
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(codes, [0x2501, 0x2502, 0x2503, 0x2504, 0x2505, 0x0001,]);
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Degraded,
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Fatal,
+                Severity::Recoverable,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "env_sensor");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(millis, [None, Some(1), None, None, Some(1), None,]);
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::EIO,
+                -errno::ETIMEDOUT,
+                -errno::ERANGE,
+                -errno::EIO,
+                -errno::EBUSY,
+                -errno::EIO,
+            ]
+        );
+    }
+}
@@ -0,0 +1,35 @@
+//! Conventional POSIX `errno` values as used by Linux and most POSIX-like RTOSes.
+//!
+//! Not all of them are used with every combination of peripheral features.
+
+#![allow(dead_code)]
+
+pub(crate) const ENOENT: i32 = 2;
+pub(crate) const EIO: i32 = 5;
+pub(crate) const ENXIO: i32 = 6;
+pub(crate) const EAGAIN: i32 = 11;
+pub(crate) const ENOMEM: i32 = 12;
+pub(crate) const EACCES: i32 = 13;
+pub(crate) const EBUSY: i32 = 16;
+pub(crate) const ENODEV: i32 = 19;
+pub(crate) const EINVAL: i32 = 22;
+pub(crate) const EPIPE: i32 = 32;
+pub(crate) const ERANGE: i32 = 34;
+pub(crate) const ENODATA: i32 = 61;
+pub(crate) const EPROTO: i32 = 71;
+pub(crate) const EBADMSG: i32 = 74;
+pub(crate) const EOVERFLOW: i32 = 75;
+pub(crate) const EILSEQ: i32 = 84;
+pub(crate) const EMSGSIZE: i32 = 90;
+pub(crate) const EOPNOTSUPP: i32 = 95;
+pub(crate) const ENETDOWN: i32 = 100;
+pub(crate) const ECONNRESET: i32 = 104;
+pub(crate) const ENOBUFS: i32 = 105;
+pub(crate) const ENOTCONN: i32 = 107;
+pub(crate) const ESHUTDOWN: i32 = 108;
+pub(crate) const ETIMEDOUT: i32 = 110;
+pub(crate) const ECONNREFUSED: i32 = 111;
+pub(crate) const EHOSTUNREACH: i32 = 113;
+pub(crate) const EALREADY: i32 = 114;
+pub(crate) const ENOMEDIUM: i32 = 123;
+pub(crate) const ECANCELED: i32 = 125;
@@ -0,0 +1,371 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// An Ethernet specific error.
+///
+/// This error type contains errors specific to Ethernet MAC peripherals. Also it has an `Impl` kind
+/// to pass through implementation specific errors occurring while trying to use an Ethernet
+/// peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum EthernetError {
+    /// All transmit buffers are in use
+    TxBufferFull,
+    /// No received frame is available
+    RxBufferEmpty,
+    /// The PHY reports that the link is down
+    LinkDown,
+    /// Communication with the PHY failed
+    PhyError,
+    /// The CRC of a received frame does not match the received data
+    CrcError,
+    /// A collision was detected after the collision window, e.g. due to a duplex mismatch
+    LateCollision,
+    /// The frame exceeds the maximum frame or buffer size
+    BufferTooLong,
+    /// No DMA descriptors are available to process the frame
+    NoDescriptorsAvailable,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl EthernetError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `TxBufferFull`, `RxBufferEmpty`, `CrcError` and `NoDescriptorsAvailable` are transient and
+    /// thus retryable. `Impl` errors are retryable if the wrapped [`ImplError`] is. All other kinds
+    /// are hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            EthernetError::TxBufferFull
+            | EthernetError::RxBufferEmpty
+            | EthernetError::CrcError
+            | EthernetError::NoDescriptorsAvailable => true,
+            EthernetError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`]. `TxBufferFull`, `RxBufferEmpty`,
+    /// `CrcError` and `NoDescriptorsAvailable` suggest 1 ms, all other kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            EthernetError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`EthernetError::Impl`] and `false` for the Ethernet specific kinds, e.g.
+    /// [`EthernetError::TxBufferFull`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, EthernetError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`EthernetError::Impl`] with `f`.
+    ///
+    /// The Ethernet specific kinds, e.g. [`EthernetError::TxBufferFull`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            EthernetError::Impl(e) => EthernetError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// Ethernet errors use the high byte `0x10` and the index of the error kind as low byte, e.g.
+    /// `0x1001` for `TxBufferFull`. See the [crate level documentation](crate#error-codes) for
+    /// details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            EthernetError::TxBufferFull => 0x1001,
+            EthernetError::RxBufferEmpty => 0x1002,
+            EthernetError::LinkDown => 0x1003,
+            EthernetError::PhyError => 0x1004,
+            EthernetError::CrcError => 0x1005,
+            EthernetError::LateCollision => 0x1006,
+            EthernetError::BufferTooLong => 0x1007,
+            EthernetError::NoDescriptorsAvailable => 0x1008,
+            EthernetError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`EthernetError::to_code`], e.g. `0x1001` to
+    /// [`EthernetError::TxBufferFull`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the Ethernet error kinds nor
+    /// to an [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x1001 => Some(EthernetError::TxBufferFull),
+            0x1002 => Some(EthernetError::RxBufferEmpty),
+            0x1003 => Some(EthernetError::LinkDown),
+            0x1004 => Some(EthernetError::PhyError),
+            0x1005 => Some(EthernetError::CrcError),
+            0x1006 => Some(EthernetError::LateCollision),
+            0x1007 => Some(EthernetError::BufferTooLong),
+            0x1008 => Some(EthernetError::NoDescriptorsAvailable),
+            0x0000..=0x00ff => ImplError::from_code(code).map(EthernetError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. All other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            EthernetError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"ethernet"`, the stable lowercase name of the Ethernet peripheral, for all kinds
+    /// including `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "ethernet"
+    }
+
+    /// Returns the name of the error kind, e.g. `"TxBufferFull"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            EthernetError::TxBufferFull => "TxBufferFull",
+            EthernetError::RxBufferEmpty => "RxBufferEmpty",
+            EthernetError::LinkDown => "LinkDown",
+            EthernetError::PhyError => "PhyError",
+            EthernetError::CrcError => "CrcError",
+            EthernetError::LateCollision => "LateCollision",
+            EthernetError::BufferTooLong => "BufferTooLong",
+            EthernetError::NoDescriptorsAvailable => "NoDescriptorsAvailable",
+            EthernetError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `RxBufferEmpty` maps to
+    /// `-ENODATA`. `CrcError` maps to `-EILSEQ`. `BufferTooLong` maps to `-EMSGSIZE`. `LinkDown`
+    /// maps to `-ENETDOWN`. `TxBufferFull` and `NoDescriptorsAvailable` map to `-ENOBUFS`. All
+    /// other kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            EthernetError::Impl(e) => e.to_errno(),
+            EthernetError::RxBufferEmpty => -errno::ENODATA,
+            EthernetError::CrcError => -errno::EILSEQ,
+            EthernetError::BufferTooLong => -errno::EMSGSIZE,
+            EthernetError::LinkDown => -errno::ENETDOWN,
+            EthernetError::TxBufferFull | EthernetError::NoDescriptorsAvailable => -errno::ENOBUFS,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for EthernetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EthernetError::TxBufferFull => f.write_str("transmit buffer full"),
+            EthernetError::RxBufferEmpty => f.write_str("receive buffer empty"),
+            EthernetError::LinkDown => f.write_str("link down"),
+            EthernetError::PhyError => f.write_str("PHY error"),
+            EthernetError::CrcError => f.write_str("CRC does not match the received data"),
+            EthernetError::LateCollision => f.write_str("late collision"),
+            EthernetError::BufferTooLong => f.write_str("buffer too long"),
+            EthernetError::NoDescriptorsAvailable => f.write_str("no descriptors available"),
+            EthernetError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for EthernetError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            EthernetError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(EthernetError, impl_error);
+
+impl PeripheralError for EthernetError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a EthernetError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a EthernetError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[EthernetError] = &[
+        EthernetError::TxBufferFull,
+        EthernetError::RxBufferEmpty,
+        EthernetError::LinkDown,
+        EthernetError::PhyError,
+        EthernetError::CrcError,
+        EthernetError::LateCollision,
+        EthernetError::BufferTooLong,
+        EthernetError::NoDescriptorsAvailable,
+        EthernetError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(EthernetError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (EthernetError::TxBufferFull, "transmit buffer full"),
+            (EthernetError::RxBufferEmpty, "receive buffer empty"),
+            (EthernetError::LinkDown, "link down"),
+            (EthernetError::PhyError, "PHY error"),
+            (
+                EthernetError::CrcError,
+                "CRC does not match the received data",
+            ),
+            (EthernetError::LateCollision, "late collision"),
+            (EthernetError::BufferTooLong, "buffer too long"),
+            (
+                EthernetError::NoDescriptorsAvailable,
+                "no descriptors available",
+            ),
+            (
+                EthernetError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(
+            retryable,
+            [
+                &EthernetError::TxBufferFull,
+                &EthernetError::RxBufferEmpty,
+                &EthernetError::CrcError,
+                &EthernetError::NoDescriptorsAvailable
+            ]
+        );
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(
+            codes,
+            [0x1001, 0x1002, 0x1003, 0x1004, 0x1005, 0x1006, 0x1007, 0x1008, 0x0001,]
+        );
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Recoverable,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "ethernet");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(
+            millis,
+            [
+                Some(1),
+                Some(1),
+                None,
+                None,
+                Some(1),
+                None,
+                None,
+                Some(1),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::ENOBUFS,
+                -errno::ENODATA,
+                -errno::ENETDOWN,
+                -errno::EIO,
+                -errno::EILSEQ,
+                -errno::EIO,
+                -errno::EMSGSIZE,
+                -errno::ENOBUFS,
+                -errno::EIO,
+            ]
+        );
+    }
+}
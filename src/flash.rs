@@ -0,0 +1,363 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// A Flash specific error.
+///
+/// This error type contains errors specific to Flash/NVM (Non-Volatile Memory) peripherals. Also it
+/// has an `Impl` kind to pass through implementation specific errors occurring while trying to use
+/// a Flash peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum FlashError {
+    /// The given address is not aligned to the required program or erase granularity
+    NotAligned {
+        /// The offending address
+        address: u32,
+    },
+    /// The given address is outside of the memory
+    OutOfBounds {
+        /// The offending address
+        address: u32,
+    },
+    /// The memory region is write protected
+    WriteProtected,
+    /// An erase operation did not complete successfully
+    EraseFailed,
+    /// A program operation did not complete successfully
+    ProgramFailed,
+    /// The memory content does not match the written data
+    VerifyFailed,
+    /// The memory controller is locked and needs to be unlocked first
+    Locked,
+    /// The memory controller is busy with another operation
+    Busy,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl FlashError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `Busy` is transient and thus retryable. `Impl` errors are retryable if the wrapped
+    /// [`ImplError`] is. All other kinds are hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            FlashError::Busy => true,
+            FlashError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`]. `Busy` suggests 1 ms, all other
+    /// kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            FlashError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`FlashError::Impl`] and `false` for the Flash specific kinds, e.g.
+    /// [`FlashError::WriteProtected`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, FlashError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`FlashError::Impl`] with `f`.
+    ///
+    /// The Flash specific kinds, e.g. [`FlashError::WriteProtected`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            FlashError::Impl(e) => FlashError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// Flash errors use the high byte `0x0e` and the index of the error kind as low byte, e.g.
+    /// `0x0e03` for `WriteProtected`. See the [crate level documentation](crate#error-codes) for
+    /// details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            FlashError::NotAligned { .. } => 0x0e01,
+            FlashError::OutOfBounds { .. } => 0x0e02,
+            FlashError::WriteProtected => 0x0e03,
+            FlashError::EraseFailed => 0x0e04,
+            FlashError::ProgramFailed => 0x0e05,
+            FlashError::VerifyFailed => 0x0e06,
+            FlashError::Locked => 0x0e07,
+            FlashError::Busy => 0x0e08,
+            FlashError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`FlashError::to_code`], e.g. `0x0e03` to
+    /// [`FlashError::WriteProtected`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the Flash error kinds nor to
+    /// an [`ImplError`]. The addresses of `NotAligned` and `OutOfBounds` are not encoded in the
+    /// code and thus restored as `0`.
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x0e01 => Some(FlashError::NotAligned { address: 0 }),
+            0x0e02 => Some(FlashError::OutOfBounds { address: 0 }),
+            0x0e03 => Some(FlashError::WriteProtected),
+            0x0e04 => Some(FlashError::EraseFailed),
+            0x0e05 => Some(FlashError::ProgramFailed),
+            0x0e06 => Some(FlashError::VerifyFailed),
+            0x0e07 => Some(FlashError::Locked),
+            0x0e08 => Some(FlashError::Busy),
+            0x0000..=0x00ff => ImplError::from_code(code).map(FlashError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. All other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            FlashError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"flash"`, the stable lowercase name of the Flash peripheral, for all kinds
+    /// including `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "flash"
+    }
+
+    /// Returns the name of the error kind, e.g. `"NotAligned"`.
+    ///
+    /// The data carried by `NotAligned` and `OutOfBounds` is not included. `Impl` errors return
+    /// `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            FlashError::NotAligned { .. } => "NotAligned",
+            FlashError::OutOfBounds { .. } => "OutOfBounds",
+            FlashError::WriteProtected => "WriteProtected",
+            FlashError::EraseFailed => "EraseFailed",
+            FlashError::ProgramFailed => "ProgramFailed",
+            FlashError::VerifyFailed => "VerifyFailed",
+            FlashError::Locked => "Locked",
+            FlashError::Busy => "Busy",
+            FlashError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `WriteProtected` and `Locked`
+    /// map to `-EACCES`. `Busy` maps to `-EBUSY`. `NotAligned` maps to `-EINVAL`. `OutOfBounds`
+    /// maps to `-ERANGE`. All other kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            FlashError::Impl(e) => e.to_errno(),
+            FlashError::WriteProtected | FlashError::Locked => -errno::EACCES,
+            FlashError::Busy => -errno::EBUSY,
+            FlashError::NotAligned { .. } => -errno::EINVAL,
+            FlashError::OutOfBounds { .. } => -errno::ERANGE,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for FlashError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FlashError::NotAligned { address } => {
+                write!(f, "address {:#010x} is not aligned", address)
+            }
+            FlashError::OutOfBounds { address } => {
+                write!(f, "address {:#010x} is out of bounds", address)
+            }
+            FlashError::WriteProtected => f.write_str("memory is write protected"),
+            FlashError::EraseFailed => f.write_str("erase failed"),
+            FlashError::ProgramFailed => f.write_str("program failed"),
+            FlashError::VerifyFailed => f.write_str("verification failed"),
+            FlashError::Locked => f.write_str("memory controller locked"),
+            FlashError::Busy => f.write_str("memory controller busy"),
+            FlashError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for FlashError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            FlashError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(FlashError, impl_error);
+
+impl PeripheralError for FlashError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a FlashError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a FlashError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[FlashError] = &[
+        FlashError::NotAligned { address: 0 },
+        FlashError::OutOfBounds { address: 0 },
+        FlashError::WriteProtected,
+        FlashError::EraseFailed,
+        FlashError::ProgramFailed,
+        FlashError::VerifyFailed,
+        FlashError::Locked,
+        FlashError::Busy,
+        FlashError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(FlashError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (
+                FlashError::NotAligned {
+                    address: 0x0800_0001,
+                },
+                "address 0x08000001 is not aligned",
+            ),
+            (
+                FlashError::OutOfBounds {
+                    address: 0x0810_0000,
+                },
+                "address 0x08100000 is out of bounds",
+            ),
+            (FlashError::WriteProtected, "memory is write protected"),
+            (FlashError::EraseFailed, "erase failed"),
+            (FlashError::ProgramFailed, "program failed"),
+            (FlashError::VerifyFailed, "verification failed"),
+            (FlashError::Locked, "memory controller locked"),
+            (FlashError::Busy, "memory controller busy"),
+            (
+                FlashError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(retryable, [&FlashError::Busy]);
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(
+            codes,
+            [0x0e01, 0x0e02, 0x0e03, 0x0e04, 0x0e05, 0x0e06, 0x0e07, 0x0e08, 0x0001,]
+        );
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Recoverable,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "flash");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(
+            millis,
+            [None, None, None, None, None, None, None, Some(1), None,]
+        );
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::EINVAL,
+                -errno::ERANGE,
+                -errno::EACCES,
+                -errno::EIO,
+                -errno::EIO,
+                -errno::EIO,
+                -errno::EACCES,
+                -errno::EBUSY,
+                -errno::EIO,
+            ]
+        );
+    }
+}
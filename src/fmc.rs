@@ -0,0 +1,307 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// An FMC specific error.
+///
+/// This error type contains errors specific to FMC/FSMC (Flexible external Memory Controller)
+/// peripherals. Also it has an `Impl` kind to pass through implementation specific errors occurring
+/// while trying to use an FMC peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum FmcError {
+    /// The requested timings cannot be achieved with the memory controller clock
+    TimingConfigurationError,
+    /// The requested memory bank does not exist or is not available
+    BankUnavailable,
+    /// An SDRAM refresh was requested while the previous one was still pending
+    RefreshError,
+    /// A transfer on the external data bus failed
+    DataBusError,
+    /// The address exceeds the configured memory size
+    AddressOutOfRange,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl FmcError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `Impl` errors are retryable if the wrapped [`ImplError`] is. All other kinds are hard
+    /// faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            FmcError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`], the FMC specific kinds are not
+    /// retryable and return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            FmcError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`FmcError::Impl`] and `false` for the FMC specific kinds, e.g.
+    /// [`FmcError::TimingConfigurationError`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, FmcError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`FmcError::Impl`] with `f`.
+    ///
+    /// The FMC specific kinds, e.g. [`FmcError::TimingConfigurationError`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            FmcError::Impl(e) => FmcError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// FMC errors use the high byte `0x1e` and the index of the error kind as low byte, e.g.
+    /// `0x1e01` for `TimingConfigurationError`. See the [crate level
+    /// documentation](crate#error-codes) for details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            FmcError::TimingConfigurationError => 0x1e01,
+            FmcError::BankUnavailable => 0x1e02,
+            FmcError::RefreshError => 0x1e03,
+            FmcError::DataBusError => 0x1e04,
+            FmcError::AddressOutOfRange => 0x1e05,
+            FmcError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`FmcError::to_code`], e.g. `0x1e01` to
+    /// [`FmcError::TimingConfigurationError`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the FMC error kinds nor to an
+    /// [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x1e01 => Some(FmcError::TimingConfigurationError),
+            0x1e02 => Some(FmcError::BankUnavailable),
+            0x1e03 => Some(FmcError::RefreshError),
+            0x1e04 => Some(FmcError::DataBusError),
+            0x1e05 => Some(FmcError::AddressOutOfRange),
+            0x0000..=0x00ff => ImplError::from_code(code).map(FmcError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. `BankUnavailable` is [`Severity::Fatal`]. All other
+    /// kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            FmcError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            FmcError::BankUnavailable => Severity::Fatal,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"fmc"`, the stable lowercase name of the FMC peripheral, for all kinds including
+    /// `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "fmc"
+    }
+
+    /// Returns the name of the error kind, e.g. `"TimingConfigurationError"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            FmcError::TimingConfigurationError => "TimingConfigurationError",
+            FmcError::BankUnavailable => "BankUnavailable",
+            FmcError::RefreshError => "RefreshError",
+            FmcError::DataBusError => "DataBusError",
+            FmcError::AddressOutOfRange => "AddressOutOfRange",
+            FmcError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `BankUnavailable` maps to
+    /// `-ENODEV`. `TimingConfigurationError` maps to `-EINVAL`. `AddressOutOfRange` maps to
+    /// `-ERANGE`. All other kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            FmcError::Impl(e) => e.to_errno(),
+            FmcError::BankUnavailable => -errno::ENODEV,
+            FmcError::TimingConfigurationError => -errno::EINVAL,
+            FmcError::AddressOutOfRange => -errno::ERANGE,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for FmcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FmcError::TimingConfigurationError => f.write_str("timing configuration error"),
+            FmcError::BankUnavailable => f.write_str("bank unavailable"),
+            FmcError::RefreshError => f.write_str("refresh error"),
+            FmcError::DataBusError => f.write_str("data bus error"),
+            FmcError::AddressOutOfRange => f.write_str("address out of range"),
+            FmcError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for FmcError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            FmcError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(FmcError, impl_error);
+
+impl PeripheralError for FmcError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a FmcError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a FmcError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[FmcError] = &[
+        FmcError::TimingConfigurationError,
+        FmcError::BankUnavailable,
+        FmcError::RefreshError,
+        FmcError::DataBusError,
+        FmcError::AddressOutOfRange,
+        FmcError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(FmcError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (
+                FmcError::TimingConfigurationError,
+                "timing configuration error",
+            ),
+            (FmcError::BankUnavailable, "bank unavailable"),
+            (FmcError::RefreshError, "refresh error"),
+            (FmcError::DataBusError, "data bus error"),
+            (FmcError::AddressOutOfRange, "address out of range"),
+            (
+                FmcError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        assert!(!KINDS.iter().any(|e| e.is_retryable()));
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(codes, [0x1e01, 0x1e02, 0x1e03, 0x1e04, 0x1e05, 0x0001,]);
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Degraded,
+                Severity::Fatal,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "fmc");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(millis, [None, None, None, None, None, None,]);
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::EINVAL,
+                -errno::ENODEV,
+                -errno::EIO,
+                -errno::EIO,
+                -errno::ERANGE,
+                -errno::EIO,
+            ]
+        );
+    }
+}
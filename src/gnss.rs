@@ -0,0 +1,325 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// A GNSS specific error.
+///
+/// This error type contains errors specific to GNSS (Global Navigation Satellite System) receiver
+/// peripherals. Also it has an `Impl` kind to pass through implementation specific errors occurring
+/// while trying to use a GNSS peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum GnssError {
+    /// The receiver has not acquired a position fix (yet)
+    NoFix,
+    /// A received sentence or message failed the checksum verification
+    ChecksumError,
+    /// The antenna is disconnected or its feed line is open
+    AntennaOpen,
+    /// The antenna feed line is shorted
+    AntennaShort,
+    /// The receiver did not respond in time
+    Timeout,
+    /// A received sentence or message could not be parsed
+    ParseError,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl GnssError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `NoFix`, `ChecksumError` and `Timeout` are transient and thus retryable. `Impl` errors are
+    /// retryable if the wrapped [`ImplError`] is. All other kinds are hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            GnssError::NoFix | GnssError::ChecksumError | GnssError::Timeout => true,
+            GnssError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`]. `NoFix`, `ChecksumError` and
+    /// `Timeout` suggest 1 ms, all other kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            GnssError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`GnssError::Impl`] and `false` for the GNSS specific kinds, e.g.
+    /// [`GnssError::NoFix`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, GnssError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`GnssError::Impl`] with `f`.
+    ///
+    /// The GNSS specific kinds, e.g. [`GnssError::NoFix`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            GnssError::Impl(e) => GnssError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// GNSS errors use the high byte `0x23` and the index of the error kind as low byte, e.g.
+    /// `0x2301` for `NoFix`. See the [crate level documentation](crate#error-codes) for details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            GnssError::NoFix => 0x2301,
+            GnssError::ChecksumError => 0x2302,
+            GnssError::AntennaOpen => 0x2303,
+            GnssError::AntennaShort => 0x2304,
+            GnssError::Timeout => 0x2305,
+            GnssError::ParseError => 0x2306,
+            GnssError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`GnssError::to_code`], e.g. `0x2301` to [`GnssError::NoFix`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the GNSS error kinds nor to an
+    /// [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x2301 => Some(GnssError::NoFix),
+            0x2302 => Some(GnssError::ChecksumError),
+            0x2303 => Some(GnssError::AntennaOpen),
+            0x2304 => Some(GnssError::AntennaShort),
+            0x2305 => Some(GnssError::Timeout),
+            0x2306 => Some(GnssError::ParseError),
+            0x0000..=0x00ff => ImplError::from_code(code).map(GnssError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. `AntennaOpen` and `AntennaShort` are
+    /// [`Severity::Fatal`]. All other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            GnssError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            GnssError::AntennaOpen | GnssError::AntennaShort => Severity::Fatal,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"gnss"`, the stable lowercase name of the GNSS peripheral, for all kinds including
+    /// `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "gnss"
+    }
+
+    /// Returns the name of the error kind, e.g. `"NoFix"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            GnssError::NoFix => "NoFix",
+            GnssError::ChecksumError => "ChecksumError",
+            GnssError::AntennaOpen => "AntennaOpen",
+            GnssError::AntennaShort => "AntennaShort",
+            GnssError::Timeout => "Timeout",
+            GnssError::ParseError => "ParseError",
+            GnssError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `NoFix` maps to `-EAGAIN`.
+    /// `ParseError` maps to `-EBADMSG`. `ChecksumError` maps to `-EILSEQ`. `Timeout` maps to
+    /// `-ETIMEDOUT`. All other kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            GnssError::Impl(e) => e.to_errno(),
+            GnssError::NoFix => -errno::EAGAIN,
+            GnssError::ParseError => -errno::EBADMSG,
+            GnssError::ChecksumError => -errno::EILSEQ,
+            GnssError::Timeout => -errno::ETIMEDOUT,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for GnssError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GnssError::NoFix => f.write_str("no position fix"),
+            GnssError::ChecksumError => f.write_str("checksum error"),
+            GnssError::AntennaOpen => f.write_str("antenna open"),
+            GnssError::AntennaShort => f.write_str("antenna short"),
+            GnssError::Timeout => f.write_str("receiver timed out"),
+            GnssError::ParseError => f.write_str("parse error"),
+            GnssError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for GnssError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            GnssError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(GnssError, impl_error);
+
+impl PeripheralError for GnssError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a GnssError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a GnssError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[GnssError] = &[
+        GnssError::NoFix,
+        GnssError::ChecksumError,
+        GnssError::AntennaOpen,
+        GnssError::AntennaShort,
+        GnssError::Timeout,
+        GnssError::ParseError,
+        GnssError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(GnssError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (GnssError::NoFix, "no position fix"),
+            (GnssError::ChecksumError, "checksum error"),
+            (GnssError::AntennaOpen, "antenna open"),
+            (GnssError::AntennaShort, "antenna short"),
+            (GnssError::Timeout, "receiver timed out"),
+            (GnssError::ParseError, "parse error"),
+            (
+                GnssError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(
+            retryable,
+            [
+                &GnssError::NoFix,
+                &GnssError::ChecksumError,
+                &GnssError::Timeout
+            ]
+        );
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(
+            codes,
+            [0x2301, 0x2302, 0x2303, 0x2304, 0x2305, 0x2306, 0x0001,]
+        );
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Fatal,
+                Severity::Fatal,
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "gnss");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(millis, [Some(1), Some(1), None, None, Some(1), None, None,]);
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::EAGAIN,
+                -errno::EILSEQ,
+                -errno::EIO,
+                -errno::EIO,
+                -errno::ETIMEDOUT,
+                -errno::EBADMSG,
+                -errno::EIO,
+            ]
+        );
+    }
+}
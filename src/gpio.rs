@@ -0,0 +1,349 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// A GPIO (General input/output) specific error.
+///
+/// This error type contains errors specific to GPIO peripherals. Also it has an `Impl` kind to
+/// pass through implementation specific errors occuring while trying to use a GPIO peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum GpioError {
+    /// The peripheral is in the wrong operational mode for the intended operation
+    WrongMode,
+    /// The pin configuration is locked (e.g. via LCKR) and cannot be changed until the next reset
+    PinLocked,
+    /// The requested alternate function is not available on this pin
+    AlternateFunctionUnavailable,
+    /// The requested output drive strength or slew rate is not supported
+    DriveStrengthUnsupported,
+    /// The requested pull-up/pull-down configuration is not supported
+    PullConfigUnsupported,
+    /// The requested interrupt or edge trigger is not supported, e.g. because the interrupt line is
+    /// shared with another pin
+    InterruptUnsupported,
+    /// Hardware debouncing is not supported
+    DebounceUnsupported,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl GpioError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `Impl` errors are retryable if the wrapped [`ImplError`] is. All other kinds are hard
+    /// faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            GpioError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`], the GPIO specific kinds are not
+    /// retryable and return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            GpioError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`GpioError::Impl`] and `false` for the GPIO specific kinds, e.g.
+    /// [`GpioError::WrongMode`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, GpioError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`GpioError::Impl`] with `f`.
+    ///
+    /// The GPIO specific kinds, e.g. [`GpioError::WrongMode`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            GpioError::Impl(e) => GpioError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// GPIO errors use the high byte `0x01` and the index of the error kind as low byte, e.g.
+    /// `0x0101` for `WrongMode`. See the [crate level documentation](crate#error-codes) for
+    /// details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            GpioError::WrongMode => 0x0101,
+            GpioError::PinLocked => 0x0102,
+            GpioError::AlternateFunctionUnavailable => 0x0103,
+            GpioError::DriveStrengthUnsupported => 0x0104,
+            GpioError::PullConfigUnsupported => 0x0105,
+            GpioError::InterruptUnsupported => 0x0106,
+            GpioError::DebounceUnsupported => 0x0107,
+            GpioError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`GpioError::to_code`], e.g. `0x0101` to
+    /// [`GpioError::WrongMode`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the GPIO error kinds nor to an
+    /// [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x0101 => Some(GpioError::WrongMode),
+            0x0102 => Some(GpioError::PinLocked),
+            0x0103 => Some(GpioError::AlternateFunctionUnavailable),
+            0x0104 => Some(GpioError::DriveStrengthUnsupported),
+            0x0105 => Some(GpioError::PullConfigUnsupported),
+            0x0106 => Some(GpioError::InterruptUnsupported),
+            0x0107 => Some(GpioError::DebounceUnsupported),
+            0x0000..=0x00ff => ImplError::from_code(code).map(GpioError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. `PinLocked`, `AlternateFunctionUnavailable`,
+    /// `DriveStrengthUnsupported`, `PullConfigUnsupported`, `InterruptUnsupported` and
+    /// `DebounceUnsupported` are [`Severity::Fatal`]. All other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            GpioError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            GpioError::PinLocked
+            | GpioError::AlternateFunctionUnavailable
+            | GpioError::DriveStrengthUnsupported
+            | GpioError::PullConfigUnsupported
+            | GpioError::InterruptUnsupported
+            | GpioError::DebounceUnsupported => Severity::Fatal,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"gpio"`, the stable lowercase name of the GPIO peripheral, for all kinds including
+    /// `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "gpio"
+    }
+
+    /// Returns the name of the error kind, e.g. `"WrongMode"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            GpioError::WrongMode => "WrongMode",
+            GpioError::PinLocked => "PinLocked",
+            GpioError::AlternateFunctionUnavailable => "AlternateFunctionUnavailable",
+            GpioError::DriveStrengthUnsupported => "DriveStrengthUnsupported",
+            GpioError::PullConfigUnsupported => "PullConfigUnsupported",
+            GpioError::InterruptUnsupported => "InterruptUnsupported",
+            GpioError::DebounceUnsupported => "DebounceUnsupported",
+            GpioError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `PinLocked` maps to `-EACCES`.
+    /// `AlternateFunctionUnavailable` maps to `-ENODEV`. `WrongMode` maps to `-EINVAL`. All other
+    /// kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            GpioError::Impl(e) => e.to_errno(),
+            GpioError::PinLocked => -errno::EACCES,
+            GpioError::AlternateFunctionUnavailable => -errno::ENODEV,
+            GpioError::WrongMode => -errno::EINVAL,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for GpioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GpioError::WrongMode => {
+                f.write_str("peripheral is in the wrong mode for this operation")
+            }
+            GpioError::PinLocked => f.write_str("pin configuration locked"),
+            GpioError::AlternateFunctionUnavailable => {
+                f.write_str("alternate function unavailable")
+            }
+            GpioError::DriveStrengthUnsupported => f.write_str("drive strength unsupported"),
+            GpioError::PullConfigUnsupported => f.write_str("pull configuration unsupported"),
+            GpioError::InterruptUnsupported => f.write_str("interrupt unsupported"),
+            GpioError::DebounceUnsupported => f.write_str("debounce unsupported"),
+            GpioError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for GpioError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            GpioError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(GpioError, impl_error);
+
+impl PeripheralError for GpioError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a GpioError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a GpioError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[GpioError] = &[
+        GpioError::WrongMode,
+        GpioError::PinLocked,
+        GpioError::AlternateFunctionUnavailable,
+        GpioError::DriveStrengthUnsupported,
+        GpioError::PullConfigUnsupported,
+        GpioError::InterruptUnsupported,
+        GpioError::DebounceUnsupported,
+        GpioError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(GpioError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (
+                GpioError::WrongMode,
+                "peripheral is in the wrong mode for this operation",
+            ),
+            (GpioError::PinLocked, "pin configuration locked"),
+            (
+                GpioError::AlternateFunctionUnavailable,
+                "alternate function unavailable",
+            ),
+            (
+                GpioError::DriveStrengthUnsupported,
+                "drive strength unsupported",
+            ),
+            (
+                GpioError::PullConfigUnsupported,
+                "pull configuration unsupported",
+            ),
+            (GpioError::InterruptUnsupported, "interrupt unsupported"),
+            (GpioError::DebounceUnsupported, "debounce unsupported"),
+            (
+                GpioError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        assert!(!KINDS.iter().any(|e| e.is_retryable()));
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(
+            codes,
+            [0x0101, 0x0102, 0x0103, 0x0104, 0x0105, 0x0106, 0x0107, 0x0001,]
+        );
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Degraded,
+                Severity::Fatal,
+                Severity::Fatal,
+                Severity::Fatal,
+                Severity::Fatal,
+                Severity::Fatal,
+                Severity::Fatal,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "gpio");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(millis, [None, None, None, None, None, None, None, None,]);
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::EINVAL,
+                -errno::EACCES,
+                -errno::ENODEV,
+                -errno::EIO,
+                -errno::EIO,
+                -errno::EIO,
+                -errno::EIO,
+                -errno::EIO,
+            ]
+        );
+    }
+}
@@ -0,0 +1,122 @@
+//! Conversions into the error kinds of [`embedded-hal`](https://docs.rs/embedded-hal) 1.0.
+
+use crate::{I2cError, NackSource, SpiError};
+use embedded_hal::{i2c, spi};
+
+/// Maps an [`I2cError`] to its closest `embedded-hal` counterpart.
+///
+/// | `I2cError`                  | `i2c::ErrorKind`                               |
+/// |-----------------------------|------------------------------------------------|
+/// | `Bus`                       | `Bus`                                          |
+/// | `ArbitrationLoss`           | `ArbitrationLoss`                              |
+/// | `NACK(NackSource::Address)` | `NoAcknowledge(NoAcknowledgeSource::Address)`  |
+/// | `NACK(NackSource::Data)`    | `NoAcknowledge(NoAcknowledgeSource::Data)`     |
+/// | `Overrun`, `Underrun`       | `Overrun`                                      |
+/// | all other kinds             | `Other`                                        |
+impl From<I2cError> for i2c::ErrorKind {
+    fn from(e: I2cError) -> Self {
+        match e {
+            I2cError::Bus => i2c::ErrorKind::Bus,
+            I2cError::ArbitrationLoss => i2c::ErrorKind::ArbitrationLoss,
+            I2cError::NACK(NackSource::Address) => {
+                i2c::ErrorKind::NoAcknowledge(i2c::NoAcknowledgeSource::Address)
+            }
+            I2cError::NACK(NackSource::Data) => {
+                i2c::ErrorKind::NoAcknowledge(i2c::NoAcknowledgeSource::Data)
+            }
+            I2cError::Overrun | I2cError::Underrun => i2c::ErrorKind::Overrun,
+            _ => i2c::ErrorKind::Other,
+        }
+    }
+}
+
+impl i2c::Error for I2cError {
+    fn kind(&self) -> i2c::ErrorKind {
+        i2c::ErrorKind::from(*self)
+    }
+}
+
+/// Maps an [`SpiError`] to its closest `embedded-hal` counterpart.
+///
+/// | `SpiError`        | `spi::ErrorKind`  |
+/// |-------------------|-------------------|
+/// | `Overrun`         | `Overrun`         |
+/// | `ModeFault`       | `ModeFault`       |
+/// | `FrameFormat`     | `FrameFormat`     |
+/// | `ChipSelectError` | `ChipSelectFault` |
+/// | all other kinds   | `Other`           |
+impl From<SpiError> for spi::ErrorKind {
+    fn from(e: SpiError) -> Self {
+        match e {
+            SpiError::Overrun => spi::ErrorKind::Overrun,
+            SpiError::ModeFault => spi::ErrorKind::ModeFault,
+            SpiError::FrameFormat => spi::ErrorKind::FrameFormat,
+            SpiError::ChipSelectError => spi::ErrorKind::ChipSelectFault,
+            _ => spi::ErrorKind::Other,
+        }
+    }
+}
+
+impl spi::Error for SpiError {
+    fn kind(&self) -> spi::ErrorKind {
+        spi::ErrorKind::from(*self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ImplError;
+
+    #[test]
+    fn i2c_error_kind() {
+        let cases = [
+            (I2cError::Bus, i2c::ErrorKind::Bus),
+            (I2cError::ArbitrationLoss, i2c::ErrorKind::ArbitrationLoss),
+            (
+                I2cError::NACK(NackSource::Address),
+                i2c::ErrorKind::NoAcknowledge(i2c::NoAcknowledgeSource::Address),
+            ),
+            (
+                I2cError::NACK(NackSource::Data),
+                i2c::ErrorKind::NoAcknowledge(i2c::NoAcknowledgeSource::Data),
+            ),
+            (I2cError::Overrun, i2c::ErrorKind::Overrun),
+            (I2cError::Underrun, i2c::ErrorKind::Overrun),
+            (I2cError::PacketErrorChecking, i2c::ErrorKind::Other),
+            (I2cError::Timeout, i2c::ErrorKind::Other),
+            (I2cError::Alert, i2c::ErrorKind::Other),
+            (I2cError::ClockStretchTimeout, i2c::ErrorKind::Other),
+            (I2cError::BusBusy, i2c::ErrorKind::Other),
+            (I2cError::StartError, i2c::ErrorKind::Other),
+            (I2cError::StopError, i2c::ErrorKind::Other),
+            (I2cError::Impl(ImplError::Internal), i2c::ErrorKind::Other),
+        ];
+        for (e, kind) in cases.iter() {
+            assert_eq!(i2c::Error::kind(e), *kind);
+        }
+    }
+
+    #[test]
+    fn spi_error_kind() {
+        let cases = [
+            (SpiError::Overrun, spi::ErrorKind::Overrun),
+            (SpiError::ModeFault, spi::ErrorKind::ModeFault),
+            (SpiError::CRCError, spi::ErrorKind::Other),
+            (SpiError::FrameFormat, spi::ErrorKind::FrameFormat),
+            (SpiError::ChipSelectError, spi::ErrorKind::ChipSelectFault),
+            (SpiError::Timeout, spi::ErrorKind::Other),
+            (
+                SpiError::TransferSizeMismatch {
+                    expected: 0,
+                    actual: 0,
+                },
+                spi::ErrorKind::Other,
+            ),
+            (SpiError::Impl(ImplError::Internal), spi::ErrorKind::Other),
+        ];
+        for (e, kind) in cases.iter() {
+            assert_eq!(spi::Error::kind(e), *kind);
+        }
+    }
+}
@@ -0,0 +1,479 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// An I2C specific error.
+///
+/// This error type contains errors specific to I2C peripherals. Also it has an `Impl` kind to pass
+/// through implementation specific errors occurring while trying to use an I2C peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum I2cError {
+    /// An unspecific bus error occured
+    Bus,
+    /// The arbitration was lost, e.g. electrical problems with the clock signal
+    ArbitrationLoss,
+    /// A bus operation received a NACK, e.g. due to the addressed device not being available on
+    /// the bus or device not being ready to process any requests at the moment
+    ///
+    /// The [`NackSource`] tells apart a device not acknowledging its address (typically no device
+    /// present) from a device rejecting a data byte in the middle of a transfer.
+    NACK(NackSource),
+    /// The peripheral receive buffer was overrun
+    Overrun,
+    /// The peripheral send buffer ran out of data
+    Underrun,
+    /// SMBus Error checking byte mismatch
+    PacketErrorChecking,
+    /// SMBus Timeout error
+    Timeout,
+    /// SMBus Alert received
+    Alert,
+    /// A device held the clock line low for longer than the controller permits for clock
+    /// stretching
+    ClockStretchTimeout,
+    /// The bus was not idle when trying to generate a START condition, e.g. another controller is
+    /// using the bus or a device is stuck holding SDA or SCL low
+    BusBusy,
+    /// A START or repeated START condition was detected at an illegal position or could not be
+    /// generated
+    StartError,
+    /// A STOP condition was detected at an illegal position or could not be generated
+    StopError,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl I2cError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `ArbitrationLoss`, `NACK`, `Overrun`, `Underrun`, `PacketErrorChecking`, `Timeout`,
+    /// `ClockStretchTimeout` and `BusBusy` are transient and thus retryable. `Impl` errors are
+    /// retryable if the wrapped [`ImplError`] is. All other kinds are hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            I2cError::ArbitrationLoss
+            | I2cError::NACK(_)
+            | I2cError::Overrun
+            | I2cError::Underrun
+            | I2cError::PacketErrorChecking
+            | I2cError::Timeout
+            | I2cError::ClockStretchTimeout
+            | I2cError::BusBusy => true,
+            I2cError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`]. `Overrun`, `Underrun`,
+    /// `PacketErrorChecking`, `Timeout`, `ClockStretchTimeout` and `BusBusy` suggest 1 ms, all
+    /// other kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            I2cError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`I2cError::Impl`] and `false` for the I2C specific kinds, e.g.
+    /// [`I2cError::Bus`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, I2cError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`I2cError::Impl`] with `f`.
+    ///
+    /// The I2C specific kinds, e.g. [`I2cError::Bus`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            I2cError::Impl(e) => I2cError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// I2C errors use the high byte `0x05` and the index of the error kind as low byte, e.g.
+    /// `0x0501` for `Bus`. See the [crate level documentation](crate#error-codes) for details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            I2cError::Bus => 0x0501,
+            I2cError::ArbitrationLoss => 0x0502,
+            I2cError::NACK(NackSource::Address) => 0x0503,
+            I2cError::Overrun => 0x0504,
+            I2cError::Underrun => 0x0505,
+            I2cError::PacketErrorChecking => 0x0506,
+            I2cError::Timeout => 0x0507,
+            I2cError::Alert => 0x0508,
+            I2cError::ClockStretchTimeout => 0x0509,
+            I2cError::BusBusy => 0x050a,
+            I2cError::StartError => 0x050b,
+            I2cError::StopError => 0x050c,
+            I2cError::NACK(NackSource::Data) => 0x050d,
+            I2cError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`I2cError::to_code`], e.g. `0x0501` to [`I2cError::Bus`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the I2C error kinds nor to an
+    /// [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x0501 => Some(I2cError::Bus),
+            0x0502 => Some(I2cError::ArbitrationLoss),
+            0x0503 => Some(I2cError::NACK(NackSource::Address)),
+            0x0504 => Some(I2cError::Overrun),
+            0x0505 => Some(I2cError::Underrun),
+            0x0506 => Some(I2cError::PacketErrorChecking),
+            0x0507 => Some(I2cError::Timeout),
+            0x0508 => Some(I2cError::Alert),
+            0x0509 => Some(I2cError::ClockStretchTimeout),
+            0x050a => Some(I2cError::BusBusy),
+            0x050b => Some(I2cError::StartError),
+            0x050c => Some(I2cError::StopError),
+            0x050d => Some(I2cError::NACK(NackSource::Data)),
+            0x0000..=0x00ff => ImplError::from_code(code).map(I2cError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. All other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            I2cError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"i2c"`, the stable lowercase name of the I2C peripheral, for all kinds including
+    /// `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "i2c"
+    }
+
+    /// Returns the name of the error kind, e.g. `"Bus"`.
+    ///
+    /// The data carried by `NACK` is not included. `Impl` errors return `"Impl"` instead of the
+    /// name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            I2cError::Bus => "Bus",
+            I2cError::ArbitrationLoss => "ArbitrationLoss",
+            I2cError::NACK(_) => "NACK",
+            I2cError::Overrun => "Overrun",
+            I2cError::Underrun => "Underrun",
+            I2cError::PacketErrorChecking => "PacketErrorChecking",
+            I2cError::Timeout => "Timeout",
+            I2cError::Alert => "Alert",
+            I2cError::ClockStretchTimeout => "ClockStretchTimeout",
+            I2cError::BusBusy => "BusBusy",
+            I2cError::StartError => "StartError",
+            I2cError::StopError => "StopError",
+            I2cError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `NACK` maps to `-ENXIO`.
+    /// `ArbitrationLoss` maps to `-EAGAIN`. `BusBusy` maps to `-EBUSY`. `Overrun` maps to
+    /// `-EOVERFLOW`. `PacketErrorChecking` maps to `-EILSEQ`. `Timeout` and `ClockStretchTimeout`
+    /// map to `-ETIMEDOUT`. All other kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            I2cError::Impl(e) => e.to_errno(),
+            I2cError::NACK(_) => -errno::ENXIO,
+            I2cError::ArbitrationLoss => -errno::EAGAIN,
+            I2cError::BusBusy => -errno::EBUSY,
+            I2cError::Overrun => -errno::EOVERFLOW,
+            I2cError::PacketErrorChecking => -errno::EILSEQ,
+            I2cError::Timeout | I2cError::ClockStretchTimeout => -errno::ETIMEDOUT,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for I2cError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            I2cError::Bus => f.write_str("bus error"),
+            I2cError::ArbitrationLoss => f.write_str("bus arbitration lost"),
+            I2cError::NACK(NackSource::Address) => {
+                f.write_str("received NACK for address, device not responding")
+            }
+            I2cError::NACK(NackSource::Data) => {
+                f.write_str("received NACK for data, device not accepting data")
+            }
+            I2cError::Overrun => f.write_str("receive buffer overrun"),
+            I2cError::Underrun => f.write_str("send buffer underrun"),
+            I2cError::PacketErrorChecking => f.write_str("SMBus packet error checking mismatch"),
+            I2cError::Timeout => f.write_str("SMBus timeout"),
+            I2cError::Alert => f.write_str("SMBus alert received"),
+            I2cError::ClockStretchTimeout => f.write_str("clock stretching timeout"),
+            I2cError::BusBusy => f.write_str("bus busy"),
+            I2cError::StartError => f.write_str("misplaced or failed START condition"),
+            I2cError::StopError => f.write_str("misplaced or failed STOP condition"),
+            I2cError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for I2cError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            I2cError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(I2cError, impl_error);
+
+impl PeripheralError for I2cError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a I2cError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a I2cError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+/// The part of an I2C transfer which was not acknowledged.
+///
+/// There is no read/write direction: a master only receives a data NACK while writing, since when
+/// reading it is the master itself which NACKs the last byte, and the direction of a NACKed
+/// address is known to the caller which issued the transfer. This matches `embedded-hal`, whose
+/// `NoAcknowledgeSource` does not carry a direction either.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum NackSource {
+    /// The address was not acknowledged, e.g. because no device with that address is on the bus
+    Address,
+    /// A data byte was not acknowledged, e.g. because the device cannot accept more data
+    Data,
+}
+
+impl NackSource {
+    /// All NACK sources.
+    pub const ALL: &'static [Self] = &[NackSource::Address, NackSource::Data];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[I2cError] = &[
+        I2cError::Bus,
+        I2cError::ArbitrationLoss,
+        I2cError::NACK(NackSource::Address),
+        I2cError::NACK(NackSource::Data),
+        I2cError::Overrun,
+        I2cError::Underrun,
+        I2cError::PacketErrorChecking,
+        I2cError::Timeout,
+        I2cError::Alert,
+        I2cError::ClockStretchTimeout,
+        I2cError::BusBusy,
+        I2cError::StartError,
+        I2cError::StopError,
+        I2cError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(I2cError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (I2cError::Bus, "bus error"),
+            (I2cError::ArbitrationLoss, "bus arbitration lost"),
+            (
+                I2cError::NACK(NackSource::Address),
+                "received NACK for address, device not responding",
+            ),
+            (
+                I2cError::NACK(NackSource::Data),
+                "received NACK for data, device not accepting data",
+            ),
+            (I2cError::Overrun, "receive buffer overrun"),
+            (I2cError::Underrun, "send buffer underrun"),
+            (
+                I2cError::PacketErrorChecking,
+                "SMBus packet error checking mismatch",
+            ),
+            (I2cError::Timeout, "SMBus timeout"),
+            (I2cError::Alert, "SMBus alert received"),
+            (I2cError::ClockStretchTimeout, "clock stretching timeout"),
+            (I2cError::BusBusy, "bus busy"),
+            (I2cError::StartError, "misplaced or failed START condition"),
+            (I2cError::StopError, "misplaced or failed STOP condition"),
+            (
+                I2cError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(
+            retryable,
+            [
+                &I2cError::ArbitrationLoss,
+                &I2cError::NACK(NackSource::Address),
+                &I2cError::NACK(NackSource::Data),
+                &I2cError::Overrun,
+                &I2cError::Underrun,
+                &I2cError::PacketErrorChecking,
+                &I2cError::Timeout,
+                &I2cError::ClockStretchTimeout,
+                &I2cError::BusBusy
+            ]
+        );
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(
+            codes,
+            [
+                0x0501, 0x0502, 0x0503, 0x050d, 0x0504, 0x0505, 0x0506, 0x0507, 0x0508, 0x0509,
+                0x050a, 0x050b, 0x050c, 0x0001,
+            ]
+        );
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Degraded,
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "i2c");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(
+            millis,
+            [
+                None,
+                Some(1),
+                Some(1),
+                Some(1),
+                Some(1),
+                Some(1),
+                Some(1),
+                Some(1),
+                None,
+                Some(1),
+                Some(1),
+                None,
+                None,
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::EIO,
+                -errno::EAGAIN,
+                -errno::ENXIO,
+                -errno::ENXIO,
+                -errno::EOVERFLOW,
+                -errno::EIO,
+                -errno::EILSEQ,
+                -errno::ETIMEDOUT,
+                -errno::EIO,
+                -errno::ETIMEDOUT,
+                -errno::EBUSY,
+                -errno::EIO,
+                -errno::EIO,
+                -errno::EIO,
+            ]
+        );
+    }
+
+    #[test]
+    fn nack_source() {
+        assert_ne!(
+            I2cError::NACK(NackSource::Address),
+            I2cError::NACK(NackSource::Data)
+        );
+    }
+}
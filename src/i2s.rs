@@ -0,0 +1,322 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// An I2S specific error.
+///
+/// This error type contains errors specific to I2S/SAI (Inter-IC Sound/Serial Audio Interface)
+/// peripherals. Also it has an `Impl` kind to pass through implementation specific errors occurring
+/// while trying to use an I2S peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum I2sError {
+    /// The peripheral receive buffer was overrun
+    Overrun,
+    /// The peripheral send buffer ran out of data
+    Underrun,
+    /// A frame synchronisation signal was detected at an unexpected position
+    FrameError,
+    /// The audio clock is missing or does not match the requested sample rate
+    ClockError,
+    /// The requested channel mode (e.g. mono, stereo, TDM) is not supported
+    ChannelModeUnsupported,
+    /// The requested data format or sample width is not supported
+    DataFormatUnsupported,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl I2sError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `Overrun`, `Underrun` and `FrameError` are transient and thus retryable. `Impl` errors are
+    /// retryable if the wrapped [`ImplError`] is. All other kinds are hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            I2sError::Overrun | I2sError::Underrun | I2sError::FrameError => true,
+            I2sError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`]. `Overrun`, `Underrun` and
+    /// `FrameError` suggest 1 ms, all other kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            I2sError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`I2sError::Impl`] and `false` for the I2S specific kinds, e.g.
+    /// [`I2sError::Overrun`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, I2sError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`I2sError::Impl`] with `f`.
+    ///
+    /// The I2S specific kinds, e.g. [`I2sError::Overrun`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            I2sError::Impl(e) => I2sError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// I2S errors use the high byte `0x11` and the index of the error kind as low byte, e.g.
+    /// `0x1101` for `Overrun`. See the [crate level documentation](crate#error-codes) for details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            I2sError::Overrun => 0x1101,
+            I2sError::Underrun => 0x1102,
+            I2sError::FrameError => 0x1103,
+            I2sError::ClockError => 0x1104,
+            I2sError::ChannelModeUnsupported => 0x1105,
+            I2sError::DataFormatUnsupported => 0x1106,
+            I2sError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`I2sError::to_code`], e.g. `0x1101` to [`I2sError::Overrun`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the I2S error kinds nor to an
+    /// [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x1101 => Some(I2sError::Overrun),
+            0x1102 => Some(I2sError::Underrun),
+            0x1103 => Some(I2sError::FrameError),
+            0x1104 => Some(I2sError::ClockError),
+            0x1105 => Some(I2sError::ChannelModeUnsupported),
+            0x1106 => Some(I2sError::DataFormatUnsupported),
+            0x0000..=0x00ff => ImplError::from_code(code).map(I2sError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. `ChannelModeUnsupported` and `DataFormatUnsupported`
+    /// are [`Severity::Fatal`]. All other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            I2sError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            I2sError::ChannelModeUnsupported | I2sError::DataFormatUnsupported => Severity::Fatal,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"i2s"`, the stable lowercase name of the I2S peripheral, for all kinds including
+    /// `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "i2s"
+    }
+
+    /// Returns the name of the error kind, e.g. `"Overrun"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            I2sError::Overrun => "Overrun",
+            I2sError::Underrun => "Underrun",
+            I2sError::FrameError => "FrameError",
+            I2sError::ClockError => "ClockError",
+            I2sError::ChannelModeUnsupported => "ChannelModeUnsupported",
+            I2sError::DataFormatUnsupported => "DataFormatUnsupported",
+            I2sError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `FrameError` maps to `-EPROTO`.
+    /// `Overrun` maps to `-EOVERFLOW`. All other kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            I2sError::Impl(e) => e.to_errno(),
+            I2sError::FrameError => -errno::EPROTO,
+            I2sError::Overrun => -errno::EOVERFLOW,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for I2sError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            I2sError::Overrun => f.write_str("receive buffer overrun"),
+            I2sError::Underrun => f.write_str("send buffer underrun"),
+            I2sError::FrameError => f.write_str("frame error"),
+            I2sError::ClockError => f.write_str("audio clock error"),
+            I2sError::ChannelModeUnsupported => f.write_str("channel mode unsupported"),
+            I2sError::DataFormatUnsupported => f.write_str("data format unsupported"),
+            I2sError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for I2sError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            I2sError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(I2sError, impl_error);
+
+impl PeripheralError for I2sError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a I2sError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a I2sError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[I2sError] = &[
+        I2sError::Overrun,
+        I2sError::Underrun,
+        I2sError::FrameError,
+        I2sError::ClockError,
+        I2sError::ChannelModeUnsupported,
+        I2sError::DataFormatUnsupported,
+        I2sError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(I2sError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (I2sError::Overrun, "receive buffer overrun"),
+            (I2sError::Underrun, "send buffer underrun"),
+            (I2sError::FrameError, "frame error"),
+            (I2sError::ClockError, "audio clock error"),
+            (I2sError::ChannelModeUnsupported, "channel mode unsupported"),
+            (I2sError::DataFormatUnsupported, "data format unsupported"),
+            (
+                I2sError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(
+            retryable,
+            [
+                &I2sError::Overrun,
+                &I2sError::Underrun,
+                &I2sError::FrameError
+            ]
+        );
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(
+            codes,
+            [0x1101, 0x1102, 0x1103, 0x1104, 0x1105, 0x1106, 0x0001,]
+        );
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Fatal,
+                Severity::Fatal,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "i2s");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(millis, [Some(1), Some(1), Some(1), None, None, None, None,]);
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::EOVERFLOW,
+                -errno::EIO,
+                -errno::EPROTO,
+                -errno::EIO,
+                -errno::EIO,
+                -errno::EIO,
+                -errno::EIO,
+            ]
+        );
+    }
+}
@@ -0,0 +1,698 @@
+use crate::{errno, PeripheralError, Severity};
+use core::fmt;
+use core::time::Duration;
+
+/// A universal implementation specific error.
+///
+/// These error kinds can be used to signal implementation specific errors unrelated to the
+/// specific peripheral. This will be used for all sorts of connectivity problems, e.g. if an
+/// adapter to the peripheral is used or the target peripheral is connected to indirectly (like bus
+/// expanders) or an operating system is controlling the access and denying access.
+///
+/// The enum has a fixed `u8` representation with explicit discriminants starting at 1, which are
+/// identical to the low byte of [`ImplError::to_code`] and are guaranteed to stay stable. New kinds
+/// are appended with new discriminants. Since `TimedOut` carries data, the discriminant cannot be
+/// obtained via an `as` cast but via `to_code() as u8`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+#[repr(u8)]
+pub enum ImplError {
+    /// Unspecified internal driver error
+    Internal = 1,
+    /// Connection lost, e.g. device adapter was unplugged
+    Disconnected = 2,
+    /// Ran out of memory while trying to allocate required buffers
+    OutOfMemory = 3,
+    /// Operation timed out, please retry
+    TimedOut {
+        /// Time in milliseconds after which the operation was aborted, if known
+        after_ms: Option<u32>,
+    } = 4,
+    /// Peripheral is sleeping or in standby
+    Asleep = 5,
+    /// Peripheral is powered down
+    PowerDown = 6,
+    /// The peripheral cannot work with the specified settings
+    InvalidConfiguration = 7,
+    /// Could not open connection to peripheral
+    CouldNotOpen = 8,
+    /// No sufficient permissions to connect to peripheral
+    PermissionDenied = 9,
+    /// Peripheral is temporarily busy, please retry
+    ///
+    /// Unlike `Asleep` the peripheral is awake and operational and will become available again
+    /// shortly without having to be woken up. Unlike `PowerDown` it is retryable.
+    Busy = 10,
+    /// Peripheral was used before being initialized
+    ///
+    /// Unlike `InvalidConfiguration` the settings may be fine, the driver simply has not been set
+    /// up yet.
+    NotInitialized = 11,
+    /// Peripheral was initialized a second time
+    ///
+    /// Unlike `InvalidConfiguration` the settings may be fine, the driver has already been set up
+    /// before.
+    AlreadyInitialized = 12,
+    /// Operation was intentionally aborted before completion, e.g. by the caller
+    ///
+    /// Unlike `TimedOut` the operation did not fail on its own, so it is not retried by default.
+    Cancelled = 13,
+    /// A shared resource like a bus or a DMA channel is currently held by another owner, please
+    /// retry
+    ResourceBusy {
+        /// Static label of the resource for diagnostics, e.g. `"DMA1 channel 3"`
+        ///
+        /// The label is lost when serializing the error, since it cannot be borrowed for `'static`
+        /// when deserializing, and when encoding it via [`ImplError::to_code`]. In both cases it is
+        /// restored as an empty label.
+        #[cfg_attr(feature = "serde", serde(skip))]
+        resource: &'static str,
+    } = 14,
+    /// The implementation does not support a required capability
+    ///
+    /// Unlike `InvalidConfiguration` this names the missing capability instead of rejecting the
+    /// settings as a whole.
+    Unsupported {
+        /// Static label of the unsupported capability for diagnostics, e.g. `"10-bit addressing"`
+        ///
+        /// The label is lost when serializing the error, since it cannot be borrowed for `'static`
+        /// when deserializing, and when encoding it via [`ImplError::to_code`]. In both cases it is
+        /// restored as an empty label.
+        #[cfg_attr(feature = "serde", serde(skip))]
+        feature: &'static str,
+    } = 15,
+}
+
+impl ImplError {
+    /// All error kinds, e.g. for checking that formatting or a classification is total.
+    ///
+    /// `TimedOut` is included with an unknown duration, `ResourceBusy` and `Unsupported` with an
+    /// empty label.
+    pub const ALL: &'static [Self] = &[
+        ImplError::Internal,
+        ImplError::Disconnected,
+        ImplError::OutOfMemory,
+        ImplError::TimedOut { after_ms: None },
+        ImplError::Asleep,
+        ImplError::PowerDown,
+        ImplError::InvalidConfiguration,
+        ImplError::CouldNotOpen,
+        ImplError::PermissionDenied,
+        ImplError::Busy,
+        ImplError::NotInitialized,
+        ImplError::AlreadyInitialized,
+        ImplError::Cancelled,
+        ImplError::ResourceBusy { resource: "" },
+        ImplError::Unsupported { feature: "" },
+    ];
+
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `TimedOut`, `Asleep`, `Busy` and `ResourceBusy` are transient and thus retryable, although
+    /// a sleeping peripheral may have to be woken up first. All other kinds are hard faults.
+    pub fn is_retryable(&self) -> bool {
+        self.is_transient()
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// The defaults are 1 ms for `Busy` and `ResourceBusy` since the peripheral or resource is
+    /// expected to become available shortly, 10 ms for `TimedOut` to give a slow device some time
+    /// to settle and 50 ms for `Asleep` to allow the peripheral to wake up. All other kinds are not
+    /// expected to resolve by waiting and return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            ImplError::Busy | ImplError::ResourceBusy { .. } => Some(Duration::from_millis(1)),
+            ImplError::TimedOut { .. } => Some(Duration::from_millis(10)),
+            ImplError::Asleep => Some(Duration::from_millis(50)),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the condition is temporary.
+    ///
+    /// `TimedOut`, `Asleep`, `Busy` and `ResourceBusy` are transient: the peripheral is expected to
+    /// become usable again, although a sleeping peripheral may have to be woken up first. Never
+    /// true for fatal errors.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            ImplError::TimedOut { .. }
+                | ImplError::Asleep
+                | ImplError::Busy
+                | ImplError::ResourceBusy { .. }
+        )
+    }
+
+    /// Returns whether the condition is permanent and cannot be resolved by the driver.
+    ///
+    /// `Internal`, `InvalidConfiguration`, `CouldNotOpen`, `PermissionDenied`, `NotInitialized` and
+    /// `Unsupported` are fatal. The remaining `Disconnected`, `OutOfMemory`, `PowerDown`,
+    /// `AlreadyInitialized` and `Cancelled` kinds are neither transient nor fatal since they may be
+    /// resolved by reconnecting, freeing memory, powering up the peripheral or simply be ignored.
+    pub fn is_fatal(&self) -> bool {
+        matches!(
+            self,
+            ImplError::Internal
+                | ImplError::InvalidConfiguration
+                | ImplError::CouldNotOpen
+                | ImplError::PermissionDenied
+                | ImplError::NotInitialized
+                | ImplError::Unsupported { .. }
+        )
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// The high byte identifies the peripheral and the low byte the error kind, see the
+    /// [crate level documentation](crate#error-codes) for details.
+    pub fn to_code(&self) -> u16 {
+        match self {
+            ImplError::Internal => 0x0001,
+            ImplError::Disconnected => 0x0002,
+            ImplError::OutOfMemory => 0x0003,
+            ImplError::TimedOut { .. } => 0x0004,
+            ImplError::Asleep => 0x0005,
+            ImplError::PowerDown => 0x0006,
+            ImplError::InvalidConfiguration => 0x0007,
+            ImplError::CouldNotOpen => 0x0008,
+            ImplError::PermissionDenied => 0x0009,
+            ImplError::Busy => 0x000a,
+            ImplError::NotInitialized => 0x000b,
+            ImplError::AlreadyInitialized => 0x000c,
+            ImplError::Cancelled => 0x000d,
+            ImplError::ResourceBusy { .. } => 0x000e,
+            ImplError::Unsupported { .. } => 0x000f,
+        }
+    }
+
+    /// Returns the error kind for a code returned by [`ImplError::to_code`].
+    ///
+    /// Returns `None` for unknown codes. The duration of `TimedOut` is not encoded in the code and
+    /// thus restored as `None`, the labels of `ResourceBusy` and `Unsupported` are lost as well and
+    /// restored as empty labels.
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x0001 => Some(ImplError::Internal),
+            0x0002 => Some(ImplError::Disconnected),
+            0x0003 => Some(ImplError::OutOfMemory),
+            0x0004 => Some(ImplError::TimedOut { after_ms: None }),
+            0x0005 => Some(ImplError::Asleep),
+            0x0006 => Some(ImplError::PowerDown),
+            0x0007 => Some(ImplError::InvalidConfiguration),
+            0x0008 => Some(ImplError::CouldNotOpen),
+            0x0009 => Some(ImplError::PermissionDenied),
+            0x000a => Some(ImplError::Busy),
+            0x000b => Some(ImplError::NotInitialized),
+            0x000c => Some(ImplError::AlreadyInitialized),
+            0x000d => Some(ImplError::Cancelled),
+            0x000e => Some(ImplError::ResourceBusy { resource: "" }),
+            0x000f => Some(ImplError::Unsupported { feature: "" }),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// [Fatal](Self::is_fatal) kinds are [`Severity::Fatal`] and [retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. All other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        if self.is_fatal() {
+            Severity::Fatal
+        } else if self.is_retryable() {
+            Severity::Recoverable
+        } else {
+            Severity::Degraded
+        }
+    }
+
+    /// Returns the name of the peripheral the error belongs to, i.e. `"impl"`.
+    ///
+    /// The name is lowercase and guaranteed to stay stable.
+    pub fn peripheral(&self) -> &'static str {
+        "impl"
+    }
+
+    /// Returns the name of the error kind, e.g. `"Internal"`.
+    ///
+    /// Data carried by the error kind is not included.
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            ImplError::Internal => "Internal",
+            ImplError::Disconnected => "Disconnected",
+            ImplError::OutOfMemory => "OutOfMemory",
+            ImplError::TimedOut { .. } => "TimedOut",
+            ImplError::Asleep => "Asleep",
+            ImplError::PowerDown => "PowerDown",
+            ImplError::InvalidConfiguration => "InvalidConfiguration",
+            ImplError::CouldNotOpen => "CouldNotOpen",
+            ImplError::PermissionDenied => "PermissionDenied",
+            ImplError::Busy => "Busy",
+            ImplError::NotInitialized => "NotInitialized",
+            ImplError::AlreadyInitialized => "AlreadyInitialized",
+            ImplError::Cancelled => "Cancelled",
+            ImplError::ResourceBusy { .. } => "ResourceBusy",
+            ImplError::Unsupported { .. } => "Unsupported",
+        }
+    }
+
+    /// Returns a short identifier of the error kind, e.g. `"power_down"`.
+    ///
+    /// Unlike the [`Display`](fmt::Display) output the identifier is terse, machine friendly
+    /// snake case and guaranteed to stay stable, e.g. for logging into fixed-width fields. Data
+    /// carried by the error kind is not included.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImplError::Internal => "internal",
+            ImplError::Disconnected => "disconnected",
+            ImplError::OutOfMemory => "out_of_memory",
+            ImplError::TimedOut { .. } => "timed_out",
+            ImplError::Asleep => "asleep",
+            ImplError::PowerDown => "power_down",
+            ImplError::InvalidConfiguration => "invalid_configuration",
+            ImplError::CouldNotOpen => "could_not_open",
+            ImplError::PermissionDenied => "permission_denied",
+            ImplError::Busy => "busy",
+            ImplError::NotInitialized => "not_initialized",
+            ImplError::AlreadyInitialized => "already_initialized",
+            ImplError::Cancelled => "cancelled",
+            ImplError::ResourceBusy { .. } => "resource_busy",
+            ImplError::Unsupported { .. } => "unsupported",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// | `ImplError`            | errno               |
+    /// |------------------------|---------------------|
+    /// | `Internal`             | `-EIO` (-5)         |
+    /// | `Disconnected`         | `-ENODEV` (-19)     |
+    /// | `OutOfMemory`          | `-ENOMEM` (-12)     |
+    /// | `TimedOut`             | `-ETIMEDOUT` (-110) |
+    /// | `Asleep`               | `-EAGAIN` (-11)     |
+    /// | `PowerDown`            | `-ESHUTDOWN` (-108) |
+    /// | `InvalidConfiguration` | `-EINVAL` (-22)     |
+    /// | `CouldNotOpen`         | `-ENOENT` (-2)      |
+    /// | `PermissionDenied`     | `-EACCES` (-13)     |
+    /// | `Busy`                 | `-EBUSY` (-16)      |
+    /// | `NotInitialized`       | `-ENXIO` (-6)       |
+    /// | `AlreadyInitialized`   | `-EALREADY` (-114)  |
+    /// | `Cancelled`            | `-ECANCELED` (-125) |
+    /// | `ResourceBusy`         | `-EBUSY` (-16)      |
+    /// | `Unsupported`          | `-EOPNOTSUPP` (-95) |
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            ImplError::Internal => -errno::EIO,
+            ImplError::Disconnected => -errno::ENODEV,
+            ImplError::OutOfMemory => -errno::ENOMEM,
+            ImplError::TimedOut { .. } => -errno::ETIMEDOUT,
+            ImplError::Asleep => -errno::EAGAIN,
+            ImplError::PowerDown => -errno::ESHUTDOWN,
+            ImplError::InvalidConfiguration => -errno::EINVAL,
+            ImplError::CouldNotOpen => -errno::ENOENT,
+            ImplError::PermissionDenied => -errno::EACCES,
+            ImplError::Busy => -errno::EBUSY,
+            ImplError::NotInitialized => -errno::ENXIO,
+            ImplError::AlreadyInitialized => -errno::EALREADY,
+            ImplError::Cancelled => -errno::ECANCELED,
+            ImplError::ResourceBusy { .. } => -errno::EBUSY,
+            ImplError::Unsupported { .. } => -errno::EOPNOTSUPP,
+        }
+    }
+}
+
+impl fmt::Display for ImplError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImplError::Internal => f.write_str("internal driver error"),
+            ImplError::Disconnected => f.write_str("connection lost"),
+            ImplError::OutOfMemory => f.write_str("out of memory"),
+            ImplError::TimedOut { after_ms: None } => f.write_str("operation timed out"),
+            ImplError::TimedOut { after_ms: Some(ms) } => {
+                write!(f, "operation timed out after {} ms", ms)
+            }
+            ImplError::Asleep => f.write_str("peripheral is asleep"),
+            ImplError::PowerDown => f.write_str("peripheral is powered down"),
+            ImplError::InvalidConfiguration => f.write_str("invalid configuration"),
+            ImplError::CouldNotOpen => f.write_str("could not open connection to peripheral"),
+            ImplError::PermissionDenied => f.write_str("permission denied"),
+            ImplError::Busy => f.write_str("peripheral is busy"),
+            ImplError::NotInitialized => f.write_str("peripheral not initialized"),
+            ImplError::AlreadyInitialized => f.write_str("peripheral already initialized"),
+            ImplError::Cancelled => f.write_str("operation cancelled"),
+            ImplError::ResourceBusy { resource: "" } => f.write_str("resource is busy"),
+            ImplError::ResourceBusy { resource } => write!(f, "resource {} is busy", resource),
+            ImplError::Unsupported { feature: "" } => f.write_str("feature is not supported"),
+            ImplError::Unsupported { feature } => write!(f, "{} is not supported", feature),
+        }
+    }
+}
+
+impl core::error::Error for ImplError {}
+
+impl PeripheralError for ImplError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        Some(self)
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[ImplError] = crate::tests::IMPL_KINDS;
+
+    crate::tests::error_tests!(ImplError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (ImplError::Internal, "internal driver error"),
+            (ImplError::Disconnected, "connection lost"),
+            (ImplError::OutOfMemory, "out of memory"),
+            (
+                ImplError::TimedOut {
+                    after_ms: Some(100),
+                },
+                "operation timed out after 100 ms",
+            ),
+            (
+                ImplError::TimedOut { after_ms: None },
+                "operation timed out",
+            ),
+            (ImplError::Asleep, "peripheral is asleep"),
+            (ImplError::PowerDown, "peripheral is powered down"),
+            (ImplError::InvalidConfiguration, "invalid configuration"),
+            (
+                ImplError::CouldNotOpen,
+                "could not open connection to peripheral",
+            ),
+            (ImplError::PermissionDenied, "permission denied"),
+            (ImplError::Busy, "peripheral is busy"),
+            (ImplError::NotInitialized, "peripheral not initialized"),
+            (
+                ImplError::AlreadyInitialized,
+                "peripheral already initialized",
+            ),
+            (ImplError::Cancelled, "operation cancelled"),
+            (
+                ImplError::ResourceBusy {
+                    resource: "DMA1 channel 3",
+                },
+                "resource DMA1 channel 3 is busy",
+            ),
+            (ImplError::ResourceBusy { resource: "" }, "resource is busy"),
+            (
+                ImplError::Unsupported {
+                    feature: "10-bit addressing",
+                },
+                "10-bit addressing is not supported",
+            ),
+            (
+                ImplError::Unsupported { feature: "" },
+                "feature is not supported",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(
+            retryable,
+            [
+                &ImplError::TimedOut { after_ms: None },
+                &ImplError::Asleep,
+                &ImplError::Busy,
+                &ImplError::ResourceBusy { resource: "" }
+            ]
+        );
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(
+            codes,
+            [
+                0x0001, 0x0002, 0x0003, 0x0004, 0x0005, 0x0006, 0x0007, 0x0008, 0x0009, 0x000a,
+                0x000b, 0x000c, 0x000d, 0x000e, 0x000f,
+            ]
+        );
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Fatal,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Fatal,
+                Severity::Fatal,
+                Severity::Fatal,
+                Severity::Recoverable,
+                Severity::Fatal,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Recoverable,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "impl");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(
+            millis,
+            [
+                None,
+                None,
+                None,
+                Some(10),
+                Some(50),
+                None,
+                None,
+                None,
+                None,
+                Some(1),
+                None,
+                None,
+                None,
+                Some(1),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::EIO,
+                -errno::ENODEV,
+                -errno::ENOMEM,
+                -errno::ETIMEDOUT,
+                -errno::EAGAIN,
+                -errno::ESHUTDOWN,
+                -errno::EINVAL,
+                -errno::ENOENT,
+                -errno::EACCES,
+                -errno::EBUSY,
+                -errno::ENXIO,
+                -errno::EALREADY,
+                -errno::ECANCELED,
+                -errno::EBUSY,
+                -errno::EOPNOTSUPP,
+            ]
+        );
+    }
+
+    #[test]
+    fn transient_and_fatal() {
+        let transient: Vec<_> = KINDS.iter().filter(|e| e.is_transient()).collect();
+        assert_eq!(
+            transient,
+            [
+                &ImplError::TimedOut { after_ms: None },
+                &ImplError::Asleep,
+                &ImplError::Busy,
+                &ImplError::ResourceBusy { resource: "" },
+            ]
+        );
+        let fatal: Vec<_> = KINDS.iter().filter(|e| e.is_fatal()).collect();
+        assert_eq!(
+            fatal,
+            [
+                &ImplError::Internal,
+                &ImplError::InvalidConfiguration,
+                &ImplError::CouldNotOpen,
+                &ImplError::PermissionDenied,
+                &ImplError::NotInitialized,
+                &ImplError::Unsupported { feature: "" },
+            ]
+        );
+    }
+
+    #[test]
+    fn impl_error() {
+        for e in KINDS {
+            assert_eq!(PeripheralError::impl_error(e), Some(e));
+        }
+    }
+
+    #[test]
+    fn all() {
+        assert_eq!(ImplError::ALL, KINDS);
+    }
+
+    #[test]
+    fn unique_errno() {
+        // `ResourceBusy` only adds the busy resource to `Busy`, so both report `-EBUSY`.
+        let shared = ["Busy", "ResourceBusy"];
+        for (i, a) in KINDS.iter().enumerate() {
+            for b in &KINDS[i + 1..] {
+                let intended =
+                    shared.contains(&a.variant_name()) && shared.contains(&b.variant_name());
+                assert_eq!(a.to_errno() == b.to_errno(), intended, "{:?} {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn discriminants() {
+        // SAFETY: `ImplError` is `repr(u8)`, so its first byte is the discriminant.
+        let discriminant = |e: &ImplError| unsafe { *(e as *const ImplError).cast::<u8>() };
+        let discriminants: Vec<_> = KINDS.iter().map(discriminant).collect();
+        assert_eq!(discriminants, (1..=KINDS.len() as u8).collect::<Vec<_>>());
+        for (e, discriminant) in KINDS.iter().zip(discriminants) {
+            assert_eq!(e.to_code() as u8, discriminant);
+        }
+    }
+
+    #[test]
+    fn as_str() {
+        let names: Vec<_> = KINDS.iter().map(|e| e.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "internal",
+                "disconnected",
+                "out_of_memory",
+                "timed_out",
+                "asleep",
+                "power_down",
+                "invalid_configuration",
+                "could_not_open",
+                "permission_denied",
+                "busy",
+                "not_initialized",
+                "already_initialized",
+                "cancelled",
+                "resource_busy",
+                "unsupported",
+            ]
+        );
+    }
+
+    #[test]
+    fn resource_busy() {
+        let e = ImplError::ResourceBusy {
+            resource: "DMA1 channel 3",
+        };
+        assert_ne!(e, ImplError::ResourceBusy { resource: "" });
+        assert_eq!(e.to_string(), "resource DMA1 channel 3 is busy");
+        assert_eq!(
+            ImplError::from_code(e.to_code()),
+            Some(ImplError::ResourceBusy { resource: "" })
+        );
+        assert_eq!(
+            ImplError::ResourceBusy { resource: "" }.to_string(),
+            "resource is busy"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn resource_busy_serde() {
+        let json = serde_json::to_string(&ImplError::ResourceBusy {
+            resource: "DMA1 channel 3",
+        })
+        .unwrap();
+        assert_eq!(json, r#"{"ResourceBusy":{}}"#);
+        assert_eq!(
+            serde_json::from_str::<ImplError>(&json).unwrap(),
+            ImplError::ResourceBusy { resource: "" }
+        );
+    }
+
+    #[test]
+    fn unsupported() {
+        let e = ImplError::Unsupported {
+            feature: "10-bit addressing",
+        };
+        assert_ne!(e, ImplError::Unsupported { feature: "" });
+        assert_eq!(e.to_string(), "10-bit addressing is not supported");
+        assert_eq!(
+            ImplError::from_code(e.to_code()),
+            Some(ImplError::Unsupported { feature: "" })
+        );
+        assert_eq!(
+            ImplError::Unsupported { feature: "" }.to_string(),
+            "feature is not supported"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn unsupported_serde() {
+        let json = serde_json::to_string(&ImplError::Unsupported {
+            feature: "10-bit addressing",
+        })
+        .unwrap();
+        assert_eq!(json, r#"{"Unsupported":{}}"#);
+        assert_eq!(
+            serde_json::from_str::<ImplError>(&json).unwrap(),
+            ImplError::Unsupported { feature: "" }
+        );
+    }
+}
@@ -0,0 +1,307 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// An IMU specific error.
+///
+/// This error type contains errors specific to IMU (Inertial Measurement Unit) and motion sensor
+/// peripherals. Also it has an `Impl` kind to pass through implementation specific errors occurring
+/// while trying to use an IMU peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum ImuError {
+    /// The identification register returned an unexpected value, e.g. because a different or no
+    /// device is connected
+    WhoAmIMismatch,
+    /// The built-in self test of the sensor failed
+    SelfTestFailed,
+    /// No new measurement is available yet
+    DataNotReady,
+    /// The sample FIFO overflowed and samples were lost
+    FifoOverflow,
+    /// The calibration of the sensor failed
+    CalibrationFailed,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl ImuError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `DataNotReady` is transient and thus retryable. `Impl` errors are retryable if the wrapped
+    /// [`ImplError`] is. All other kinds are hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ImuError::DataNotReady => true,
+            ImuError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`]. `DataNotReady` suggests 1 ms,
+    /// all other kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            ImuError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`ImuError::Impl`] and `false` for the IMU specific kinds, e.g.
+    /// [`ImuError::WhoAmIMismatch`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, ImuError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`ImuError::Impl`] with `f`.
+    ///
+    /// The IMU specific kinds, e.g. [`ImuError::WhoAmIMismatch`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            ImuError::Impl(e) => ImuError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// IMU errors use the high byte `0x26` and the index of the error kind as low byte, e.g.
+    /// `0x2601` for `WhoAmIMismatch`. See the [crate level documentation](crate#error-codes) for
+    /// details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            ImuError::WhoAmIMismatch => 0x2601,
+            ImuError::SelfTestFailed => 0x2602,
+            ImuError::DataNotReady => 0x2603,
+            ImuError::FifoOverflow => 0x2604,
+            ImuError::CalibrationFailed => 0x2605,
+            ImuError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`ImuError::to_code`], e.g. `0x2601` to
+    /// [`ImuError::WhoAmIMismatch`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the IMU error kinds nor to an
+    /// [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x2601 => Some(ImuError::WhoAmIMismatch),
+            0x2602 => Some(ImuError::SelfTestFailed),
+            0x2603 => Some(ImuError::DataNotReady),
+            0x2604 => Some(ImuError::FifoOverflow),
+            0x2605 => Some(ImuError::CalibrationFailed),
+            0x0000..=0x00ff => ImplError::from_code(code).map(ImuError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. `WhoAmIMismatch` and `SelfTestFailed` are
+    /// [`Severity::Fatal`]. All other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            ImuError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            ImuError::WhoAmIMismatch | ImuError::SelfTestFailed => Severity::Fatal,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"imu"`, the stable lowercase name of the IMU peripheral, for all kinds including
+    /// `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "imu"
+    }
+
+    /// Returns the name of the error kind, e.g. `"WhoAmIMismatch"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            ImuError::WhoAmIMismatch => "WhoAmIMismatch",
+            ImuError::SelfTestFailed => "SelfTestFailed",
+            ImuError::DataNotReady => "DataNotReady",
+            ImuError::FifoOverflow => "FifoOverflow",
+            ImuError::CalibrationFailed => "CalibrationFailed",
+            ImuError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `DataNotReady` maps to
+    /// `-EAGAIN`. `WhoAmIMismatch` maps to `-ENODEV`. `FifoOverflow` maps to `-EOVERFLOW`. All
+    /// other kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            ImuError::Impl(e) => e.to_errno(),
+            ImuError::DataNotReady => -errno::EAGAIN,
+            ImuError::WhoAmIMismatch => -errno::ENODEV,
+            ImuError::FifoOverflow => -errno::EOVERFLOW,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for ImuError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImuError::WhoAmIMismatch => f.write_str("unexpected device identification"),
+            ImuError::SelfTestFailed => f.write_str("self test failed"),
+            ImuError::DataNotReady => f.write_str("data not ready"),
+            ImuError::FifoOverflow => f.write_str("FIFO overflow"),
+            ImuError::CalibrationFailed => f.write_str("calibration failed"),
+            ImuError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for ImuError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            ImuError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(ImuError, impl_error);
+
+impl PeripheralError for ImuError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a ImuError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a ImuError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[ImuError] = &[
+        ImuError::WhoAmIMismatch,
+        ImuError::SelfTestFailed,
+        ImuError::DataNotReady,
+        ImuError::FifoOverflow,
+        ImuError::CalibrationFailed,
+        ImuError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(ImuError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (ImuError::WhoAmIMismatch, "unexpected device identification"),
+            (ImuError::SelfTestFailed, "self test failed"),
+            (ImuError::DataNotReady, "data not ready"),
+            (ImuError::FifoOverflow, "FIFO overflow"),
+            (ImuError::CalibrationFailed, "calibration failed"),
+            (
+                ImuError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(retryable, [&ImuError::DataNotReady]);
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(codes, [0x2601, 0x2602, 0x2603, 0x2604, 0x2605, 0x0001,]);
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Fatal,
+                Severity::Fatal,
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "imu");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(millis, [None, None, Some(1), None, None, None,]);
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::ENODEV,
+                -errno::EIO,
+                -errno::EAGAIN,
+                -errno::EOVERFLOW,
+                -errno::EIO,
+                -errno::EIO,
+            ]
+        );
+    }
+}
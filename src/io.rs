@@ -0,0 +1,186 @@
+//! Conversions into the error kinds of [`embedded-io`](https://docs.rs/embedded-io).
+
+use crate::{ImplError, SerialError, UsbError};
+use embedded_io::{Error, ErrorKind};
+
+/// Maps an [`ImplError`] to its closest `embedded-io` counterpart.
+///
+/// | `ImplError`            | `ErrorKind`         |
+/// |------------------------|---------------------|
+/// | `Disconnected`         | `NotConnected`      |
+/// | `OutOfMemory`          | `OutOfMemory`       |
+/// | `TimedOut`             | `TimedOut`          |
+/// | `InvalidConfiguration` | `InvalidInput`      |
+/// | `CouldNotOpen`         | `ConnectionRefused` |
+/// | `PermissionDenied`     | `PermissionDenied`  |
+/// | `Cancelled`            | `Interrupted`       |
+/// | `Unsupported`          | `Unsupported`       |
+/// | all other kinds        | `Other`             |
+impl From<ImplError> for ErrorKind {
+    fn from(e: ImplError) -> Self {
+        match e {
+            ImplError::Disconnected => ErrorKind::NotConnected,
+            ImplError::OutOfMemory => ErrorKind::OutOfMemory,
+            ImplError::TimedOut { .. } => ErrorKind::TimedOut,
+            ImplError::InvalidConfiguration => ErrorKind::InvalidInput,
+            ImplError::CouldNotOpen => ErrorKind::ConnectionRefused,
+            ImplError::PermissionDenied => ErrorKind::PermissionDenied,
+            ImplError::Cancelled => ErrorKind::Interrupted,
+            ImplError::Unsupported { .. } => ErrorKind::Unsupported,
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+/// Maps a [`SerialError`] to its closest `embedded-io` counterpart.
+///
+/// | `SerialError`                     | `ErrorKind`                   |
+/// |-----------------------------------|-------------------------------|
+/// | `FrameFormat`, `Parity`, `Noise`  | `InvalidData`                 |
+/// | `BufferFull`                      | `OutOfMemory`                 |
+/// | `Impl`                            | see [`ImplError`] mapping     |
+/// | all other kinds                   | `Other`                       |
+impl From<SerialError> for ErrorKind {
+    fn from(e: SerialError) -> Self {
+        match e {
+            SerialError::FrameFormat | SerialError::Parity | SerialError::Noise(_) => {
+                ErrorKind::InvalidData
+            }
+            SerialError::BufferFull => ErrorKind::OutOfMemory,
+            SerialError::Impl(e) => e.into(),
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+impl Error for SerialError {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::from(*self)
+    }
+}
+
+/// Maps a [`UsbError`] to its closest `embedded-io` counterpart.
+///
+/// `WouldBlock` maps to `Other` since `embedded-io` signals a blocking operation on a different
+/// layer (e.g. via `nb::Error::WouldBlock` or by awaiting).
+///
+/// | `UsbError`                                                     | `ErrorKind`               |
+/// |----------------------------------------------------------------|---------------------------|
+/// | `ParseError`, `DataToggleError`                                | `InvalidData`             |
+/// | `BufferOverflow`, `EndpointOverflow`, `EndpointMemoryOverflow` | `OutOfMemory`             |
+/// | `InvalidEndpoint`                                              | `InvalidInput`            |
+/// | `Unsupported`                                                  | `Unsupported`             |
+/// | `BusReset`                                                     | `ConnectionReset`         |
+/// | `Suspended`                                                    | `NotConnected`            |
+/// | `Impl`                                                         | see [`ImplError`] mapping |
+/// | all other kinds                                                | `Other`                   |
+impl From<UsbError> for ErrorKind {
+    fn from(e: UsbError) -> Self {
+        match e {
+            UsbError::ParseError | UsbError::DataToggleError => ErrorKind::InvalidData,
+            UsbError::BufferOverflow { .. }
+            | UsbError::EndpointOverflow
+            | UsbError::EndpointMemoryOverflow => ErrorKind::OutOfMemory,
+            UsbError::InvalidEndpoint { .. } => ErrorKind::InvalidInput,
+            UsbError::Unsupported => ErrorKind::Unsupported,
+            UsbError::BusReset => ErrorKind::ConnectionReset,
+            UsbError::Suspended => ErrorKind::NotConnected,
+            UsbError::Impl(e) => e.into(),
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+impl Error for UsbError {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::from(*self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serial::NoiseKind;
+
+    #[test]
+    fn impl_error_kind() {
+        let cases = [
+            (ImplError::Internal, ErrorKind::Other),
+            (ImplError::Disconnected, ErrorKind::NotConnected),
+            (ImplError::OutOfMemory, ErrorKind::OutOfMemory),
+            (ImplError::TimedOut { after_ms: None }, ErrorKind::TimedOut),
+            (ImplError::Asleep, ErrorKind::Other),
+            (ImplError::PowerDown, ErrorKind::Other),
+            (ImplError::InvalidConfiguration, ErrorKind::InvalidInput),
+            (ImplError::CouldNotOpen, ErrorKind::ConnectionRefused),
+            (ImplError::PermissionDenied, ErrorKind::PermissionDenied),
+            (ImplError::Busy, ErrorKind::Other),
+            (ImplError::NotInitialized, ErrorKind::Other),
+            (ImplError::AlreadyInitialized, ErrorKind::Other),
+            (ImplError::Cancelled, ErrorKind::Interrupted),
+            (ImplError::ResourceBusy { resource: "" }, ErrorKind::Other),
+            (
+                ImplError::Unsupported { feature: "" },
+                ErrorKind::Unsupported,
+            ),
+        ];
+        for (e, kind) in cases.iter() {
+            assert_eq!(ErrorKind::from(*e), *kind);
+        }
+    }
+
+    #[test]
+    fn serial_error_kind() {
+        let cases = [
+            (SerialError::Overrun, ErrorKind::Other),
+            (SerialError::FrameFormat, ErrorKind::InvalidData),
+            (SerialError::Parity, ErrorKind::InvalidData),
+            (
+                SerialError::Noise(NoiseKind::Glitch),
+                ErrorKind::InvalidData,
+            ),
+            (
+                SerialError::Noise(NoiseKind::Sustained),
+                ErrorKind::InvalidData,
+            ),
+            (SerialError::Break, ErrorKind::Other),
+            (SerialError::FlowControl, ErrorKind::Other),
+            (SerialError::BufferFull, ErrorKind::OutOfMemory),
+            (SerialError::Impl(ImplError::Internal), ErrorKind::Other),
+        ];
+        for (e, kind) in cases.iter() {
+            assert_eq!(Error::kind(e), *kind);
+        }
+    }
+
+    #[test]
+    fn usb_error_kind() {
+        let cases = [
+            (UsbError::WouldBlock, ErrorKind::Other),
+            (UsbError::ParseError, ErrorKind::InvalidData),
+            (
+                UsbError::BufferOverflow {
+                    needed: 0,
+                    available: 0,
+                },
+                ErrorKind::OutOfMemory,
+            ),
+            (UsbError::EndpointOverflow, ErrorKind::OutOfMemory),
+            (UsbError::EndpointMemoryOverflow, ErrorKind::OutOfMemory),
+            (
+                UsbError::InvalidEndpoint { address: 0 },
+                ErrorKind::InvalidInput,
+            ),
+            (UsbError::Unsupported, ErrorKind::Unsupported),
+            (UsbError::InvalidState, ErrorKind::Other),
+            (UsbError::Stalled, ErrorKind::Other),
+            (UsbError::BusReset, ErrorKind::ConnectionReset),
+            (UsbError::Suspended, ErrorKind::NotConnected),
+            (UsbError::DataToggleError, ErrorKind::InvalidData),
+            (UsbError::Impl(ImplError::Internal), ErrorKind::Other),
+        ];
+        for (e, kind) in cases.iter() {
+            assert_eq!(Error::kind(e), *kind);
+        }
+    }
+}
@@ -0,0 +1,306 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// An IR specific error.
+///
+/// This error type contains errors specific to IR (Infrared) transceiver peripherals. Also it has
+/// an `Impl` kind to pass through implementation specific errors occurring while trying to use an
+/// IR peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum IrError {
+    /// The received pulse train could not be decoded
+    DecodeError,
+    /// The protocol of the received or requested frame is not supported
+    UnsupportedProtocol,
+    /// The frame was not completed in time
+    Timeout,
+    /// The frame exceeds the maximum supported length
+    FrameTooLong,
+    /// The carrier frequency is missing or out of range
+    CarrierError,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl IrError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `DecodeError` and `Timeout` are transient and thus retryable. `Impl` errors are retryable if
+    /// the wrapped [`ImplError`] is. All other kinds are hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            IrError::DecodeError | IrError::Timeout => true,
+            IrError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`]. `DecodeError` and `Timeout`
+    /// suggest 1 ms, all other kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            IrError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`IrError::Impl`] and `false` for the IR specific kinds, e.g.
+    /// [`IrError::DecodeError`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, IrError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`IrError::Impl`] with `f`.
+    ///
+    /// The IR specific kinds, e.g. [`IrError::DecodeError`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            IrError::Impl(e) => IrError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// IR errors use the high byte `0x1d` and the index of the error kind as low byte, e.g.
+    /// `0x1d01` for `DecodeError`. See the [crate level documentation](crate#error-codes) for
+    /// details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            IrError::DecodeError => 0x1d01,
+            IrError::UnsupportedProtocol => 0x1d02,
+            IrError::Timeout => 0x1d03,
+            IrError::FrameTooLong => 0x1d04,
+            IrError::CarrierError => 0x1d05,
+            IrError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`IrError::to_code`], e.g. `0x1d01` to [`IrError::DecodeError`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the IR error kinds nor to an
+    /// [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x1d01 => Some(IrError::DecodeError),
+            0x1d02 => Some(IrError::UnsupportedProtocol),
+            0x1d03 => Some(IrError::Timeout),
+            0x1d04 => Some(IrError::FrameTooLong),
+            0x1d05 => Some(IrError::CarrierError),
+            0x0000..=0x00ff => ImplError::from_code(code).map(IrError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. `UnsupportedProtocol` is [`Severity::Fatal`]. All other
+    /// kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            IrError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            IrError::UnsupportedProtocol => Severity::Fatal,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"ir"`, the stable lowercase name of the IR peripheral, for all kinds including
+    /// `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "ir"
+    }
+
+    /// Returns the name of the error kind, e.g. `"DecodeError"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            IrError::DecodeError => "DecodeError",
+            IrError::UnsupportedProtocol => "UnsupportedProtocol",
+            IrError::Timeout => "Timeout",
+            IrError::FrameTooLong => "FrameTooLong",
+            IrError::CarrierError => "CarrierError",
+            IrError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `DecodeError` maps to
+    /// `-EBADMSG`. `FrameTooLong` maps to `-EMSGSIZE`. `UnsupportedProtocol` maps to `-EOPNOTSUPP`.
+    /// `Timeout` maps to `-ETIMEDOUT`. All other kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            IrError::Impl(e) => e.to_errno(),
+            IrError::DecodeError => -errno::EBADMSG,
+            IrError::FrameTooLong => -errno::EMSGSIZE,
+            IrError::UnsupportedProtocol => -errno::EOPNOTSUPP,
+            IrError::Timeout => -errno::ETIMEDOUT,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for IrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IrError::DecodeError => f.write_str("decode error"),
+            IrError::UnsupportedProtocol => f.write_str("unsupported protocol"),
+            IrError::Timeout => f.write_str("timeout"),
+            IrError::FrameTooLong => f.write_str("frame too long"),
+            IrError::CarrierError => f.write_str("carrier error"),
+            IrError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for IrError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            IrError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(IrError, impl_error);
+
+impl PeripheralError for IrError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a IrError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a IrError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[IrError] = &[
+        IrError::DecodeError,
+        IrError::UnsupportedProtocol,
+        IrError::Timeout,
+        IrError::FrameTooLong,
+        IrError::CarrierError,
+        IrError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(IrError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (IrError::DecodeError, "decode error"),
+            (IrError::UnsupportedProtocol, "unsupported protocol"),
+            (IrError::Timeout, "timeout"),
+            (IrError::FrameTooLong, "frame too long"),
+            (IrError::CarrierError, "carrier error"),
+            (
+                IrError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(retryable, [&IrError::DecodeError, &IrError::Timeout]);
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(codes, [0x1d01, 0x1d02, 0x1d03, 0x1d04, 0x1d05, 0x0001,]);
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Recoverable,
+                Severity::Fatal,
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "ir");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(millis, [Some(1), None, Some(1), None, None, None,]);
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::EBADMSG,
+                -errno::EOPNOTSUPP,
+                -errno::ETIMEDOUT,
+                -errno::EMSGSIZE,
+                -errno::EIO,
+                -errno::EIO,
+            ]
+        );
+    }
+}
@@ -0,0 +1,300 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// A keypad specific error.
+///
+/// This error type contains errors specific to keypad and key matrix scanning peripherals. Also it
+/// has an `Impl` kind to pass through implementation specific errors occurring while trying to use
+/// a keypad peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum KeypadError {
+    /// Multiple simultaneously pressed keys made the scan result ambiguous
+    Ghosting,
+    /// The matrix scan did not complete in time
+    ScanTimeout,
+    /// The requested column line is not available
+    ColumnUnavailable,
+    /// The requested row line is not available
+    RowUnavailable,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl KeypadError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `Ghosting` and `ScanTimeout` are transient and thus retryable. `Impl` errors are retryable
+    /// if the wrapped [`ImplError`] is. All other kinds are hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            KeypadError::Ghosting | KeypadError::ScanTimeout => true,
+            KeypadError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`]. `Ghosting` and `ScanTimeout`
+    /// suggest 1 ms, all other kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            KeypadError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`KeypadError::Impl`] and `false` for the keypad specific kinds, e.g.
+    /// [`KeypadError::Ghosting`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, KeypadError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`KeypadError::Impl`] with `f`.
+    ///
+    /// The keypad specific kinds, e.g. [`KeypadError::Ghosting`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            KeypadError::Impl(e) => KeypadError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// Keypad errors use the high byte `0x29` and the index of the error kind as low byte, e.g.
+    /// `0x2901` for `Ghosting`. See the [crate level documentation](crate#error-codes) for details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            KeypadError::Ghosting => 0x2901,
+            KeypadError::ScanTimeout => 0x2902,
+            KeypadError::ColumnUnavailable => 0x2903,
+            KeypadError::RowUnavailable => 0x2904,
+            KeypadError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`KeypadError::to_code`], e.g. `0x2901` to
+    /// [`KeypadError::Ghosting`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the keypad error kinds nor to
+    /// an [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x2901 => Some(KeypadError::Ghosting),
+            0x2902 => Some(KeypadError::ScanTimeout),
+            0x2903 => Some(KeypadError::ColumnUnavailable),
+            0x2904 => Some(KeypadError::RowUnavailable),
+            0x0000..=0x00ff => ImplError::from_code(code).map(KeypadError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. `ColumnUnavailable` and `RowUnavailable` are
+    /// [`Severity::Fatal`]. All other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            KeypadError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            KeypadError::ColumnUnavailable | KeypadError::RowUnavailable => Severity::Fatal,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"keypad"`, the stable lowercase name of the keypad peripheral, for all kinds
+    /// including `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "keypad"
+    }
+
+    /// Returns the name of the error kind, e.g. `"Ghosting"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            KeypadError::Ghosting => "Ghosting",
+            KeypadError::ScanTimeout => "ScanTimeout",
+            KeypadError::ColumnUnavailable => "ColumnUnavailable",
+            KeypadError::RowUnavailable => "RowUnavailable",
+            KeypadError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `Ghosting` maps to `-EAGAIN`.
+    /// `ColumnUnavailable` and `RowUnavailable` map to `-ENODEV`. `ScanTimeout` maps to
+    /// `-ETIMEDOUT`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            KeypadError::Impl(e) => e.to_errno(),
+            KeypadError::Ghosting => -errno::EAGAIN,
+            KeypadError::ColumnUnavailable | KeypadError::RowUnavailable => -errno::ENODEV,
+            KeypadError::ScanTimeout => -errno::ETIMEDOUT,
+        }
+    }
+}
+
+impl fmt::Display for KeypadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeypadError::Ghosting => f.write_str("ambiguous key combination (ghosting)"),
+            KeypadError::ScanTimeout => f.write_str("scan timed out"),
+            KeypadError::ColumnUnavailable => f.write_str("column unavailable"),
+            KeypadError::RowUnavailable => f.write_str("row unavailable"),
+            KeypadError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for KeypadError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            KeypadError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(KeypadError, impl_error);
+
+impl PeripheralError for KeypadError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a KeypadError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a KeypadError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[KeypadError] = &[
+        KeypadError::Ghosting,
+        KeypadError::ScanTimeout,
+        KeypadError::ColumnUnavailable,
+        KeypadError::RowUnavailable,
+        KeypadError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(KeypadError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (
+                KeypadError::Ghosting,
+                "ambiguous key combination (ghosting)",
+            ),
+            (KeypadError::ScanTimeout, "scan timed out"),
+            (KeypadError::ColumnUnavailable, "column unavailable"),
+            (KeypadError::RowUnavailable, "row unavailable"),
+            (
+                KeypadError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(
+            retryable,
+            [&KeypadError::Ghosting, &KeypadError::ScanTimeout]
+        );
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(codes, [0x2901, 0x2902, 0x2903, 0x2904, 0x0001,]);
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Fatal,
+                Severity::Fatal,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "keypad");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(millis, [Some(1), Some(1), None, None, None,]);
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::EAGAIN,
+                -errno::ETIMEDOUT,
+                -errno::ENODEV,
+                -errno::ENODEV,
+                -errno::EIO,
+            ]
+        );
+    }
+}
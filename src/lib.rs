@@ -1,153 +1,1028 @@
+//! This crate contains a variety of universal error types which can be used to universally model
+//! conditions which can typically arise for certain peripherals.
+//!
+//! When used by HAL implementations, they allow drivers and applications alike to generically
+//! handle those situations without the error handling being specific to the hardware it is
+//! supposed to run on (which is usually not possible to implement in drivers).
+//!
+//! All of the enums in this crate are marked as `#[non_exhaustive]` to allow for additions of new
+//! error kinds without requiring a breaking change and version bump. The error enums are also
+//! marked as `#[must_use]` so constructing an error and dropping it unhandled causes a warning.
+//!
+//! # Error codes
+//!
+//! For transmission over constrained links every error kind can be converted into a stable `u16`
+//! code via `to_code()` and back via `from_code()`. The high byte of the code identifies the
+//! peripheral and the low byte the 1-based index of the error kind within its enum. Allocated
+//! codes are never reordered or reused, new error kinds are assigned the next free index.
+//!
+//! Kinds wrapping a nested error without data, e.g. [`I2cError::NACK`], are assigned one code per
+//! nested kind. The first nested kind uses the code of the wrapping kind, the others are assigned
+//! the next free indices like new error kinds. Other data carried by an error kind is not encoded,
+//! `from_code()` restores numbers as `0`, optional values as `None` and labels as `""`.
+//!
+//! `Impl` errors are encoded with the code of the wrapped [`ImplError`], which uses the high byte
+//! `0x00`. The peripherals are allocated as follows:
+//!
+//! | High byte | Error type                      |
+//! |-----------|---------------------------------|
+//! | `0x00`    | [`ImplError`]                   |
+//! | `0x01`    | [`GpioError`]                   |
+//! | `0x02`    | [`UsbError`]                    |
+//! | `0x03`    | [`SpiError`]                    |
+//! | `0x04`    | [`SerialError`]                 |
+//! | `0x05`    | [`I2cError`]                    |
+//! | `0x06`    | [`mci::MciError`]               |
+//! | `0x07`    | [`can::CanError`]               |
+//! | `0x08`    | [`adc::AdcError`]               |
+//! | `0x09`    | [`dac::DacError`]               |
+//! | `0x0a`    | [`pwm::PwmError`]               |
+//! | `0x0b`    | [`timer::TimerError`]           |
+//! | `0x0c`    | [`rtc::RtcError`]               |
+//! | `0x0d`    | [`watchdog::WatchdogError`]     |
+//! | `0x0e`    | [`flash::FlashError`]           |
+//! | `0x0f`    | [`dma::DmaError`]               |
+//! | `0x10`    | [`ethernet::EthernetError`]     |
+//! | `0x11`    | [`i2s::I2sError`]               |
+//! | `0x12`    | [`qspi::QspiError`]             |
+//! | `0x13`    | [`rng::RngError`]               |
+//! | `0x14`    | [`crypto::CryptoError`]         |
+//! | `0x15`    | [`comparator::ComparatorError`] |
+//! | `0x16`    | [`clock::ClockError`]           |
+//! | `0x17`    | [`power::PowerError`]           |
+//! | `0x18`    | [`one_wire::OneWireError`]      |
+//! | `0x19`    | [`lin::LinError`]               |
+//! | `0x1a`    | [`touch::TouchError`]           |
+//! | `0x1b`    | [`encoder::EncoderError`]       |
+//! | `0x1c`    | [`smartcard::SmartCardError`]   |
+//! | `0x1d`    | [`ir::IrError`]                 |
+//! | `0x1e`    | [`fmc::FmcError`]               |
+//! | `0x1f`    | [`crc::CrcUnitError`]           |
+//! | `0x20`    | [`opamp::OpAmpError`]           |
+//! | `0x21`    | [`cec::CecError`]               |
+//! | `0x22`    | [`pdm::PdmError`]               |
+//! | `0x23`    | [`gnss::GnssError`]             |
+//! | `0x24`    | [`modem::ModemError`]           |
+//! | `0x25`    | [`env_sensor::EnvSensorError`]  |
+//! | `0x26`    | [`imu::ImuError`]               |
+//! | `0x27`    | [`display::DisplayError`]       |
+//! | `0x28`    | [`ble::BleError`]               |
+//! | `0x29`    | [`keypad::KeypadError`]         |
+//! | `0x2a`    | [`stepper::StepperError`]       |
+//! | `0x2b`    | [`servo::ServoError`]           |
+//! | `0x2c`    | [`lora::LoRaError`]             |
+//! | `0x2d`    | [`wifi::WifiError`]             |
+//! | `0x2e`    | [`battery::BatteryError`]       |
+
 #![no_std]
 
+#[cfg(test)]
+extern crate std;
+
+use core::fmt;
+
+#[cfg(feature = "adc")]
+pub mod adc;
+#[cfg(feature = "battery")]
+pub mod battery;
+#[cfg(feature = "ble")]
+pub mod ble;
+#[cfg(feature = "can")]
+pub mod can;
+#[cfg(feature = "cec")]
+pub mod cec;
+#[cfg(feature = "clock")]
+pub mod clock;
+#[cfg(feature = "comparator")]
+pub mod comparator;
+#[cfg(feature = "crc")]
+pub mod crc;
+#[cfg(feature = "crypto")]
+pub mod crypto;
+#[cfg(feature = "dac")]
+pub mod dac;
+#[cfg(feature = "display")]
+pub mod display;
+#[cfg(feature = "dma")]
+pub mod dma;
+#[cfg(feature = "encoder")]
+pub mod encoder;
+#[cfg(feature = "env_sensor")]
+pub mod env_sensor;
+#[cfg(feature = "ethernet")]
+pub mod ethernet;
+#[cfg(feature = "flash")]
+pub mod flash;
+#[cfg(feature = "fmc")]
+pub mod fmc;
+#[cfg(feature = "gnss")]
+pub mod gnss;
+#[cfg(feature = "gpio")]
+pub mod gpio;
+#[cfg(feature = "i2c")]
+pub mod i2c;
+#[cfg(feature = "i2s")]
+pub mod i2s;
+#[cfg(feature = "imu")]
+pub mod imu;
+#[cfg(feature = "ir")]
+pub mod ir;
+#[cfg(feature = "keypad")]
+pub mod keypad;
+#[cfg(feature = "lin")]
+pub mod lin;
+#[cfg(feature = "lora")]
+pub mod lora;
+#[cfg(feature = "mci")]
 pub mod mci;
+#[cfg(feature = "modem")]
+pub mod modem;
+#[cfg(feature = "one_wire")]
+pub mod one_wire;
+#[cfg(feature = "opamp")]
+pub mod opamp;
+#[cfg(feature = "pdm")]
+pub mod pdm;
+#[cfg(feature = "power")]
+pub mod power;
+pub mod prelude;
+#[cfg(feature = "pwm")]
+pub mod pwm;
+#[cfg(feature = "qspi")]
+pub mod qspi;
+#[cfg(feature = "rng")]
+pub mod rng;
+#[cfg(feature = "rtc")]
+pub mod rtc;
+#[cfg(feature = "serial")]
+pub mod serial;
+#[cfg(feature = "servo")]
+pub mod servo;
+#[cfg(feature = "smartcard")]
+pub mod smartcard;
+#[cfg(feature = "spi")]
+pub mod spi;
+#[cfg(feature = "stepper")]
+pub mod stepper;
+#[cfg(feature = "timer")]
+pub mod timer;
+#[cfg(feature = "touch")]
+pub mod touch;
+#[cfg(feature = "usb")]
+pub mod usb;
+#[cfg(feature = "watchdog")]
+pub mod watchdog;
+#[cfg(feature = "wifi")]
+pub mod wifi;
+
+mod errno;
+#[cfg(feature = "embedded-hal")]
+mod hal;
+mod impl_error;
+#[cfg(feature = "embedded-io")]
+mod io;
+mod macros;
+#[cfg(feature = "nb")]
+mod nb;
+
+#[cfg(feature = "gpio")]
+pub use gpio::GpioError;
+#[cfg(feature = "i2c")]
+pub use i2c::{I2cError, NackSource};
+#[cfg(feature = "serial")]
+pub use serial::{NoiseKind, SerialError};
+#[cfg(feature = "spi")]
+pub use spi::SpiError;
+#[cfg(feature = "usb")]
+pub use usb::UsbError;
 
-/// This crate contains a variety of universal error types which can be used to universally model
-/// conditions which can typically arise for certain peripherals.
+pub use impl_error::ImplError;
+
+/// Common interface of all error types in this crate.
 ///
-/// When used by HAL implementations, they allow drivers and applications alike to generically
-/// handle those situations without the error handling being specific to the hardware it is
-/// supposed to run on (which is usually not possible to implement in drivers).
+/// This allows generic code like logging or telemetry layers to handle errors of any peripheral
+/// uniformly, e.g. via `&dyn PeripheralError`.
 ///
-/// All of the enums in this crate are marked as `#[non_exhaustive]` to allow for additions of new
-/// error kinds without requiring a breaking change and version bump.
+/// Every peripheral specific error has an `Impl(ImplError)` kind to pass through implementation
+/// specific errors, so [`impl_error`](Self::impl_error) can always surface them. This is checked at
+/// compile time for every peripheral listed in [`AnyError`], which is where a new peripheral module
+/// has to be added. Only the nested error types describing the details of another kind, e.g.
+/// `mci::SetupError`, are exempt.
+pub trait PeripheralError {
+    /// Returns the wrapped implementation specific error, if any.
+    fn impl_error(&self) -> Option<&ImplError>;
 
-/// A GPIO (General input/output) specific error.
-///
-/// This error type contains errors specific to GPIO peripherals. Also it has an `Impl` kind to
-/// pass through implementation specific errors occuring while trying to use a GPIO peripheral.
-#[derive(Debug, Clone)]
-#[non_exhaustive]
-pub enum GpioError {
-    /// The peripheral is in the wrong operational mode for the intended operation
-    WrongMode,
-    /// Implementation specific error (shared across all peripheral specific error kinds)
-    Impl(ImplError),
+    /// Returns the name of the peripheral the error belongs to, e.g. `"i2c"`.
+    fn peripheral(&self) -> &'static str;
+
+    /// Returns the name of the error kind, e.g. `"NACK"`.
+    fn variant_name(&self) -> &'static str;
+
+    /// Returns the severity of the error.
+    fn severity(&self) -> Severity;
 }
 
-/// A USB specific error.
+/// Returns the highest [`Severity`] of `errors`, e.g. to decide the overall system health from a
+/// batch of faults.
 ///
-/// This error type contains errors specific to USB peripherals. Also it has an `Impl` kind to pass
-/// through implementation specific errors occuring while trying to use a USB peripheral.
-#[derive(Debug, Clone)]
-#[non_exhaustive]
-pub enum UsbError {
-    /// An operation would block because the device is currently busy or there is no data available.
-    WouldBlock,
-    /// Parsing failed due to invalid input.
-    ParseError,
-    /// A buffer too short for the data to read was passed, or provided data cannot fit within
-    /// length constraints.
-    BufferOverflow,
-    /// Classes attempted to allocate more endpoints than the peripheral supports.
-    EndpointOverflow,
-    /// Classes attempted to allocate more packet buffer memory than the peripheral supports. This
-    /// can be caused by either a single class trying to allocate a packet buffer larger than the
-    /// peripheral supports per endpoint, or multiple allocated endpoints together using more memory
-    /// than the peripheral has available for the buffers.
-    EndpointMemoryOverflow,
-    /// The endpoint address is invalid or already used.
-    InvalidEndpoint,
-    /// Operation is not supported by device or configuration.
-    Unsupported,
-    /// Operation is not valid in the current state of the object.
-    InvalidState,
-    /// Implementation specific error (shared across all peripheral specific error kinds)
-    Impl(ImplError),
+/// Returns `None` if `errors` is empty.
+pub fn max_severity<'a>(
+    errors: impl IntoIterator<Item = &'a dyn PeripheralError>,
+) -> Option<Severity> {
+    errors.into_iter().map(|e| e.severity()).max()
 }
 
-/// A SPI specific error.
-///
-/// This error type contains errors specific to SPI peripherals. Also it has an `Impl` kind to pass
-/// through implementation specific errors occuring while trying to use a SPI peripheral.
-#[derive(Debug, Clone)]
-#[non_exhaustive]
-pub enum SpiError {
-    /// The peripheral receive buffer was overrun
-    Overrun,
-    /// Multiple devices on the SPI bus are trying across each other, e.g. in a multi-master setup
-    ModeFault,
-    /// CRC does not match the received data
-    CRCError,
-    /// Received data does not conform to the peripheral configuration
-    FrameFormat,
-    /// Implementation specific error (shared across all peripheral specific error kinds)
-    Impl(ImplError),
+/// Extension trait for results carrying a [`PeripheralError`].
+pub trait ResultExt<T, E: PeripheralError> {
+    /// Returns the contained `Ok` value.
+    ///
+    /// Intended for prototyping, this is similar to [`Result::unwrap`] but does not require the
+    /// error to implement `Debug`, which avoids pulling its formatting machinery into the binary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an `Err`, with a message containing the [`peripheral`] and the
+    /// [`variant_name`] of the error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_error::{ImplError, ResultExt};
+    ///
+    /// let r: Result<u8, ImplError> = Ok(42);
+    /// assert_eq!(r.unwrap_peripheral(), 42);
+    /// ```
+    ///
+    /// [`peripheral`]: PeripheralError::peripheral
+    /// [`variant_name`]: PeripheralError::variant_name
+    fn unwrap_peripheral(self) -> T;
 }
 
-/// A Serial specific error.
-///
-/// This error type contains errors specific to Serial peripherals. Also it has an `Impl` kind to pass
-/// through implementation specific errors occurring while trying to use a Serial peripheral.
-#[derive(Debug, Clone)]
-#[non_exhaustive]
-pub enum SerialError {
-    /// The peripheral receive buffer was overrun.
-    Overrun,
-    /// Received data does not conform to the peripheral configuration.
-    /// Can be caused by a misconfigured device on either end of the serial line.
-    FrameFormat,
-    /// Parity check failed.
-    Parity,
-    /// Serial line is too noisy to read valid data.
-    Noise,
-    /// Implementation specific error (shared across all peripheral specific error kinds).
-    Impl(ImplError),
+impl<T, E: PeripheralError> ResultExt<T, E> for Result<T, E> {
+    #[track_caller]
+    fn unwrap_peripheral(self) -> T {
+        match self {
+            Ok(t) => t,
+            Err(e) => panic!("{} error: {}", e.peripheral(), e.variant_name()),
+        }
+    }
 }
 
-/// An I2C specific error.
+/// A coarse, peripheral independent classification of an error.
 ///
-/// This error type contains errors specific to I2C peripherals. Also it has an `Impl` kind to pass
-/// through implementation specific errors occurring while trying to use an I2C peripheral.
-#[derive(Debug, Clone)]
+/// Severities are ordered by their impact, i.e. `Recoverable < Degraded < Fatal`. All error types
+/// classify exactly their retryable kinds, i.e. those whose `is_retryable()` returns `true`, as
+/// `Recoverable`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
-pub enum I2cError {
-    /// An unspecific bus error occured
-    Bus,
-    /// The arbitration was lost, e.g. electrical problems with the clock signal
-    ArbitrationLoss,
-    /// A bus operation received a NACK, e.g. due to the addressed device not being available on
-    /// the bus or device not being ready to process any requests at the moment
-    NACK,
-    /// The peripheral receive buffer was overrun
-    Overrun,
-    /// The peripheral send buffer ran out of data
-    Underrun,
-    /// SMBus Error checking byte mismatch
-    PacketErrorChecking,
-    /// SMBus Timeout error
-    Timeout,
-    /// SMBus Alert received
-    Alert,
-    /// Implementation specific error (shared across all peripheral specific error kinds)
-    Impl(ImplError),
+pub enum Severity {
+    /// The condition is temporary, the operation can be retried
+    Recoverable,
+    /// The operation failed but the peripheral may still be usable, possibly after some
+    /// intervention like a reset, a reconfiguration or with reduced functionality
+    Degraded,
+    /// The operation can never succeed with the current hardware or configuration
+    Fatal,
 }
 
-/// A universal implementation specific error.
+/// Defines [`AnyError`] from the list of peripheral errors in its invocation below.
 ///
-/// These error kinds can be used to signal implementation specific errors unrelated to the
-/// specific peripheral. This will be used for all sorts of connectivity problems, e.g. if an
-/// adapter to the peripheral is used or the target peripheral is connected to indirectly (like bus
-/// expanders) or an operating system is controlling the access and denying access.
-#[derive(Debug, Clone)]
-#[non_exhaustive]
-pub enum ImplError {
-    /// Unspecified internal driver error
-    Internal,
-    /// Connection lost, e.g. device adapter was unplugged
-    Disconnected,
-    /// Ran out of memory while trying to allocate required buffers
-    OutOfMemory,
-    /// Operation timed out, please retry
-    TimedOut,
-    /// Peripheral is sleeping or in standby
-    Asleep,
-    /// Peripheral is powered down
-    PowerDown,
-    /// The peripheral cannot work with the specified settings
-    InvalidConfiguration,
-    /// Could not open connection to peripheral
-    CouldNotOpen,
-    /// No sufficient permissions to connect to peripheral
-    PermissionDenied,
+/// Besides the enum itself this generates its `Display`, `Error` and [`PeripheralError`]
+/// implementations, the `From` conversions and the compile time check that every listed error has
+/// an `Impl` kind, so a peripheral only has to be added to the list.
+macro_rules! any_error {
+    (
+        $(#[$attr:meta])*
+        pub enum $any:ident {
+            $(
+                $(#[doc = $doc:literal])*
+                #[cfg(feature = $feature:literal)]
+                $variant:ident($error:ty),
+            )*
+        }
+    ) => {
+        $(#[$attr])*
+        pub enum $any {
+            $(
+                $(#[doc = $doc])*
+                #[cfg(feature = $feature)]
+                $variant($error),
+            )*
+            /// Implementation specific error (shared across all peripheral specific error kinds)
+            Impl(ImplError),
+        }
+
+        impl fmt::Display for $any {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self {
+                    $(
+                        #[cfg(feature = $feature)]
+                        $any::$variant(e) => fmt::Display::fmt(e, f),
+                    )*
+                    $any::Impl(e) => fmt::Display::fmt(e, f),
+                }
+            }
+        }
+
+        impl core::error::Error for $any {
+            fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+                match self {
+                    $(
+                        #[cfg(feature = $feature)]
+                        $any::$variant(e) => Some(e),
+                    )*
+                    $any::Impl(e) => Some(e),
+                }
+            }
+        }
+
+        impl PeripheralError for $any {
+            fn impl_error(&self) -> Option<&ImplError> {
+                match self {
+                    $(
+                        #[cfg(feature = $feature)]
+                        $any::$variant(e) => e.impl_error(),
+                    )*
+                    $any::Impl(e) => Some(e),
+                }
+            }
+
+            fn peripheral(&self) -> &'static str {
+                match self {
+                    $(
+                        #[cfg(feature = $feature)]
+                        $any::$variant(e) => e.peripheral(),
+                    )*
+                    $any::Impl(e) => e.peripheral(),
+                }
+            }
+
+            fn variant_name(&self) -> &'static str {
+                match self {
+                    $(
+                        #[cfg(feature = $feature)]
+                        $any::$variant(e) => e.variant_name(),
+                    )*
+                    $any::Impl(e) => e.variant_name(),
+                }
+            }
+
+            fn severity(&self) -> Severity {
+                match self {
+                    $(
+                        #[cfg(feature = $feature)]
+                        $any::$variant(e) => e.severity(),
+                    )*
+                    $any::Impl(e) => e.severity(),
+                }
+            }
+        }
+
+        $(
+            #[cfg(feature = $feature)]
+            impl From<$error> for $any {
+                fn from(e: $error) -> Self {
+                    $any::$variant(e)
+                }
+            }
+        )*
+
+        // Every peripheral error has to be able to pass through an `ImplError` via an `Impl` kind,
+        // see `PeripheralError`. Forgetting it for a listed peripheral fails to compile here.
+        const _: () = {
+            $(
+                #[cfg(feature = $feature)]
+                let _ = <$error>::Impl(ImplError::Internal);
+            )*
+        };
+    };
+}
+
+any_error! {
+    /// Any error of this crate.
+    ///
+    /// This error type unifies the errors of all peripherals, allowing applications using several
+    /// peripherals to use a single error type and to forward peripheral specific errors via the `?`
+    /// operator. Each variant is only available if the feature of the respective peripheral is
+    /// enabled.
+    ///
+    /// ```
+    /// # #[cfg(all(feature = "i2c", feature = "spi"))]
+    /// # {
+    /// use embedded_error::{AnyError, I2cError, SpiError};
+    ///
+    /// fn transfer(i2c: Result<u8, I2cError>, spi: Result<u8, SpiError>) -> Result<u16, AnyError> {
+    ///     Ok(u16::from(i2c?) + u16::from(spi?))
+    /// }
+    ///
+    /// assert_eq!(transfer(Ok(1), Ok(2)), Ok(3));
+    /// assert_eq!(
+    ///     transfer(Err(I2cError::Overrun), Ok(2)),
+    ///     Err(AnyError::I2c(I2cError::Overrun))
+    /// );
+    /// assert_eq!(
+    ///     transfer(Ok(1), Err(SpiError::ModeFault)),
+    ///     Err(AnyError::Spi(SpiError::ModeFault))
+    /// );
+    /// # }
+    /// ```
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[non_exhaustive]
+    #[must_use]
+    pub enum AnyError {
+        /// ADC error
+        #[cfg(feature = "adc")]
+        Adc(adc::AdcError),
+        /// Battery error
+        #[cfg(feature = "battery")]
+        Battery(battery::BatteryError),
+        /// Bluetooth/BLE error
+        #[cfg(feature = "ble")]
+        Ble(ble::BleError),
+        /// CAN error
+        #[cfg(feature = "can")]
+        Can(can::CanError),
+        /// CEC error
+        #[cfg(feature = "cec")]
+        Cec(cec::CecError),
+        /// Clock error
+        #[cfg(feature = "clock")]
+        Clock(clock::ClockError),
+        /// Comparator error
+        #[cfg(feature = "comparator")]
+        Comparator(comparator::ComparatorError),
+        /// CRC unit error
+        #[cfg(feature = "crc")]
+        CrcUnit(crc::CrcUnitError),
+        /// Crypto error
+        #[cfg(feature = "crypto")]
+        Crypto(crypto::CryptoError),
+        /// DAC error
+        #[cfg(feature = "dac")]
+        Dac(dac::DacError),
+        /// Display error
+        #[cfg(feature = "display")]
+        Display(display::DisplayError),
+        /// DMA error
+        #[cfg(feature = "dma")]
+        Dma(dma::DmaError),
+        /// Quadrature encoder error
+        #[cfg(feature = "encoder")]
+        Encoder(encoder::EncoderError),
+        /// Environmental sensor error
+        #[cfg(feature = "env_sensor")]
+        EnvSensor(env_sensor::EnvSensorError),
+        /// Ethernet error
+        #[cfg(feature = "ethernet")]
+        Ethernet(ethernet::EthernetError),
+        /// Flash error
+        #[cfg(feature = "flash")]
+        Flash(flash::FlashError),
+        /// FMC error
+        #[cfg(feature = "fmc")]
+        Fmc(fmc::FmcError),
+        /// GNSS error
+        #[cfg(feature = "gnss")]
+        Gnss(gnss::GnssError),
+        /// GPIO error
+        #[cfg(feature = "gpio")]
+        Gpio(gpio::GpioError),
+        /// I2C error
+        #[cfg(feature = "i2c")]
+        I2c(i2c::I2cError),
+        /// I2S error
+        #[cfg(feature = "i2s")]
+        I2s(i2s::I2sError),
+        /// IMU error
+        #[cfg(feature = "imu")]
+        Imu(imu::ImuError),
+        /// IR error
+        #[cfg(feature = "ir")]
+        Ir(ir::IrError),
+        /// Keypad error
+        #[cfg(feature = "keypad")]
+        Keypad(keypad::KeypadError),
+        /// LIN error
+        #[cfg(feature = "lin")]
+        Lin(lin::LinError),
+        /// LoRa error
+        #[cfg(feature = "lora")]
+        LoRa(lora::LoRaError),
+        /// MCI error
+        #[cfg(feature = "mci")]
+        Mci(mci::MciError),
+        /// Modem error
+        #[cfg(feature = "modem")]
+        Modem(modem::ModemError),
+        /// 1-Wire error
+        #[cfg(feature = "one_wire")]
+        OneWire(one_wire::OneWireError),
+        /// Op-amp error
+        #[cfg(feature = "opamp")]
+        OpAmp(opamp::OpAmpError),
+        /// PDM error
+        #[cfg(feature = "pdm")]
+        Pdm(pdm::PdmError),
+        /// Power management error
+        #[cfg(feature = "power")]
+        Power(power::PowerError),
+        /// PWM error
+        #[cfg(feature = "pwm")]
+        Pwm(pwm::PwmError),
+        /// QSPI error
+        #[cfg(feature = "qspi")]
+        Qspi(qspi::QspiError),
+        /// RNG error
+        #[cfg(feature = "rng")]
+        Rng(rng::RngError),
+        /// RTC error
+        #[cfg(feature = "rtc")]
+        Rtc(rtc::RtcError),
+        /// Serial error
+        #[cfg(feature = "serial")]
+        Serial(serial::SerialError),
+        /// Servo error
+        #[cfg(feature = "servo")]
+        Servo(servo::ServoError),
+        /// Smart card error
+        #[cfg(feature = "smartcard")]
+        SmartCard(smartcard::SmartCardError),
+        /// SPI error
+        #[cfg(feature = "spi")]
+        Spi(spi::SpiError),
+        /// Stepper motor error
+        #[cfg(feature = "stepper")]
+        Stepper(stepper::StepperError),
+        /// Timer error
+        #[cfg(feature = "timer")]
+        Timer(timer::TimerError),
+        /// Touch sensing error
+        #[cfg(feature = "touch")]
+        Touch(touch::TouchError),
+        /// USB error
+        #[cfg(feature = "usb")]
+        Usb(usb::UsbError),
+        /// Watchdog error
+        #[cfg(feature = "watchdog")]
+        Watchdog(watchdog::WatchdogError),
+        /// Wi-Fi error
+        #[cfg(feature = "wifi")]
+        Wifi(wifi::WifiError),
+    }
+}
+
+crate::impl_error_passthrough!(AnyError);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::error::Error;
+    use core::hash::{Hash, Hasher};
+    #[cfg(feature = "defmt")]
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashSet;
+    use std::string::ToString;
+
+    /// One instance of every [`ImplError`] kind, e.g. to check the `Impl` kinds of the peripheral
+    /// errors.
+    pub(crate) const IMPL_KINDS: &[ImplError] = &[
+        ImplError::Internal,
+        ImplError::Disconnected,
+        ImplError::OutOfMemory,
+        ImplError::TimedOut { after_ms: None },
+        ImplError::Asleep,
+        ImplError::PowerDown,
+        ImplError::InvalidConfiguration,
+        ImplError::CouldNotOpen,
+        ImplError::PermissionDenied,
+        ImplError::Busy,
+        ImplError::NotInitialized,
+        ImplError::AlreadyInitialized,
+        ImplError::Cancelled,
+        ImplError::ResourceBusy { resource: "" },
+        ImplError::Unsupported { feature: "" },
+    ];
+
+    /// Checks that distinct kinds have distinct hashes.
+    pub(crate) fn assert_distinct_hashes<T: Hash>(kinds: &[T]) {
+        let hashes: HashSet<u64> = kinds
+            .iter()
+            .map(|kind| {
+                let mut hasher = DefaultHasher::new();
+                kind.hash(&mut hasher);
+                hasher.finish()
+            })
+            .collect();
+        assert_eq!(hashes.len(), kinds.len());
+    }
+
+    #[cfg(feature = "defmt")]
+    static DEFMT_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+    /// Counts the bytes written by `defmt` instead of transmitting them.
+    #[cfg(feature = "defmt")]
+    #[defmt::global_logger]
+    struct Logger;
+
+    #[cfg(feature = "defmt")]
+    defmt::timestamp!("");
+
+    #[cfg(feature = "defmt")]
+    unsafe impl defmt::Logger for Logger {
+        fn acquire() {}
+
+        unsafe fn flush() {}
+
+        unsafe fn release() {}
+
+        unsafe fn write(bytes: &[u8]) {
+            DEFMT_BYTES.fetch_add(bytes.len(), Ordering::Relaxed);
+        }
+    }
+
+    /// Checks that every kind can be formatted with `defmt`.
+    #[cfg(feature = "defmt")]
+    pub(crate) fn assert_defmt<T: defmt::Format>(kinds: &[T]) {
+        for kind in kinds {
+            let written = DEFMT_BYTES.load(Ordering::Relaxed);
+            defmt::println!("{}", kind);
+            assert!(DEFMT_BYTES.load(Ordering::Relaxed) > written);
+        }
+    }
+
+    /// Checks that every kind survives a round trip through `serde_json`.
+    #[cfg(feature = "serde")]
+    pub(crate) fn assert_serde_round_trip<T>(kinds: &[T])
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned + PartialEq + core::fmt::Debug,
+    {
+        for kind in kinds {
+            let json = serde_json::to_string(kind).unwrap();
+            assert_eq!(&serde_json::from_str::<T>(&json).unwrap(), kind);
+        }
+    }
+
+    /// Generates the tests shared by all error types for `$kinds`, a slice containing one instance
+    /// of every kind of `$error`.
+    ///
+    /// The kinds matching one of the `$chained` patterns are expected to have a source.
+    macro_rules! error_tests {
+        ($error:ident, $kinds:expr $(, $chained:pat)*) => {
+            #[test]
+            fn source() {
+                for e in $kinds {
+                    let chained = [$(matches!(e, $chained)),*].contains(&true);
+                    assert_eq!(core::error::Error::source(e).is_some(), chained, "{:?}", e);
+                }
+            }
+
+            #[test]
+            fn equality() {
+                for (i, a) in $kinds.iter().enumerate() {
+                    for (j, b) in $kinds.iter().enumerate() {
+                        assert_eq!(a == b, i == j);
+                    }
+                }
+            }
+
+            #[test]
+            fn copy() {
+                fn assert_copy<T: Copy>() {}
+
+                assert_copy::<$error>();
+            }
+
+            #[test]
+            fn hash() {
+                $crate::tests::assert_distinct_hashes($kinds);
+            }
+
+            #[cfg(feature = "defmt")]
+            #[test]
+            fn defmt() {
+                $crate::tests::assert_defmt($kinds);
+            }
+
+            #[cfg(feature = "serde")]
+            #[test]
+            fn serde() {
+                $crate::tests::assert_serde_round_trip($kinds);
+                assert!(serde_json::from_str::<$error>("\"Unknown\"").is_err());
+            }
+
+            #[test]
+            fn classification() {
+                for e in $kinds {
+                    assert_eq!(e.severity() == $crate::Severity::Recoverable, e.is_retryable());
+                    assert_eq!(e.retry_after().is_some(), e.is_retryable());
+                    assert!(e.to_errno() < 0);
+                }
+            }
+
+            #[test]
+            fn peripheral_error() {
+                for e in $kinds {
+                    let dyn_e: &dyn $crate::PeripheralError = e;
+                    assert_eq!(dyn_e.severity(), e.severity());
+                    assert_eq!(dyn_e.peripheral(), e.peripheral());
+                    assert_eq!(dyn_e.variant_name(), e.variant_name());
+                }
+            }
+
+            #[test]
+            fn variant_name() {
+                for e in $kinds {
+                    let debug = std::format!("{:?}", e);
+                    let data = debug.strip_prefix(e.variant_name()).unwrap();
+                    assert!(data.is_empty() || data.starts_with('(') || data.starts_with(" {"));
+                }
+            }
+        };
+    }
+
+    pub(crate) use error_tests;
+
+    /// Generates the tests shared by all peripheral error types, additionally checking that their
+    /// `Impl` kind passes through the wrapped [`ImplError`].
+    #[allow(unused_macros)]
+    macro_rules! peripheral_error_tests {
+        ($error:ident, $kinds:expr $(, $chained:pat)*) => {
+            $crate::tests::error_tests!($error, $kinds, $error::Impl(_) $(, $chained)*);
+
+            #[test]
+            fn impl_source() {
+                let e = $error::Impl($crate::ImplError::Internal);
+                let source = core::error::Error::source(&e).unwrap();
+                assert_eq!(
+                    std::format!("{}", source),
+                    std::format!("{}", $crate::ImplError::Internal)
+                );
+            }
+
+            #[test]
+            fn impl_delegation() {
+                for i in $crate::tests::IMPL_KINDS {
+                    let e = $error::Impl(*i);
+                    assert_eq!(std::format!("{}", e), std::format!("{}", i));
+                    assert_eq!(e.is_retryable(), i.is_retryable());
+                    assert_eq!(e.to_code(), i.to_code());
+                    assert_eq!(e.severity(), i.severity());
+                    assert_eq!(e.retry_after(), i.retry_after());
+                    assert_eq!(e.to_errno(), i.to_errno());
+                }
+            }
+
+            #[test]
+            fn impl_error() {
+                for e in $kinds {
+                    match e {
+                        $error::Impl(i) => assert_eq!(e.impl_error(), Some(i)),
+                        _ => assert_eq!(e.impl_error(), None),
+                    }
+                    assert_eq!($crate::PeripheralError::impl_error(e), e.impl_error());
+                }
+            }
+
+            #[test]
+            fn from_impl_error() {
+                fn open() -> Result<(), $crate::ImplError> {
+                    Err($crate::ImplError::CouldNotOpen)
+                }
+
+                fn init() -> Result<(), $error> {
+                    open()?;
+                    Ok(())
+                }
+
+                assert_eq!(init(), Err($error::Impl($crate::ImplError::CouldNotOpen)));
+            }
+
+            #[test]
+            fn code_round_trip() {
+                let base = $kinds[0].to_code() & 0xff00;
+                for e in $kinds {
+                    assert_eq!($error::from_code(e.to_code()), Some(*e));
+                    if !matches!(e, $error::Impl(_)) {
+                        assert_eq!(e.to_code() & 0xff00, base);
+                    }
+                }
+                for i in $crate::tests::IMPL_KINDS {
+                    assert_eq!($error::from_code(i.to_code()), Some($error::Impl(*i)));
+                }
+                assert_eq!($error::from_code(base), None);
+                assert_eq!($error::from_code(base | 0xff), None);
+            }
+
+            #[test]
+            fn try_from() {
+                for e in $kinds {
+                    let expected = match e {
+                        $error::Impl(i) => Ok(i),
+                        _ => Err(()),
+                    };
+                    assert_eq!(<&$crate::ImplError>::try_from(e), expected);
+                }
+            }
+
+            #[test]
+            fn is_impl() {
+                const _: () = assert!($error::Impl($crate::ImplError::Internal).is_impl());
+
+                for e in $kinds {
+                    assert_eq!(e.is_impl(), matches!(e, $error::Impl(_)));
+                }
+            }
+
+            #[test]
+            fn map_impl() {
+                for e in $kinds {
+                    let expected = match e {
+                        $error::Impl(_) => $error::Impl($crate::ImplError::Busy),
+                        _ => *e,
+                    };
+                    assert_eq!(e.map_impl(|_| $crate::ImplError::Busy), expected);
+                }
+            }
+        };
+    }
+
+    #[allow(unused_imports)]
+    pub(crate) use peripheral_error_tests;
+
+    #[test]
+    fn peripheral_error_trait_object() {
+        fn describe(e: &dyn PeripheralError) -> (&'static str, Option<ImplError>) {
+            (e.peripheral(), e.impl_error().copied())
+        }
+
+        assert_eq!(
+            describe(&ImplError::Internal),
+            ("impl", Some(ImplError::Internal))
+        );
+        #[cfg(feature = "i2c")]
+        {
+            assert_eq!(describe(&I2cError::Overrun), ("i2c", None));
+            assert_eq!(
+                describe(&I2cError::Impl(ImplError::Internal)),
+                ("i2c", Some(ImplError::Internal))
+            );
+        }
+        #[cfg(feature = "mci")]
+        assert_eq!(
+            describe(&mci::SetupError::CouldNotSetBusWidth),
+            ("mci", None)
+        );
+    }
+
+    #[test]
+    fn root_re_exports() {
+        assert_eq!(crate::ImplError::Internal, impl_error::ImplError::Internal);
+        #[cfg(feature = "gpio")]
+        assert_eq!(crate::GpioError::PinLocked, gpio::GpioError::PinLocked);
+        #[cfg(feature = "i2c")]
+        assert_eq!(
+            crate::I2cError::NACK(crate::NackSource::Data),
+            i2c::I2cError::NACK(i2c::NackSource::Data)
+        );
+        #[cfg(feature = "serial")]
+        assert_eq!(crate::SerialError::Overrun, serial::SerialError::Overrun);
+        #[cfg(feature = "spi")]
+        assert_eq!(crate::SpiError::Overrun, spi::SpiError::Overrun);
+        #[cfg(feature = "usb")]
+        assert_eq!(crate::UsbError::Stalled, usb::UsbError::Stalled);
+    }
+
+    #[cfg(all(feature = "i2c", feature = "spi", feature = "can"))]
+    #[test]
+    fn any_error_question_mark() {
+        fn read(
+            i2c: Result<u8, I2cError>,
+            spi: Result<u8, SpiError>,
+            can: Result<u8, can::CanError>,
+        ) -> Result<u8, AnyError> {
+            Ok(i2c? + spi? + can?)
+        }
+
+        assert_eq!(read(Ok(1), Ok(2), Ok(3)), Ok(6));
+        assert_eq!(
+            read(Err(I2cError::Overrun), Ok(2), Ok(3)),
+            Err(AnyError::I2c(I2cError::Overrun))
+        );
+        assert_eq!(
+            read(Ok(1), Err(SpiError::ModeFault), Ok(3)),
+            Err(AnyError::Spi(SpiError::ModeFault))
+        );
+        assert_eq!(
+            read(Ok(1), Ok(2), Err(can::CanError::BusOff)),
+            Err(AnyError::Can(can::CanError::BusOff))
+        );
+    }
+
+    #[test]
+    fn any_error_from_impl_error() {
+        fn open() -> Result<(), ImplError> {
+            Err(ImplError::CouldNotOpen)
+        }
+
+        fn init() -> Result<(), AnyError> {
+            open()?;
+            Ok(())
+        }
+
+        assert_eq!(init(), Err(AnyError::Impl(ImplError::CouldNotOpen)));
+        assert_eq!(
+            AnyError::from(ImplError::Busy),
+            AnyError::Impl(ImplError::Busy)
+        );
+    }
+
+    #[test]
+    fn any_error_source() {
+        let e = AnyError::Impl(ImplError::Busy);
+        assert_eq!(e.to_string(), ImplError::Busy.to_string());
+        assert_eq!(e.source().unwrap().to_string(), ImplError::Busy.to_string());
+        #[cfg(feature = "i2c")]
+        {
+            let e = AnyError::I2c(I2cError::Overrun);
+            assert_eq!(e.to_string(), I2cError::Overrun.to_string());
+            assert_eq!(
+                e.source().unwrap().to_string(),
+                I2cError::Overrun.to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn any_error_peripheral_error() {
+        let e = AnyError::Impl(ImplError::Busy);
+        assert_eq!(e.peripheral(), "impl");
+        assert_eq!(e.variant_name(), "Busy");
+        assert_eq!(e.severity(), Severity::Recoverable);
+        assert_eq!(e.impl_error(), Some(&ImplError::Busy));
+        #[cfg(feature = "i2c")]
+        {
+            let nack = I2cError::NACK(NackSource::Data);
+            let e = AnyError::I2c(nack);
+            assert_eq!(e.peripheral(), "i2c");
+            assert_eq!(e.variant_name(), "NACK");
+            assert_eq!(e.severity(), nack.severity());
+            assert_eq!(e.impl_error(), None);
+            let e = AnyError::I2c(I2cError::Impl(ImplError::Busy));
+            assert_eq!(e.impl_error(), Some(&ImplError::Busy));
+        }
+    }
+
+    #[test]
+    fn unwrap_peripheral_ok() {
+        let r: Result<u8, ImplError> = Ok(42);
+        assert_eq!(r.unwrap_peripheral(), 42);
+    }
+
+    #[test]
+    #[should_panic(expected = "impl error: Busy")]
+    fn unwrap_peripheral_err() {
+        let r: Result<u8, ImplError> = Err(ImplError::Busy);
+        r.unwrap_peripheral();
+    }
+
+    #[cfg(feature = "i2c")]
+    #[test]
+    #[should_panic(expected = "i2c error: NACK")]
+    fn unwrap_peripheral_err_peripheral() {
+        let r: Result<u8, I2cError> = Err(I2cError::NACK(NackSource::Address));
+        r.unwrap_peripheral();
+    }
+
+    #[test]
+    fn max_severity_of_batch() {
+        assert!(Severity::Recoverable < Severity::Degraded);
+        assert!(Severity::Degraded < Severity::Fatal);
+        assert_eq!(max_severity(core::iter::empty()), None);
+        let errors: [&dyn PeripheralError; 1] = [&ImplError::Busy];
+        assert_eq!(max_severity(errors), Some(Severity::Recoverable));
+        let errors: [&dyn PeripheralError; 2] = [&ImplError::Busy, &ImplError::Disconnected];
+        assert_eq!(max_severity(errors), Some(Severity::Degraded));
+        let errors: [&dyn PeripheralError; 3] = [
+            &ImplError::Busy,
+            &ImplError::Internal,
+            &ImplError::Disconnected,
+        ];
+        assert_eq!(max_severity(errors), Some(Severity::Fatal));
+        #[cfg(all(feature = "i2c", feature = "spi"))]
+        {
+            let errors: [&dyn PeripheralError; 2] = [
+                &I2cError::NACK(NackSource::Data),
+                &SpiError::TransferSizeMismatch {
+                    expected: 4,
+                    actual: 2,
+                },
+            ];
+            assert_eq!(max_severity(errors), Some(Severity::Fatal));
+        }
+    }
 }
@@ -0,0 +1,320 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// A LIN specific error.
+///
+/// This error type contains errors specific to LIN (Local Interconnect Network) bus peripherals.
+/// Also it has an `Impl` kind to pass through implementation specific errors occurring while trying
+/// to use a LIN peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum LinError {
+    /// The checksum of a received frame does not match the received data
+    ChecksumError,
+    /// The sync field was not received correctly
+    SyncError,
+    /// A stop bit was not received at the expected position
+    FramingError,
+    /// No node responded to a frame header
+    NoResponse,
+    /// The measured baud rate deviates too much from the expected one
+    InconsistentSync,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl LinError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `ChecksumError`, `SyncError`, `FramingError` and `NoResponse` are transient and thus
+    /// retryable. `Impl` errors are retryable if the wrapped [`ImplError`] is. All other kinds are
+    /// hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            LinError::ChecksumError
+            | LinError::SyncError
+            | LinError::FramingError
+            | LinError::NoResponse => true,
+            LinError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`]. `ChecksumError`, `SyncError`,
+    /// `FramingError` and `NoResponse` suggest 1 ms, all other kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            LinError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`LinError::Impl`] and `false` for the LIN specific kinds, e.g.
+    /// [`LinError::ChecksumError`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, LinError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`LinError::Impl`] with `f`.
+    ///
+    /// The LIN specific kinds, e.g. [`LinError::ChecksumError`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            LinError::Impl(e) => LinError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// LIN errors use the high byte `0x19` and the index of the error kind as low byte, e.g.
+    /// `0x1901` for `ChecksumError`. See the [crate level documentation](crate#error-codes) for
+    /// details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            LinError::ChecksumError => 0x1901,
+            LinError::SyncError => 0x1902,
+            LinError::FramingError => 0x1903,
+            LinError::NoResponse => 0x1904,
+            LinError::InconsistentSync => 0x1905,
+            LinError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`LinError::to_code`], e.g. `0x1901` to
+    /// [`LinError::ChecksumError`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the LIN error kinds nor to an
+    /// [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x1901 => Some(LinError::ChecksumError),
+            0x1902 => Some(LinError::SyncError),
+            0x1903 => Some(LinError::FramingError),
+            0x1904 => Some(LinError::NoResponse),
+            0x1905 => Some(LinError::InconsistentSync),
+            0x0000..=0x00ff => ImplError::from_code(code).map(LinError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. All other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            LinError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"lin"`, the stable lowercase name of the LIN peripheral, for all kinds including
+    /// `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "lin"
+    }
+
+    /// Returns the name of the error kind, e.g. `"ChecksumError"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            LinError::ChecksumError => "ChecksumError",
+            LinError::SyncError => "SyncError",
+            LinError::FramingError => "FramingError",
+            LinError::NoResponse => "NoResponse",
+            LinError::InconsistentSync => "InconsistentSync",
+            LinError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `SyncError`, `FramingError` and
+    /// `InconsistentSync` map to `-EPROTO`. `ChecksumError` maps to `-EILSEQ`. `NoResponse` maps to
+    /// `-ETIMEDOUT`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            LinError::Impl(e) => e.to_errno(),
+            LinError::SyncError | LinError::FramingError | LinError::InconsistentSync => {
+                -errno::EPROTO
+            }
+            LinError::ChecksumError => -errno::EILSEQ,
+            LinError::NoResponse => -errno::ETIMEDOUT,
+        }
+    }
+}
+
+impl fmt::Display for LinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LinError::ChecksumError => f.write_str("checksum does not match the received data"),
+            LinError::SyncError => f.write_str("sync field error"),
+            LinError::FramingError => f.write_str("framing error"),
+            LinError::NoResponse => f.write_str("no response"),
+            LinError::InconsistentSync => f.write_str("inconsistent sync field"),
+            LinError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for LinError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            LinError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(LinError, impl_error);
+
+impl PeripheralError for LinError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a LinError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a LinError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[LinError] = &[
+        LinError::ChecksumError,
+        LinError::SyncError,
+        LinError::FramingError,
+        LinError::NoResponse,
+        LinError::InconsistentSync,
+        LinError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(LinError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (
+                LinError::ChecksumError,
+                "checksum does not match the received data",
+            ),
+            (LinError::SyncError, "sync field error"),
+            (LinError::FramingError, "framing error"),
+            (LinError::NoResponse, "no response"),
+            (LinError::InconsistentSync, "inconsistent sync field"),
+            (
+                LinError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(
+            retryable,
+            [
+                &LinError::ChecksumError,
+                &LinError::SyncError,
+                &LinError::FramingError,
+                &LinError::NoResponse
+            ]
+        );
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(codes, [0x1901, 0x1902, 0x1903, 0x1904, 0x1905, 0x0001,]);
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "lin");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(millis, [Some(1), Some(1), Some(1), Some(1), None, None,]);
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::EILSEQ,
+                -errno::EPROTO,
+                -errno::EPROTO,
+                -errno::ETIMEDOUT,
+                -errno::EPROTO,
+                -errno::EIO,
+            ]
+        );
+    }
+}
@@ -0,0 +1,333 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// A LoRa specific error.
+///
+/// This error type contains errors specific to LoRa radio transceiver peripherals. Also it has an
+/// `Impl` kind to pass through implementation specific errors occurring while trying to use a LoRa
+/// peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum LoRaError {
+    /// The payload CRC of a received packet did not match
+    CrcError,
+    /// No packet was received within the reception window
+    RxTimeout,
+    /// The transmission did not complete in time
+    TxTimeout,
+    /// The header of a received packet was invalid
+    HeaderError,
+    /// The transceiver signalled an unexpected or unknown interrupt
+    InvalidIrq,
+    /// The transceiver is busy, e.g. because its BUSY line is asserted
+    Busy,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl LoRaError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `CrcError`, `RxTimeout`, `TxTimeout`, `HeaderError` and `Busy` are transient and thus
+    /// retryable. `Impl` errors are retryable if the wrapped [`ImplError`] is. All other kinds are
+    /// hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            LoRaError::CrcError
+            | LoRaError::RxTimeout
+            | LoRaError::TxTimeout
+            | LoRaError::HeaderError
+            | LoRaError::Busy => true,
+            LoRaError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`]. `CrcError`, `RxTimeout`,
+    /// `TxTimeout`, `HeaderError` and `Busy` suggest 1 ms, all other kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            LoRaError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`LoRaError::Impl`] and `false` for the LoRa specific kinds, e.g.
+    /// [`LoRaError::CrcError`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, LoRaError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`LoRaError::Impl`] with `f`.
+    ///
+    /// The LoRa specific kinds, e.g. [`LoRaError::CrcError`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            LoRaError::Impl(e) => LoRaError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// LoRa errors use the high byte `0x2c` and the index of the error kind as low byte, e.g.
+    /// `0x2c01` for `CrcError`. See the [crate level documentation](crate#error-codes) for details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            LoRaError::CrcError => 0x2c01,
+            LoRaError::RxTimeout => 0x2c02,
+            LoRaError::TxTimeout => 0x2c03,
+            LoRaError::HeaderError => 0x2c04,
+            LoRaError::InvalidIrq => 0x2c05,
+            LoRaError::Busy => 0x2c06,
+            LoRaError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`LoRaError::to_code`], e.g. `0x2c01` to [`LoRaError::CrcError`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the LoRa error kinds nor to an
+    /// [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x2c01 => Some(LoRaError::CrcError),
+            0x2c02 => Some(LoRaError::RxTimeout),
+            0x2c03 => Some(LoRaError::TxTimeout),
+            0x2c04 => Some(LoRaError::HeaderError),
+            0x2c05 => Some(LoRaError::InvalidIrq),
+            0x2c06 => Some(LoRaError::Busy),
+            0x0000..=0x00ff => ImplError::from_code(code).map(LoRaError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. All other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            LoRaError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"lora"`, the stable lowercase name of the LoRa peripheral, for all kinds including
+    /// `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "lora"
+    }
+
+    /// Returns the name of the error kind, e.g. `"CrcError"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            LoRaError::CrcError => "CrcError",
+            LoRaError::RxTimeout => "RxTimeout",
+            LoRaError::TxTimeout => "TxTimeout",
+            LoRaError::HeaderError => "HeaderError",
+            LoRaError::InvalidIrq => "InvalidIrq",
+            LoRaError::Busy => "Busy",
+            LoRaError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `Busy` maps to `-EBUSY`.
+    /// `HeaderError` maps to `-EPROTO`. `CrcError` maps to `-EILSEQ`. `RxTimeout` and `TxTimeout`
+    /// map to `-ETIMEDOUT`. All other kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            LoRaError::Impl(e) => e.to_errno(),
+            LoRaError::Busy => -errno::EBUSY,
+            LoRaError::HeaderError => -errno::EPROTO,
+            LoRaError::CrcError => -errno::EILSEQ,
+            LoRaError::RxTimeout | LoRaError::TxTimeout => -errno::ETIMEDOUT,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for LoRaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoRaError::CrcError => f.write_str("payload CRC mismatch"),
+            LoRaError::RxTimeout => f.write_str("receive timed out"),
+            LoRaError::TxTimeout => f.write_str("transmit timed out"),
+            LoRaError::HeaderError => f.write_str("invalid packet header"),
+            LoRaError::InvalidIrq => f.write_str("unexpected interrupt"),
+            LoRaError::Busy => f.write_str("transceiver busy"),
+            LoRaError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for LoRaError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            LoRaError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(LoRaError, impl_error);
+
+impl PeripheralError for LoRaError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a LoRaError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a LoRaError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[LoRaError] = &[
+        LoRaError::CrcError,
+        LoRaError::RxTimeout,
+        LoRaError::TxTimeout,
+        LoRaError::HeaderError,
+        LoRaError::InvalidIrq,
+        LoRaError::Busy,
+        LoRaError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(LoRaError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (LoRaError::CrcError, "payload CRC mismatch"),
+            (LoRaError::RxTimeout, "receive timed out"),
+            (LoRaError::TxTimeout, "transmit timed out"),
+            (LoRaError::HeaderError, "invalid packet header"),
+            (LoRaError::InvalidIrq, "unexpected interrupt"),
+            (LoRaError::Busy, "transceiver busy"),
+            (
+                LoRaError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(
+            retryable,
+            [
+                &LoRaError::CrcError,
+                &LoRaError::RxTimeout,
+                &LoRaError::TxTimeout,
+                &LoRaError::HeaderError,
+                &LoRaError::Busy
+            ]
+        );
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(
+            codes,
+            [0x2c01, 0x2c02, 0x2c03, 0x2c04, 0x2c05, 0x2c06, 0x0001,]
+        );
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Recoverable,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "lora");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(
+            millis,
+            [Some(1), Some(1), Some(1), Some(1), None, Some(1), None,]
+        );
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::EILSEQ,
+                -errno::ETIMEDOUT,
+                -errno::ETIMEDOUT,
+                -errno::EPROTO,
+                -errno::EIO,
+                -errno::EBUSY,
+                -errno::EIO,
+            ]
+        );
+    }
+}
@@ -0,0 +1,86 @@
+/// Implements the `Impl(ImplError)` passthrough for a custom error type.
+///
+/// Error types of HALs and drivers which follow the convention of this crate and have an
+/// `Impl(ImplError)` variant can use this macro to generate a `From<ImplError>` implementation, so
+/// the `?` operator can be used on functions returning an [`ImplError`](crate::ImplError).
+/// Passing `impl_error` as second argument additionally generates an `impl_error()` accessor
+/// returning the wrapped [`ImplError`](crate::ImplError), if any. The peripheral specific errors of
+/// this crate are implemented the same way.
+///
+/// ```
+/// use embedded_error::{impl_error_passthrough, ImplError};
+///
+/// #[derive(Debug)]
+/// pub enum SensorError {
+///     NotCalibrated,
+///     Impl(ImplError),
+/// }
+///
+/// impl_error_passthrough!(SensorError, impl_error);
+///
+/// fn open() -> Result<(), ImplError> {
+///     Err(ImplError::CouldNotOpen)
+/// }
+///
+/// fn measure() -> Result<u16, SensorError> {
+///     open()?;
+///     Ok(42)
+/// }
+///
+/// assert_eq!(measure().unwrap_err().impl_error(), Some(&ImplError::CouldNotOpen));
+/// ```
+#[macro_export]
+macro_rules! impl_error_passthrough {
+    ($error:ty) => {
+        impl ::core::convert::From<$crate::ImplError> for $error {
+            fn from(e: $crate::ImplError) -> Self {
+                Self::Impl(e)
+            }
+        }
+    };
+    ($error:ty, impl_error) => {
+        $crate::impl_error_passthrough!($error);
+
+        impl $error {
+            /// Returns the wrapped implementation specific error, if any.
+            pub fn impl_error(&self) -> ::core::option::Option<&$crate::ImplError> {
+                match self {
+                    Self::Impl(e) => ::core::option::Option::Some(e),
+                    _ => ::core::option::Option::None,
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ImplError;
+
+    #[derive(Debug, PartialEq)]
+    enum SensorError {
+        NotCalibrated,
+        Impl(ImplError),
+    }
+
+    crate::impl_error_passthrough!(SensorError, impl_error);
+
+    #[test]
+    fn passthrough() {
+        fn open() -> Result<(), ImplError> {
+            Err(ImplError::CouldNotOpen)
+        }
+
+        fn measure() -> Result<u16, SensorError> {
+            open()?;
+            Ok(42)
+        }
+
+        assert_eq!(measure(), Err(SensorError::Impl(ImplError::CouldNotOpen)));
+        assert_eq!(
+            measure().unwrap_err().impl_error(),
+            Some(&ImplError::CouldNotOpen)
+        );
+        assert_eq!(SensorError::NotCalibrated.impl_error(), None);
+    }
+}
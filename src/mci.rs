@@ -1,12 +1,18 @@
-use crate::ImplError;
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
 
 /// An MCI specific error
 ///
 /// This error type contains errors specific to MCI (MultiMedia Card Interface) peripherals. Also it
 /// has an `Impl` kind to pass through implementation specific errors occurring while trying to use
 /// an MCI peripheral.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
+#[must_use]
 pub enum MciError {
     /// Data Error, can be a CRC problem, timeout or end bit problem
     DataError(CommandOrDataError),
@@ -29,41 +35,1126 @@ pub enum MciError {
     /// Card is unusable
     UnusableCard,
     /// Read error
-    ReadError,
+    ReadError {
+        /// Address of the block which could not be read, if known
+        block: Option<u32>,
+    },
     /// Card is write protected
     WriteProtected,
     /// Write error
-    WriteError,
+    WriteError {
+        /// Address of the block which could not be written, if known
+        block: Option<u32>,
+    },
     /// Error reading a pin's value
     PinLevelReadError,
     /// Setup error
     Setup(SetupError),
+    /// Switching the signalling voltage to 1.8V failed, e.g. when entering an UHS-I mode
+    VoltageSwitchError,
+    /// Tuning the sampling point failed, e.g. when entering the SDR104, HS200 or HS400 mode
+    TuningError,
+    /// Erasing blocks failed, e.g. due to an invalid erase sequence or erase range
+    EraseError,
+    /// Card is locked by a password and must be unlocked before data can be accessed
+    CardLocked,
     /// Implementation specific error (shared across all peripheral specific error kinds)
     Impl(ImplError),
 }
 
+impl MciError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `CommandInhibited` and `GroupBusy` are transient and thus retryable. `DataError` and
+    /// `CommandError` errors are retryable if the wrapped [`CommandOrDataError`] is. `Setup` errors
+    /// are retryable if the wrapped [`SetupError`] is. `Impl` errors are retryable if the wrapped
+    /// [`ImplError`] is. All other kinds are hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            MciError::CommandInhibited | MciError::GroupBusy => true,
+            MciError::DataError(e) | MciError::CommandError(e) => e.is_retryable(),
+            MciError::Setup(e) => e.is_retryable(),
+            MciError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors and the nested `DataError`, `CommandError` and `Setup` kinds return the hint
+    /// of the wrapped error. `CommandInhibited` and `GroupBusy` suggest 1 ms, all other kinds
+    /// return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            MciError::DataError(e) | MciError::CommandError(e) => e.retry_after(),
+            MciError::Setup(e) => e.retry_after(),
+            MciError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`MciError::Impl`] and `false` for the MCI specific kinds, e.g.
+    /// [`MciError::CommandInhibited`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, MciError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`MciError::Impl`] with `f`.
+    ///
+    /// The MCI specific kinds, e.g. [`MciError::CommandInhibited`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            MciError::Impl(e) => MciError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Wraps a [`CommandOrDataError`] which occurred while sending a command.
+    ///
+    /// There is no `From<CommandOrDataError>` implementation since the same error kinds are used
+    /// for both the command and the data phase, so the conversion would be ambiguous.
+    pub fn from_command_error(e: CommandOrDataError) -> Self {
+        MciError::CommandError(e)
+    }
+
+    /// Wraps a [`CommandOrDataError`] which occurred while transferring data.
+    ///
+    /// See [`MciError::from_command_error`] for why there is no `From` implementation.
+    pub fn from_data_error(e: CommandOrDataError) -> Self {
+        MciError::DataError(e)
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// MCI errors use the high byte `0x06` and the index of the error kind as low byte, e.g.
+    /// `0x0602` for `CommandInhibited`. See the [crate level documentation](crate#error-codes) for
+    /// details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            MciError::DataError(CommandOrDataError::Timeout) => 0x0601,
+            MciError::CommandInhibited => 0x0602,
+            MciError::CommandError(CommandOrDataError::Timeout) => 0x0603,
+            MciError::Adma => 0x0604,
+            MciError::GroupBusy => 0x0605,
+            MciError::CiaCouldNotFindTuple => 0x0606,
+            MciError::IncorrectDataSize => 0x0607,
+            MciError::CouldNotSelectDevice => 0x0608,
+            MciError::NoCard => 0x0609,
+            MciError::UnusableCard => 0x060a,
+            MciError::ReadError { .. } => 0x060b,
+            MciError::WriteProtected => 0x060c,
+            MciError::WriteError { .. } => 0x060d,
+            MciError::PinLevelReadError => 0x060e,
+            MciError::Setup(SetupError::CouldNotSetBusWidth) => 0x060f,
+            MciError::VoltageSwitchError => 0x0610,
+            MciError::TuningError => 0x0611,
+            MciError::EraseError => 0x0612,
+            MciError::CardLocked => 0x0613,
+            MciError::DataError(CommandOrDataError::Crc) => 0x0614,
+            MciError::DataError(CommandOrDataError::EndBit) => 0x0615,
+            MciError::DataError(CommandOrDataError::Index) => 0x0616,
+            MciError::CommandError(CommandOrDataError::Crc) => 0x0617,
+            MciError::CommandError(CommandOrDataError::EndBit) => 0x0618,
+            MciError::CommandError(CommandOrDataError::Index) => 0x0619,
+            MciError::Setup(SetupError::CouldNotSetToHighSpeed) => 0x061a,
+            MciError::Setup(SetupError::CouldNotCheckIfIsHighSpeed) => 0x061b,
+            MciError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`MciError::to_code`], e.g. `0x0602` to
+    /// [`MciError::CommandInhibited`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the MCI error kinds nor to an
+    /// [`ImplError`]. Optional block addresses are not encoded in the code and thus restored as
+    /// `None`.
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x0601 => Some(MciError::DataError(CommandOrDataError::Timeout)),
+            0x0602 => Some(MciError::CommandInhibited),
+            0x0603 => Some(MciError::CommandError(CommandOrDataError::Timeout)),
+            0x0604 => Some(MciError::Adma),
+            0x0605 => Some(MciError::GroupBusy),
+            0x0606 => Some(MciError::CiaCouldNotFindTuple),
+            0x0607 => Some(MciError::IncorrectDataSize),
+            0x0608 => Some(MciError::CouldNotSelectDevice),
+            0x0609 => Some(MciError::NoCard),
+            0x060a => Some(MciError::UnusableCard),
+            0x060b => Some(MciError::ReadError { block: None }),
+            0x060c => Some(MciError::WriteProtected),
+            0x060d => Some(MciError::WriteError { block: None }),
+            0x060e => Some(MciError::PinLevelReadError),
+            0x060f => Some(MciError::Setup(SetupError::CouldNotSetBusWidth)),
+            0x0610 => Some(MciError::VoltageSwitchError),
+            0x0611 => Some(MciError::TuningError),
+            0x0612 => Some(MciError::EraseError),
+            0x0613 => Some(MciError::CardLocked),
+            0x0614 => Some(MciError::DataError(CommandOrDataError::Crc)),
+            0x0615 => Some(MciError::DataError(CommandOrDataError::EndBit)),
+            0x0616 => Some(MciError::DataError(CommandOrDataError::Index)),
+            0x0617 => Some(MciError::CommandError(CommandOrDataError::Crc)),
+            0x0618 => Some(MciError::CommandError(CommandOrDataError::EndBit)),
+            0x0619 => Some(MciError::CommandError(CommandOrDataError::Index)),
+            0x061a => Some(MciError::Setup(SetupError::CouldNotSetToHighSpeed)),
+            0x061b => Some(MciError::Setup(SetupError::CouldNotCheckIfIsHighSpeed)),
+            0x0000..=0x00ff => ImplError::from_code(code).map(MciError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `DataError` and `CommandError` errors are classified by the wrapped [`CommandOrDataError`].
+    /// `Setup` errors are classified by the wrapped [`SetupError`]. `Impl` errors are classified by
+    /// the wrapped [`ImplError`]. [Retryable](Self::is_retryable) kinds are
+    /// [`Severity::Recoverable`]. `IncorrectDataSize` and `UnusableCard` are [`Severity::Fatal`].
+    /// All other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            MciError::DataError(e) | MciError::CommandError(e) => e.severity(),
+            MciError::Setup(e) => e.severity(),
+            MciError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            MciError::IncorrectDataSize | MciError::UnusableCard => Severity::Fatal,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"mci"`, the stable lowercase name of the MCI peripheral, for all kinds including
+    /// `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "mci"
+    }
+
+    /// Returns the name of the error kind, e.g. `"DataError"`.
+    ///
+    /// The data carried by `DataError`, `CommandError`, `ReadError`, `WriteError` and `Setup` is
+    /// not included. `Impl` errors return `"Impl"` instead of the name of the wrapped
+    /// [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            MciError::DataError(_) => "DataError",
+            MciError::CommandInhibited => "CommandInhibited",
+            MciError::CommandError(_) => "CommandError",
+            MciError::Adma => "Adma",
+            MciError::GroupBusy => "GroupBusy",
+            MciError::CiaCouldNotFindTuple => "CiaCouldNotFindTuple",
+            MciError::IncorrectDataSize => "IncorrectDataSize",
+            MciError::CouldNotSelectDevice => "CouldNotSelectDevice",
+            MciError::NoCard => "NoCard",
+            MciError::UnusableCard => "UnusableCard",
+            MciError::ReadError { .. } => "ReadError",
+            MciError::WriteProtected => "WriteProtected",
+            MciError::WriteError { .. } => "WriteError",
+            MciError::PinLevelReadError => "PinLevelReadError",
+            MciError::Setup(_) => "Setup",
+            MciError::VoltageSwitchError => "VoltageSwitchError",
+            MciError::TuningError => "TuningError",
+            MciError::EraseError => "EraseError",
+            MciError::CardLocked => "CardLocked",
+            MciError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors and the nested `DataError`, `CommandError` and `Setup` kinds return the value
+    /// of the wrapped error. `CouldNotSelectDevice` maps to `-ENXIO`. `WriteProtected` and
+    /// `CardLocked` map to `-EACCES`. `CommandInhibited` and `GroupBusy` map to `-EBUSY`.
+    /// `UnusableCard` maps to `-ENODEV`. `IncorrectDataSize` maps to `-EINVAL`. `NoCard` maps to
+    /// `-ENOMEDIUM`. All other kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            MciError::DataError(e) => e.to_errno(),
+            MciError::CommandError(e) => e.to_errno(),
+            MciError::Setup(e) => e.to_errno(),
+            MciError::Impl(e) => e.to_errno(),
+            MciError::CouldNotSelectDevice => -errno::ENXIO,
+            MciError::WriteProtected | MciError::CardLocked => -errno::EACCES,
+            MciError::CommandInhibited | MciError::GroupBusy => -errno::EBUSY,
+            MciError::UnusableCard => -errno::ENODEV,
+            MciError::IncorrectDataSize => -errno::EINVAL,
+            MciError::NoCard => -errno::ENOMEDIUM,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+/// Nested `DataError`, `CommandError` and `Setup` kinds are flattened into a single message
+/// prefixed with the failed stage, i.e. `MCI data transfer failed: <error>`,
+/// `MCI command failed: <error>` and `MCI setup failed: <error>`, e.g.
+/// `MCI setup failed: could not set bus width`. The nested error is still returned by
+/// [`source`](core::error::Error::source).
+impl fmt::Display for MciError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MciError::DataError(e) => write!(f, "MCI data transfer failed: {}", e),
+            MciError::CommandInhibited => f.write_str("commands are inhibited"),
+            MciError::CommandError(e) => write!(f, "MCI command failed: {}", e),
+            MciError::Adma => f.write_str("ADMA error"),
+            MciError::GroupBusy => f.write_str("function group is busy"),
+            MciError::CiaCouldNotFindTuple => f.write_str("could not find tuple in CIA register"),
+            MciError::IncorrectDataSize => f.write_str("incorrect data size"),
+            MciError::CouldNotSelectDevice => f.write_str("could not select device"),
+            MciError::NoCard => f.write_str("no card inserted"),
+            MciError::UnusableCard => f.write_str("card is unusable"),
+            MciError::ReadError { block: None } => f.write_str("read error"),
+            MciError::ReadError { block: Some(block) } => {
+                write!(f, "read error at block {}", block)
+            }
+            MciError::WriteProtected => f.write_str("card is write protected"),
+            MciError::WriteError { block: None } => f.write_str("write error"),
+            MciError::WriteError { block: Some(block) } => {
+                write!(f, "write error at block {}", block)
+            }
+            MciError::PinLevelReadError => f.write_str("could not read pin level"),
+            MciError::Setup(e) => write!(f, "MCI setup failed: {}", e),
+            MciError::VoltageSwitchError => f.write_str("signal voltage switch failed"),
+            MciError::TuningError => f.write_str("tuning failed"),
+            MciError::EraseError => f.write_str("erase error"),
+            MciError::CardLocked => f.write_str("card is locked"),
+            MciError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for MciError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            MciError::DataError(e) | MciError::CommandError(e) => Some(e),
+            MciError::Setup(e) => Some(e),
+            MciError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(MciError, impl_error);
+
+impl From<SetupError> for MciError {
+    fn from(e: SetupError) -> Self {
+        MciError::Setup(e)
+    }
+}
+
+impl PeripheralError for MciError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a MciError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a MciError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
 /// Enumeration used when setting up the device especially when installing MMC
-#[derive(Debug, Clone)]
+///
+/// The enum has a fixed `u8` representation with explicit discriminants starting at 1 which are
+/// guaranteed to stay stable, e.g. for passing them across an FFI boundary via an `as u8` cast.
+/// New kinds are appended with new discriminants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
+#[must_use]
+#[repr(u8)]
 pub enum SetupError {
     /// Could not set bus width
-    CouldNotSetBusWidth,
+    CouldNotSetBusWidth = 1,
     /// Could not set to high speed
-    CouldNotSetToHighSpeed,
+    CouldNotSetToHighSpeed = 2,
     /// Could not check if it is a high speed device
-    CouldNotCheckIfIsHighSpeed,
+    CouldNotCheckIfIsHighSpeed = 3,
+}
+
+impl SetupError {
+    /// All error kinds, e.g. for checking that formatting or a classification is total.
+    ///
+    /// The peripheral specific errors do not provide this list since their `Impl` kind can wrap any
+    /// [`ImplError`], which provides its own [`ImplError::ALL`].
+    pub const ALL: &'static [Self] = &[
+        SetupError::CouldNotSetBusWidth,
+        SetupError::CouldNotSetToHighSpeed,
+        SetupError::CouldNotCheckIfIsHighSpeed,
+    ];
+
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// None of the kinds are transient, so this always returns `false`.
+    pub fn is_retryable(&self) -> bool {
+        false
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// The [`SetupError`] kinds are not retryable and return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        if self.is_retryable() {
+            Some(Duration::from_millis(1))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// [Retryable](Self::is_retryable) kinds are [`Severity::Recoverable`]. All other kinds are
+    /// [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        if self.is_retryable() {
+            Severity::Recoverable
+        } else {
+            Severity::Degraded
+        }
+    }
+
+    /// Returns `"mci"`, the stable lowercase name of the MCI peripheral, like the wrapping
+    /// [`MciError`].
+    pub fn peripheral(&self) -> &'static str {
+        "mci"
+    }
+
+    /// Returns the name of the error kind, e.g. `"CouldNotSetBusWidth"`.
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            SetupError::CouldNotSetBusWidth => "CouldNotSetBusWidth",
+            SetupError::CouldNotSetToHighSpeed => "CouldNotSetToHighSpeed",
+            SetupError::CouldNotCheckIfIsHighSpeed => "CouldNotCheckIfIsHighSpeed",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// All kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        -errno::EIO
+    }
+}
+
+impl fmt::Display for SetupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SetupError::CouldNotSetBusWidth => f.write_str("could not set bus width"),
+            SetupError::CouldNotSetToHighSpeed => f.write_str("could not set to high speed"),
+            SetupError::CouldNotCheckIfIsHighSpeed => {
+                f.write_str("could not check for high speed support")
+            }
+        }
+    }
+}
+
+impl core::error::Error for SetupError {}
+
+impl PeripheralError for SetupError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        None
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
 }
 
 /// When sending a command (or receiving its response) something can go wrong
-#[derive(Debug, Clone)]
+///
+/// The enum has a fixed `u8` representation with explicit discriminants starting at 1 which are
+/// guaranteed to stay stable, e.g. for passing them across an FFI boundary via an `as u8` cast.
+/// New kinds are appended with new discriminants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
+#[must_use]
+#[repr(u8)]
 pub enum CommandOrDataError {
     /// Timeout occurred
-    Timeout,
+    Timeout = 1,
     /// CRC check failed
-    Crc,
+    Crc = 2,
     /// End bit error
-    EndBit,
+    EndBit = 3,
     /// Command index fault
-    Index,
+    Index = 4,
+}
+
+impl CommandOrDataError {
+    /// All error kinds, e.g. for checking that formatting or a classification is total.
+    ///
+    /// The peripheral specific errors do not provide this list since their `Impl` kind can wrap any
+    /// [`ImplError`], which provides its own [`ImplError::ALL`].
+    pub const ALL: &'static [Self] = &[
+        CommandOrDataError::Timeout,
+        CommandOrDataError::Crc,
+        CommandOrDataError::EndBit,
+        CommandOrDataError::Index,
+    ];
+
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `Timeout`, `Crc` and `EndBit` are transient and thus retryable. All other kinds are hard
+    /// faults.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            CommandOrDataError::Timeout | CommandOrDataError::Crc | CommandOrDataError::EndBit
+        )
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Timeout`, `Crc` and `EndBit` suggest 1 ms, all other kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        if self.is_retryable() {
+            Some(Duration::from_millis(1))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// [Retryable](Self::is_retryable) kinds are [`Severity::Recoverable`]. All other kinds are
+    /// [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        if self.is_retryable() {
+            Severity::Recoverable
+        } else {
+            Severity::Degraded
+        }
+    }
+
+    /// Returns `"mci"`, the stable lowercase name of the MCI peripheral, like the wrapping
+    /// [`MciError`].
+    pub fn peripheral(&self) -> &'static str {
+        "mci"
+    }
+
+    /// Returns the name of the error kind, e.g. `"Timeout"`.
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            CommandOrDataError::Timeout => "Timeout",
+            CommandOrDataError::Crc => "Crc",
+            CommandOrDataError::EndBit => "EndBit",
+            CommandOrDataError::Index => "Index",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `EndBit` and `Index` map to `-EPROTO`. `Crc` maps to `-EILSEQ`. `Timeout` maps to
+    /// `-ETIMEDOUT`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            CommandOrDataError::EndBit | CommandOrDataError::Index => -errno::EPROTO,
+            CommandOrDataError::Crc => -errno::EILSEQ,
+            CommandOrDataError::Timeout => -errno::ETIMEDOUT,
+        }
+    }
+}
+
+impl fmt::Display for CommandOrDataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommandOrDataError::Timeout => f.write_str("timeout"),
+            CommandOrDataError::Crc => f.write_str("CRC check failed"),
+            CommandOrDataError::EndBit => f.write_str("end bit error"),
+            CommandOrDataError::Index => f.write_str("command index fault"),
+        }
+    }
+}
+
+impl core::error::Error for CommandOrDataError {}
+
+impl PeripheralError for CommandOrDataError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        None
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::error::Error;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[MciError] = &[
+        MciError::DataError(CommandOrDataError::Timeout),
+        MciError::DataError(CommandOrDataError::Crc),
+        MciError::DataError(CommandOrDataError::EndBit),
+        MciError::DataError(CommandOrDataError::Index),
+        MciError::CommandInhibited,
+        MciError::CommandError(CommandOrDataError::Timeout),
+        MciError::CommandError(CommandOrDataError::Crc),
+        MciError::CommandError(CommandOrDataError::EndBit),
+        MciError::CommandError(CommandOrDataError::Index),
+        MciError::Adma,
+        MciError::GroupBusy,
+        MciError::CiaCouldNotFindTuple,
+        MciError::IncorrectDataSize,
+        MciError::CouldNotSelectDevice,
+        MciError::NoCard,
+        MciError::UnusableCard,
+        MciError::ReadError { block: None },
+        MciError::WriteProtected,
+        MciError::WriteError { block: None },
+        MciError::PinLevelReadError,
+        MciError::Setup(SetupError::CouldNotSetBusWidth),
+        MciError::Setup(SetupError::CouldNotSetToHighSpeed),
+        MciError::Setup(SetupError::CouldNotCheckIfIsHighSpeed),
+        MciError::VoltageSwitchError,
+        MciError::TuningError,
+        MciError::EraseError,
+        MciError::CardLocked,
+        MciError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(
+        MciError,
+        KINDS,
+        MciError::DataError(_),
+        MciError::CommandError(_),
+        MciError::Setup(_)
+    );
+
+    #[test]
+    fn display() {
+        let cases = [
+            (
+                MciError::DataError(CommandOrDataError::Timeout),
+                "MCI data transfer failed: timeout",
+            ),
+            (
+                MciError::DataError(CommandOrDataError::Crc),
+                "MCI data transfer failed: CRC check failed",
+            ),
+            (
+                MciError::DataError(CommandOrDataError::EndBit),
+                "MCI data transfer failed: end bit error",
+            ),
+            (
+                MciError::DataError(CommandOrDataError::Index),
+                "MCI data transfer failed: command index fault",
+            ),
+            (MciError::CommandInhibited, "commands are inhibited"),
+            (
+                MciError::CommandError(CommandOrDataError::Timeout),
+                "MCI command failed: timeout",
+            ),
+            (
+                MciError::CommandError(CommandOrDataError::Crc),
+                "MCI command failed: CRC check failed",
+            ),
+            (
+                MciError::CommandError(CommandOrDataError::EndBit),
+                "MCI command failed: end bit error",
+            ),
+            (
+                MciError::CommandError(CommandOrDataError::Index),
+                "MCI command failed: command index fault",
+            ),
+            (MciError::Adma, "ADMA error"),
+            (MciError::GroupBusy, "function group is busy"),
+            (
+                MciError::CiaCouldNotFindTuple,
+                "could not find tuple in CIA register",
+            ),
+            (MciError::IncorrectDataSize, "incorrect data size"),
+            (MciError::CouldNotSelectDevice, "could not select device"),
+            (MciError::NoCard, "no card inserted"),
+            (MciError::UnusableCard, "card is unusable"),
+            (
+                MciError::ReadError { block: Some(7) },
+                "read error at block 7",
+            ),
+            (MciError::ReadError { block: None }, "read error"),
+            (MciError::WriteProtected, "card is write protected"),
+            (
+                MciError::WriteError { block: Some(7) },
+                "write error at block 7",
+            ),
+            (MciError::WriteError { block: None }, "write error"),
+            (MciError::PinLevelReadError, "could not read pin level"),
+            (
+                MciError::Setup(SetupError::CouldNotSetBusWidth),
+                "MCI setup failed: could not set bus width",
+            ),
+            (
+                MciError::Setup(SetupError::CouldNotSetToHighSpeed),
+                "MCI setup failed: could not set to high speed",
+            ),
+            (
+                MciError::Setup(SetupError::CouldNotCheckIfIsHighSpeed),
+                "MCI setup failed: could not check for high speed support",
+            ),
+            (MciError::VoltageSwitchError, "signal voltage switch failed"),
+            (MciError::TuningError, "tuning failed"),
+            (MciError::EraseError, "erase error"),
+            (MciError::CardLocked, "card is locked"),
+            (
+                MciError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(
+            retryable,
+            [
+                &MciError::DataError(CommandOrDataError::Timeout),
+                &MciError::DataError(CommandOrDataError::Crc),
+                &MciError::DataError(CommandOrDataError::EndBit),
+                &MciError::CommandInhibited,
+                &MciError::CommandError(CommandOrDataError::Timeout),
+                &MciError::CommandError(CommandOrDataError::Crc),
+                &MciError::CommandError(CommandOrDataError::EndBit),
+                &MciError::GroupBusy
+            ]
+        );
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(
+            codes,
+            [
+                0x0601, 0x0614, 0x0615, 0x0616, 0x0602, 0x0603, 0x0617, 0x0618, 0x0619, 0x0604,
+                0x0605, 0x0606, 0x0607, 0x0608, 0x0609, 0x060a, 0x060b, 0x060c, 0x060d, 0x060e,
+                0x060f, 0x061a, 0x061b, 0x0610, 0x0611, 0x0612, 0x0613, 0x0001,
+            ]
+        );
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Fatal,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Fatal,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "mci");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(
+            millis,
+            [
+                Some(1),
+                Some(1),
+                Some(1),
+                None,
+                Some(1),
+                Some(1),
+                Some(1),
+                Some(1),
+                None,
+                None,
+                Some(1),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::ETIMEDOUT,
+                -errno::EILSEQ,
+                -errno::EPROTO,
+                -errno::EPROTO,
+                -errno::EBUSY,
+                -errno::ETIMEDOUT,
+                -errno::EILSEQ,
+                -errno::EPROTO,
+                -errno::EPROTO,
+                -errno::EIO,
+                -errno::EBUSY,
+                -errno::EIO,
+                -errno::EINVAL,
+                -errno::ENXIO,
+                -errno::ENOMEDIUM,
+                -errno::ENODEV,
+                -errno::EIO,
+                -errno::EACCES,
+                -errno::EIO,
+                -errno::EIO,
+                -errno::EIO,
+                -errno::EIO,
+                -errno::EIO,
+                -errno::EIO,
+                -errno::EIO,
+                -errno::EIO,
+                -errno::EACCES,
+                -errno::EIO,
+            ]
+        );
+    }
+
+    #[test]
+    fn source_chain() {
+        let cases = [
+            (
+                MciError::DataError(CommandOrDataError::Timeout),
+                CommandOrDataError::Timeout.to_string(),
+            ),
+            (
+                MciError::DataError(CommandOrDataError::Crc),
+                CommandOrDataError::Crc.to_string(),
+            ),
+            (
+                MciError::DataError(CommandOrDataError::EndBit),
+                CommandOrDataError::EndBit.to_string(),
+            ),
+            (
+                MciError::DataError(CommandOrDataError::Index),
+                CommandOrDataError::Index.to_string(),
+            ),
+            (
+                MciError::CommandError(CommandOrDataError::Timeout),
+                CommandOrDataError::Timeout.to_string(),
+            ),
+            (
+                MciError::CommandError(CommandOrDataError::Crc),
+                CommandOrDataError::Crc.to_string(),
+            ),
+            (
+                MciError::CommandError(CommandOrDataError::EndBit),
+                CommandOrDataError::EndBit.to_string(),
+            ),
+            (
+                MciError::CommandError(CommandOrDataError::Index),
+                CommandOrDataError::Index.to_string(),
+            ),
+            (
+                MciError::Setup(SetupError::CouldNotSetBusWidth),
+                SetupError::CouldNotSetBusWidth.to_string(),
+            ),
+            (
+                MciError::Setup(SetupError::CouldNotSetToHighSpeed),
+                SetupError::CouldNotSetToHighSpeed.to_string(),
+            ),
+            (
+                MciError::Setup(SetupError::CouldNotCheckIfIsHighSpeed),
+                SetupError::CouldNotCheckIfIsHighSpeed.to_string(),
+            ),
+        ];
+        for (e, source) in cases.iter() {
+            assert_eq!(e.source().unwrap().to_string(), *source);
+        }
+    }
+
+    #[test]
+    #[allow(clippy::clone_on_copy)]
+    fn clone() {
+        let e = MciError::DataError(CommandOrDataError::Crc);
+        assert_eq!(e.clone(), e);
+    }
+
+    #[test]
+    fn conversions() {
+        fn set_bus_width() -> Result<(), SetupError> {
+            Err(SetupError::CouldNotSetBusWidth)
+        }
+
+        fn init() -> Result<(), MciError> {
+            set_bus_width()?;
+            Ok(())
+        }
+
+        assert_eq!(
+            init(),
+            Err(MciError::Setup(SetupError::CouldNotSetBusWidth))
+        );
+        assert_eq!(
+            MciError::from_command_error(CommandOrDataError::Crc),
+            MciError::CommandError(CommandOrDataError::Crc)
+        );
+        assert_eq!(
+            MciError::from_data_error(CommandOrDataError::Crc),
+            MciError::DataError(CommandOrDataError::Crc)
+        );
+    }
+
+    mod setup_error {
+        use super::*;
+
+        const KINDS: &[SetupError] = &[
+            SetupError::CouldNotSetBusWidth,
+            SetupError::CouldNotSetToHighSpeed,
+            SetupError::CouldNotCheckIfIsHighSpeed,
+        ];
+
+        crate::tests::error_tests!(SetupError, KINDS);
+
+        #[test]
+        fn display() {
+            let cases = [
+                (SetupError::CouldNotSetBusWidth, "could not set bus width"),
+                (
+                    SetupError::CouldNotSetToHighSpeed,
+                    "could not set to high speed",
+                ),
+                (
+                    SetupError::CouldNotCheckIfIsHighSpeed,
+                    "could not check for high speed support",
+                ),
+            ];
+            for (e, display) in cases.iter() {
+                assert_eq!(e.to_string(), *display);
+            }
+        }
+
+        #[test]
+        fn is_retryable() {
+            assert!(!KINDS.iter().any(|e| e.is_retryable()));
+        }
+
+        #[test]
+        fn severity() {
+            let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+            assert_eq!(
+                severities,
+                [Severity::Degraded, Severity::Degraded, Severity::Degraded,]
+            );
+        }
+
+        #[test]
+        fn peripheral() {
+            for e in KINDS {
+                assert_eq!(e.peripheral(), "mci");
+            }
+        }
+
+        #[test]
+        fn retry_after() {
+            let millis: Vec<_> = KINDS
+                .iter()
+                .map(|e| e.retry_after().map(|d| d.as_millis()))
+                .collect();
+            assert_eq!(millis, [None, None, None,]);
+        }
+
+        #[test]
+        fn to_errno() {
+            let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+            assert_eq!(errnos, [-errno::EIO, -errno::EIO, -errno::EIO,]);
+        }
+
+        #[test]
+        fn all() {
+            assert_eq!(SetupError::ALL, KINDS);
+        }
+
+        #[test]
+        fn discriminants() {
+            let discriminants: Vec<_> = KINDS.iter().map(|e| *e as u8).collect();
+            assert_eq!(discriminants, [1, 2, 3]);
+        }
+    }
+
+    mod command_or_data_error {
+        use super::*;
+
+        const KINDS: &[CommandOrDataError] = &[
+            CommandOrDataError::Timeout,
+            CommandOrDataError::Crc,
+            CommandOrDataError::EndBit,
+            CommandOrDataError::Index,
+        ];
+
+        crate::tests::error_tests!(CommandOrDataError, KINDS);
+
+        #[test]
+        fn display() {
+            let cases = [
+                (CommandOrDataError::Timeout, "timeout"),
+                (CommandOrDataError::Crc, "CRC check failed"),
+                (CommandOrDataError::EndBit, "end bit error"),
+                (CommandOrDataError::Index, "command index fault"),
+            ];
+            for (e, display) in cases.iter() {
+                assert_eq!(e.to_string(), *display);
+            }
+        }
+
+        #[test]
+        fn is_retryable() {
+            let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+            assert_eq!(
+                retryable,
+                [
+                    &CommandOrDataError::Timeout,
+                    &CommandOrDataError::Crc,
+                    &CommandOrDataError::EndBit
+                ]
+            );
+        }
+
+        #[test]
+        fn severity() {
+            let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+            assert_eq!(
+                severities,
+                [
+                    Severity::Recoverable,
+                    Severity::Recoverable,
+                    Severity::Recoverable,
+                    Severity::Degraded,
+                ]
+            );
+        }
+
+        #[test]
+        fn peripheral() {
+            for e in KINDS {
+                assert_eq!(e.peripheral(), "mci");
+            }
+        }
+
+        #[test]
+        fn retry_after() {
+            let millis: Vec<_> = KINDS
+                .iter()
+                .map(|e| e.retry_after().map(|d| d.as_millis()))
+                .collect();
+            assert_eq!(millis, [Some(1), Some(1), Some(1), None,]);
+        }
+
+        #[test]
+        fn to_errno() {
+            let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+            assert_eq!(
+                errnos,
+                [
+                    -errno::ETIMEDOUT,
+                    -errno::EILSEQ,
+                    -errno::EPROTO,
+                    -errno::EPROTO,
+                ]
+            );
+        }
+
+        #[test]
+        fn all() {
+            assert_eq!(CommandOrDataError::ALL, KINDS);
+        }
+
+        #[test]
+        fn discriminants() {
+            let discriminants: Vec<_> = KINDS.iter().map(|e| *e as u8).collect();
+            assert_eq!(discriminants, [1, 2, 3, 4]);
+        }
+    }
 }
@@ -0,0 +1,326 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// A modem specific error.
+///
+/// This error type contains errors specific to AT command driven modem peripherals. Also it has an
+/// `Impl` kind to pass through implementation specific errors occurring while trying to use a modem
+/// peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum ModemError {
+    /// The modem sent a response which does not match the issued command
+    UnexpectedResponse,
+    /// The modem rejected the command with an error response, e.g. `ERROR` or `+CME ERROR`
+    ErrorResponse,
+    /// The modem did not respond to the command in time
+    Timeout,
+    /// The connection could not be established or was lost (`NO CARRIER`)
+    NoCarrier,
+    /// The response did not fit into the provided buffer
+    BufferOverflow,
+    /// The modem or the remote end is busy (`BUSY`)
+    Busy,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl ModemError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `Timeout`, `NoCarrier` and `Busy` are transient and thus retryable. `Impl` errors are
+    /// retryable if the wrapped [`ImplError`] is. All other kinds are hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ModemError::Timeout | ModemError::NoCarrier | ModemError::Busy => true,
+            ModemError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`]. `Timeout`, `NoCarrier` and
+    /// `Busy` suggest 1 ms, all other kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            ModemError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`ModemError::Impl`] and `false` for the modem specific kinds, e.g.
+    /// [`ModemError::UnexpectedResponse`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, ModemError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`ModemError::Impl`] with `f`.
+    ///
+    /// The modem specific kinds, e.g. [`ModemError::UnexpectedResponse`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            ModemError::Impl(e) => ModemError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// Modem errors use the high byte `0x24` and the index of the error kind as low byte, e.g.
+    /// `0x2401` for `UnexpectedResponse`. See the [crate level documentation](crate#error-codes)
+    /// for details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            ModemError::UnexpectedResponse => 0x2401,
+            ModemError::ErrorResponse => 0x2402,
+            ModemError::Timeout => 0x2403,
+            ModemError::NoCarrier => 0x2404,
+            ModemError::BufferOverflow => 0x2405,
+            ModemError::Busy => 0x2406,
+            ModemError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`ModemError::to_code`], e.g. `0x2401` to
+    /// [`ModemError::UnexpectedResponse`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the modem error kinds nor to
+    /// an [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x2401 => Some(ModemError::UnexpectedResponse),
+            0x2402 => Some(ModemError::ErrorResponse),
+            0x2403 => Some(ModemError::Timeout),
+            0x2404 => Some(ModemError::NoCarrier),
+            0x2405 => Some(ModemError::BufferOverflow),
+            0x2406 => Some(ModemError::Busy),
+            0x0000..=0x00ff => ImplError::from_code(code).map(ModemError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. All other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            ModemError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"modem"`, the stable lowercase name of the modem peripheral, for all kinds
+    /// including `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "modem"
+    }
+
+    /// Returns the name of the error kind, e.g. `"UnexpectedResponse"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            ModemError::UnexpectedResponse => "UnexpectedResponse",
+            ModemError::ErrorResponse => "ErrorResponse",
+            ModemError::Timeout => "Timeout",
+            ModemError::NoCarrier => "NoCarrier",
+            ModemError::BufferOverflow => "BufferOverflow",
+            ModemError::Busy => "Busy",
+            ModemError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `Busy` maps to `-EBUSY`.
+    /// `UnexpectedResponse` maps to `-EPROTO`. `BufferOverflow` maps to `-ENOBUFS`. `NoCarrier`
+    /// maps to `-ENOTCONN`. `Timeout` maps to `-ETIMEDOUT`. All other kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            ModemError::Impl(e) => e.to_errno(),
+            ModemError::Busy => -errno::EBUSY,
+            ModemError::UnexpectedResponse => -errno::EPROTO,
+            ModemError::BufferOverflow => -errno::ENOBUFS,
+            ModemError::NoCarrier => -errno::ENOTCONN,
+            ModemError::Timeout => -errno::ETIMEDOUT,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for ModemError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModemError::UnexpectedResponse => f.write_str("unexpected response"),
+            ModemError::ErrorResponse => f.write_str("error response"),
+            ModemError::Timeout => f.write_str("modem timed out"),
+            ModemError::NoCarrier => f.write_str("no carrier"),
+            ModemError::BufferOverflow => f.write_str("response buffer overflow"),
+            ModemError::Busy => f.write_str("modem busy"),
+            ModemError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for ModemError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            ModemError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(ModemError, impl_error);
+
+impl PeripheralError for ModemError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a ModemError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a ModemError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[ModemError] = &[
+        ModemError::UnexpectedResponse,
+        ModemError::ErrorResponse,
+        ModemError::Timeout,
+        ModemError::NoCarrier,
+        ModemError::BufferOverflow,
+        ModemError::Busy,
+        ModemError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(ModemError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (ModemError::UnexpectedResponse, "unexpected response"),
+            (ModemError::ErrorResponse, "error response"),
+            (ModemError::Timeout, "modem timed out"),
+            (ModemError::NoCarrier, "no carrier"),
+            (ModemError::BufferOverflow, "response buffer overflow"),
+            (ModemError::Busy, "modem busy"),
+            (
+                ModemError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(
+            retryable,
+            [
+                &ModemError::Timeout,
+                &ModemError::NoCarrier,
+                &ModemError::Busy
+            ]
+        );
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(
+            codes,
+            [0x2401, 0x2402, 0x2403, 0x2404, 0x2405, 0x2406, 0x0001,]
+        );
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Recoverable,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "modem");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(millis, [None, None, Some(1), Some(1), None, Some(1), None,]);
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::EPROTO,
+                -errno::EIO,
+                -errno::ETIMEDOUT,
+                -errno::ENOTCONN,
+                -errno::ENOBUFS,
+                -errno::EBUSY,
+                -errno::EIO,
+            ]
+        );
+    }
+}
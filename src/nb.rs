@@ -0,0 +1,137 @@
+//! Conversions from the [`nb`](https://docs.rs/nb) non-blocking error wrapper.
+
+use crate::ImplError;
+
+/// Unwraps an `nb::Error::Other` and maps `nb::Error::WouldBlock` to [`ImplError::Busy`].
+impl From<::nb::Error<ImplError>> for ImplError {
+    fn from(e: ::nb::Error<ImplError>) -> Self {
+        match e {
+            ::nb::Error::Other(e) => e,
+            ::nb::Error::WouldBlock => ImplError::Busy,
+        }
+    }
+}
+
+/// Unwraps an `nb::Error::Other` and maps `nb::Error::WouldBlock` to
+/// [`UsbError::WouldBlock`](crate::UsbError::WouldBlock).
+#[cfg(feature = "usb")]
+impl From<::nb::Error<crate::UsbError>> for crate::UsbError {
+    fn from(e: ::nb::Error<crate::UsbError>) -> Self {
+        match e {
+            ::nb::Error::Other(e) => e,
+            ::nb::Error::WouldBlock => crate::UsbError::WouldBlock,
+        }
+    }
+}
+
+/// Implements the conversion for error kinds without a dedicated would block kind, mapping
+/// `nb::Error::WouldBlock` to [`ImplError::Busy`] wrapped in their `Impl` kind.
+macro_rules! impl_from_nb {
+    ($($feature:tt => $error:ty,)*) => {
+        $(
+            /// Unwraps an `nb::Error::Other` and maps `nb::Error::WouldBlock` to
+            /// [`ImplError::Busy`] wrapped in the `Impl` kind.
+            #[cfg(feature = $feature)]
+            impl From<::nb::Error<$error>> for $error {
+                fn from(e: ::nb::Error<$error>) -> Self {
+                    match e {
+                        ::nb::Error::Other(e) => e,
+                        ::nb::Error::WouldBlock => <$error>::Impl(ImplError::Busy),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_nb! {
+    "adc" => crate::adc::AdcError,
+    "battery" => crate::battery::BatteryError,
+    "ble" => crate::ble::BleError,
+    "can" => crate::can::CanError,
+    "cec" => crate::cec::CecError,
+    "clock" => crate::clock::ClockError,
+    "comparator" => crate::comparator::ComparatorError,
+    "crc" => crate::crc::CrcUnitError,
+    "crypto" => crate::crypto::CryptoError,
+    "dac" => crate::dac::DacError,
+    "display" => crate::display::DisplayError,
+    "dma" => crate::dma::DmaError,
+    "encoder" => crate::encoder::EncoderError,
+    "env_sensor" => crate::env_sensor::EnvSensorError,
+    "ethernet" => crate::ethernet::EthernetError,
+    "flash" => crate::flash::FlashError,
+    "fmc" => crate::fmc::FmcError,
+    "gnss" => crate::gnss::GnssError,
+    "gpio" => crate::gpio::GpioError,
+    "i2c" => crate::i2c::I2cError,
+    "i2s" => crate::i2s::I2sError,
+    "imu" => crate::imu::ImuError,
+    "ir" => crate::ir::IrError,
+    "keypad" => crate::keypad::KeypadError,
+    "lin" => crate::lin::LinError,
+    "lora" => crate::lora::LoRaError,
+    "mci" => crate::mci::MciError,
+    "modem" => crate::modem::ModemError,
+    "one_wire" => crate::one_wire::OneWireError,
+    "opamp" => crate::opamp::OpAmpError,
+    "pdm" => crate::pdm::PdmError,
+    "power" => crate::power::PowerError,
+    "pwm" => crate::pwm::PwmError,
+    "qspi" => crate::qspi::QspiError,
+    "rng" => crate::rng::RngError,
+    "rtc" => crate::rtc::RtcError,
+    "serial" => crate::serial::SerialError,
+    "servo" => crate::servo::ServoError,
+    "smartcard" => crate::smartcard::SmartCardError,
+    "spi" => crate::spi::SpiError,
+    "stepper" => crate::stepper::StepperError,
+    "timer" => crate::timer::TimerError,
+    "touch" => crate::touch::TouchError,
+    "watchdog" => crate::watchdog::WatchdogError,
+    "wifi" => crate::wifi::WifiError,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn impl_error() {
+        assert_eq!(
+            ImplError::from(::nb::Error::Other(ImplError::Internal)),
+            ImplError::Internal
+        );
+        assert_eq!(ImplError::from(::nb::Error::WouldBlock), ImplError::Busy);
+    }
+
+    #[cfg(feature = "usb")]
+    #[test]
+    fn usb_error() {
+        use crate::UsbError;
+
+        assert_eq!(
+            UsbError::from(::nb::Error::Other(UsbError::Stalled)),
+            UsbError::Stalled
+        );
+        assert_eq!(
+            UsbError::from(::nb::Error::WouldBlock),
+            UsbError::WouldBlock
+        );
+    }
+
+    #[cfg(feature = "i2c")]
+    #[test]
+    fn peripheral_error() {
+        use crate::I2cError;
+
+        assert_eq!(
+            I2cError::from(::nb::Error::Other(I2cError::Overrun)),
+            I2cError::Overrun
+        );
+        assert_eq!(
+            I2cError::from(::nb::Error::WouldBlock),
+            I2cError::Impl(ImplError::Busy)
+        );
+    }
+}
@@ -0,0 +1,313 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// A 1-Wire specific error.
+///
+/// This error type contains errors specific to 1-Wire bus peripherals. Also it has an `Impl` kind
+/// to pass through implementation specific errors occurring while trying to use a 1-Wire
+/// peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum OneWireError {
+    /// No presence pulse was detected after the reset pulse
+    NoDevicePresent,
+    /// The CRC of the received data does not match
+    CrcMismatch,
+    /// The bus is held low permanently
+    BusShorted,
+    /// The bus did not return to idle in time
+    Timeout,
+    /// A device responded with unexpected data, e.g. during a search
+    UnexpectedResponse,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl OneWireError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `CrcMismatch` and `Timeout` are transient and thus retryable. `Impl` errors are retryable if
+    /// the wrapped [`ImplError`] is. All other kinds are hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            OneWireError::CrcMismatch | OneWireError::Timeout => true,
+            OneWireError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`]. `CrcMismatch` and `Timeout`
+    /// suggest 1 ms, all other kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            OneWireError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`OneWireError::Impl`] and `false` for the 1-Wire specific kinds, e.g.
+    /// [`OneWireError::NoDevicePresent`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, OneWireError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`OneWireError::Impl`] with `f`.
+    ///
+    /// The 1-Wire specific kinds, e.g. [`OneWireError::NoDevicePresent`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            OneWireError::Impl(e) => OneWireError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// 1-Wire errors use the high byte `0x18` and the index of the error kind as low byte, e.g.
+    /// `0x1801` for `NoDevicePresent`. See the [crate level documentation](crate#error-codes) for
+    /// details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            OneWireError::NoDevicePresent => 0x1801,
+            OneWireError::CrcMismatch => 0x1802,
+            OneWireError::BusShorted => 0x1803,
+            OneWireError::Timeout => 0x1804,
+            OneWireError::UnexpectedResponse => 0x1805,
+            OneWireError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`OneWireError::to_code`], e.g. `0x1801` to
+    /// [`OneWireError::NoDevicePresent`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the 1-Wire error kinds nor to
+    /// an [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x1801 => Some(OneWireError::NoDevicePresent),
+            0x1802 => Some(OneWireError::CrcMismatch),
+            0x1803 => Some(OneWireError::BusShorted),
+            0x1804 => Some(OneWireError::Timeout),
+            0x1805 => Some(OneWireError::UnexpectedResponse),
+            0x0000..=0x00ff => ImplError::from_code(code).map(OneWireError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. `BusShorted` is [`Severity::Fatal`]. All other kinds
+    /// are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            OneWireError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            OneWireError::BusShorted => Severity::Fatal,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"one_wire"`, the stable lowercase name of the 1-Wire peripheral, for all kinds
+    /// including `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "one_wire"
+    }
+
+    /// Returns the name of the error kind, e.g. `"NoDevicePresent"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            OneWireError::NoDevicePresent => "NoDevicePresent",
+            OneWireError::CrcMismatch => "CrcMismatch",
+            OneWireError::BusShorted => "BusShorted",
+            OneWireError::Timeout => "Timeout",
+            OneWireError::UnexpectedResponse => "UnexpectedResponse",
+            OneWireError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `NoDevicePresent` maps to
+    /// `-ENXIO`. `UnexpectedResponse` maps to `-EPROTO`. `CrcMismatch` maps to `-EILSEQ`. `Timeout`
+    /// maps to `-ETIMEDOUT`. All other kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            OneWireError::Impl(e) => e.to_errno(),
+            OneWireError::NoDevicePresent => -errno::ENXIO,
+            OneWireError::UnexpectedResponse => -errno::EPROTO,
+            OneWireError::CrcMismatch => -errno::EILSEQ,
+            OneWireError::Timeout => -errno::ETIMEDOUT,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for OneWireError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OneWireError::NoDevicePresent => f.write_str("no device present"),
+            OneWireError::CrcMismatch => f.write_str("CRC does not match the received data"),
+            OneWireError::BusShorted => f.write_str("bus shorted"),
+            OneWireError::Timeout => f.write_str("timeout"),
+            OneWireError::UnexpectedResponse => f.write_str("unexpected response"),
+            OneWireError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for OneWireError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            OneWireError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(OneWireError, impl_error);
+
+impl PeripheralError for OneWireError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a OneWireError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a OneWireError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[OneWireError] = &[
+        OneWireError::NoDevicePresent,
+        OneWireError::CrcMismatch,
+        OneWireError::BusShorted,
+        OneWireError::Timeout,
+        OneWireError::UnexpectedResponse,
+        OneWireError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(OneWireError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (OneWireError::NoDevicePresent, "no device present"),
+            (
+                OneWireError::CrcMismatch,
+                "CRC does not match the received data",
+            ),
+            (OneWireError::BusShorted, "bus shorted"),
+            (OneWireError::Timeout, "timeout"),
+            (OneWireError::UnexpectedResponse, "unexpected response"),
+            (
+                OneWireError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(
+            retryable,
+            [&OneWireError::CrcMismatch, &OneWireError::Timeout]
+        );
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(codes, [0x1801, 0x1802, 0x1803, 0x1804, 0x1805, 0x0001,]);
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Degraded,
+                Severity::Recoverable,
+                Severity::Fatal,
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "one_wire");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(millis, [None, Some(1), None, Some(1), None, None,]);
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::ENXIO,
+                -errno::EILSEQ,
+                -errno::EIO,
+                -errno::ETIMEDOUT,
+                -errno::EPROTO,
+                -errno::EIO,
+            ]
+        );
+    }
+}
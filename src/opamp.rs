@@ -0,0 +1,293 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// An op-amp specific error.
+///
+/// This error type contains errors specific to operational amplifier peripherals. Also it has an
+/// `Impl` kind to pass through implementation specific errors occurring while trying to use an
+/// op-amp peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum OpAmpError {
+    /// The requested gain is not supported in PGA mode
+    GainUnsupported,
+    /// The requested input cannot be connected to the amplifier
+    InputUnavailable,
+    /// The offset calibration did not complete successfully
+    CalibrationFailed,
+    /// The output cannot be routed to the requested pin or peripheral
+    OutputUnavailable,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl OpAmpError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `Impl` errors are retryable if the wrapped [`ImplError`] is. All other kinds are hard
+    /// faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            OpAmpError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`], the op-amp specific kinds are
+    /// not retryable and return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            OpAmpError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`OpAmpError::Impl`] and `false` for the op-amp specific kinds, e.g.
+    /// [`OpAmpError::GainUnsupported`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, OpAmpError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`OpAmpError::Impl`] with `f`.
+    ///
+    /// The op-amp specific kinds, e.g. [`OpAmpError::GainUnsupported`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            OpAmpError::Impl(e) => OpAmpError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// Op-amp errors use the high byte `0x20` and the index of the error kind as low byte, e.g.
+    /// `0x2001` for `GainUnsupported`. See the [crate level documentation](crate#error-codes) for
+    /// details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            OpAmpError::GainUnsupported => 0x2001,
+            OpAmpError::InputUnavailable => 0x2002,
+            OpAmpError::CalibrationFailed => 0x2003,
+            OpAmpError::OutputUnavailable => 0x2004,
+            OpAmpError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`OpAmpError::to_code`], e.g. `0x2001` to
+    /// [`OpAmpError::GainUnsupported`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the op-amp error kinds nor to
+    /// an [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x2001 => Some(OpAmpError::GainUnsupported),
+            0x2002 => Some(OpAmpError::InputUnavailable),
+            0x2003 => Some(OpAmpError::CalibrationFailed),
+            0x2004 => Some(OpAmpError::OutputUnavailable),
+            0x0000..=0x00ff => ImplError::from_code(code).map(OpAmpError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. `GainUnsupported`, `InputUnavailable` and
+    /// `OutputUnavailable` are [`Severity::Fatal`]. All other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            OpAmpError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            OpAmpError::GainUnsupported
+            | OpAmpError::InputUnavailable
+            | OpAmpError::OutputUnavailable => Severity::Fatal,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"opamp"`, the stable lowercase name of the op-amp peripheral, for all kinds
+    /// including `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "opamp"
+    }
+
+    /// Returns the name of the error kind, e.g. `"GainUnsupported"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            OpAmpError::GainUnsupported => "GainUnsupported",
+            OpAmpError::InputUnavailable => "InputUnavailable",
+            OpAmpError::CalibrationFailed => "CalibrationFailed",
+            OpAmpError::OutputUnavailable => "OutputUnavailable",
+            OpAmpError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `InputUnavailable` and
+    /// `OutputUnavailable` map to `-ENODEV`. All other kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            OpAmpError::Impl(e) => e.to_errno(),
+            OpAmpError::InputUnavailable | OpAmpError::OutputUnavailable => -errno::ENODEV,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for OpAmpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OpAmpError::GainUnsupported => f.write_str("gain unsupported"),
+            OpAmpError::InputUnavailable => f.write_str("input unavailable"),
+            OpAmpError::CalibrationFailed => f.write_str("calibration failed"),
+            OpAmpError::OutputUnavailable => f.write_str("output unavailable"),
+            OpAmpError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for OpAmpError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            OpAmpError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(OpAmpError, impl_error);
+
+impl PeripheralError for OpAmpError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a OpAmpError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a OpAmpError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[OpAmpError] = &[
+        OpAmpError::GainUnsupported,
+        OpAmpError::InputUnavailable,
+        OpAmpError::CalibrationFailed,
+        OpAmpError::OutputUnavailable,
+        OpAmpError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(OpAmpError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (OpAmpError::GainUnsupported, "gain unsupported"),
+            (OpAmpError::InputUnavailable, "input unavailable"),
+            (OpAmpError::CalibrationFailed, "calibration failed"),
+            (OpAmpError::OutputUnavailable, "output unavailable"),
+            (
+                OpAmpError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        assert!(!KINDS.iter().any(|e| e.is_retryable()));
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(codes, [0x2001, 0x2002, 0x2003, 0x2004, 0x0001,]);
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Fatal,
+                Severity::Fatal,
+                Severity::Degraded,
+                Severity::Fatal,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "opamp");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(millis, [None, None, None, None, None,]);
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::EIO,
+                -errno::ENODEV,
+                -errno::EIO,
+                -errno::ENODEV,
+                -errno::EIO,
+            ]
+        );
+    }
+}
@@ -0,0 +1,291 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// A PDM specific error.
+///
+/// This error type contains errors specific to PDM (Pulse Density Modulation) microphone interface
+/// peripherals. Also it has an `Impl` kind to pass through implementation specific errors occurring
+/// while trying to use a PDM peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum PdmError {
+    /// The peripheral receive buffer was overrun
+    Overrun,
+    /// The peripheral ran out of data, e.g. because the DMA was not served in time
+    Underrun,
+    /// The microphone clock is missing or outside of the supported range
+    ClockError,
+    /// The requested decimation ratio or filter order is not supported
+    DecimationUnsupported,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl PdmError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `Overrun` and `Underrun` are transient and thus retryable. `Impl` errors are retryable if
+    /// the wrapped [`ImplError`] is. All other kinds are hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            PdmError::Overrun | PdmError::Underrun => true,
+            PdmError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`]. `Overrun` and `Underrun` suggest
+    /// 1 ms, all other kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            PdmError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`PdmError::Impl`] and `false` for the PDM specific kinds, e.g.
+    /// [`PdmError::Overrun`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, PdmError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`PdmError::Impl`] with `f`.
+    ///
+    /// The PDM specific kinds, e.g. [`PdmError::Overrun`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            PdmError::Impl(e) => PdmError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// PDM errors use the high byte `0x22` and the index of the error kind as low byte, e.g.
+    /// `0x2201` for `Overrun`. See the [crate level documentation](crate#error-codes) for details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            PdmError::Overrun => 0x2201,
+            PdmError::Underrun => 0x2202,
+            PdmError::ClockError => 0x2203,
+            PdmError::DecimationUnsupported => 0x2204,
+            PdmError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`PdmError::to_code`], e.g. `0x2201` to [`PdmError::Overrun`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the PDM error kinds nor to an
+    /// [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x2201 => Some(PdmError::Overrun),
+            0x2202 => Some(PdmError::Underrun),
+            0x2203 => Some(PdmError::ClockError),
+            0x2204 => Some(PdmError::DecimationUnsupported),
+            0x0000..=0x00ff => ImplError::from_code(code).map(PdmError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. `DecimationUnsupported` is [`Severity::Fatal`]. All
+    /// other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            PdmError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            PdmError::DecimationUnsupported => Severity::Fatal,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"pdm"`, the stable lowercase name of the PDM peripheral, for all kinds including
+    /// `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "pdm"
+    }
+
+    /// Returns the name of the error kind, e.g. `"Overrun"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            PdmError::Overrun => "Overrun",
+            PdmError::Underrun => "Underrun",
+            PdmError::ClockError => "ClockError",
+            PdmError::DecimationUnsupported => "DecimationUnsupported",
+            PdmError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `Overrun` maps to `-EOVERFLOW`.
+    /// All other kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            PdmError::Impl(e) => e.to_errno(),
+            PdmError::Overrun => -errno::EOVERFLOW,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for PdmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PdmError::Overrun => f.write_str("receive buffer overrun"),
+            PdmError::Underrun => f.write_str("buffer underrun"),
+            PdmError::ClockError => f.write_str("microphone clock error"),
+            PdmError::DecimationUnsupported => f.write_str("decimation unsupported"),
+            PdmError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for PdmError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            PdmError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(PdmError, impl_error);
+
+impl PeripheralError for PdmError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a PdmError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a PdmError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[PdmError] = &[
+        PdmError::Overrun,
+        PdmError::Underrun,
+        PdmError::ClockError,
+        PdmError::DecimationUnsupported,
+        PdmError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(PdmError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (PdmError::Overrun, "receive buffer overrun"),
+            (PdmError::Underrun, "buffer underrun"),
+            (PdmError::ClockError, "microphone clock error"),
+            (PdmError::DecimationUnsupported, "decimation unsupported"),
+            (
+                PdmError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(retryable, [&PdmError::Overrun, &PdmError::Underrun]);
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(codes, [0x2201, 0x2202, 0x2203, 0x2204, 0x0001,]);
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Fatal,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "pdm");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(millis, [Some(1), Some(1), None, None, None,]);
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::EOVERFLOW,
+                -errno::EIO,
+                -errno::EIO,
+                -errno::EIO,
+                -errno::EIO,
+            ]
+        );
+    }
+}
@@ -0,0 +1,312 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// A power management specific error.
+///
+/// This error type contains errors specific to power management peripherals. Also it has an `Impl`
+/// kind to pass through implementation specific errors occurring while trying to use a power
+/// management peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum PowerError {
+    /// The voltage regulator is not ready
+    RegulatorNotReady,
+    /// Changing the core voltage scaling did not complete
+    VoltageScalingFailed,
+    /// The requested wakeup source is not available in the target mode
+    WakeupSourceUnavailable,
+    /// Entering or leaving the requested power mode failed
+    ModeTransitionFailed,
+    /// The backup domain is write protected
+    BackupDomainLocked,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl PowerError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `RegulatorNotReady` is transient and thus retryable. `Impl` errors are retryable if the
+    /// wrapped [`ImplError`] is. All other kinds are hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            PowerError::RegulatorNotReady => true,
+            PowerError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`]. `RegulatorNotReady` suggests 1
+    /// ms, all other kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            PowerError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`PowerError::Impl`] and `false` for the power specific kinds, e.g.
+    /// [`PowerError::RegulatorNotReady`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, PowerError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`PowerError::Impl`] with `f`.
+    ///
+    /// The power specific kinds, e.g. [`PowerError::RegulatorNotReady`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            PowerError::Impl(e) => PowerError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// Power errors use the high byte `0x17` and the index of the error kind as low byte, e.g.
+    /// `0x1701` for `RegulatorNotReady`. See the [crate level documentation](crate#error-codes) for
+    /// details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            PowerError::RegulatorNotReady => 0x1701,
+            PowerError::VoltageScalingFailed => 0x1702,
+            PowerError::WakeupSourceUnavailable => 0x1703,
+            PowerError::ModeTransitionFailed => 0x1704,
+            PowerError::BackupDomainLocked => 0x1705,
+            PowerError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`PowerError::to_code`], e.g. `0x1701` to
+    /// [`PowerError::RegulatorNotReady`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the power error kinds nor to
+    /// an [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x1701 => Some(PowerError::RegulatorNotReady),
+            0x1702 => Some(PowerError::VoltageScalingFailed),
+            0x1703 => Some(PowerError::WakeupSourceUnavailable),
+            0x1704 => Some(PowerError::ModeTransitionFailed),
+            0x1705 => Some(PowerError::BackupDomainLocked),
+            0x0000..=0x00ff => ImplError::from_code(code).map(PowerError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. `WakeupSourceUnavailable` is [`Severity::Fatal`]. All
+    /// other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            PowerError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            PowerError::WakeupSourceUnavailable => Severity::Fatal,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"power"`, the stable lowercase name of the power peripheral, for all kinds
+    /// including `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "power"
+    }
+
+    /// Returns the name of the error kind, e.g. `"RegulatorNotReady"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            PowerError::RegulatorNotReady => "RegulatorNotReady",
+            PowerError::VoltageScalingFailed => "VoltageScalingFailed",
+            PowerError::WakeupSourceUnavailable => "WakeupSourceUnavailable",
+            PowerError::ModeTransitionFailed => "ModeTransitionFailed",
+            PowerError::BackupDomainLocked => "BackupDomainLocked",
+            PowerError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `RegulatorNotReady` maps to
+    /// `-EAGAIN`. `BackupDomainLocked` maps to `-EACCES`. `WakeupSourceUnavailable` maps to
+    /// `-ENODEV`. All other kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            PowerError::Impl(e) => e.to_errno(),
+            PowerError::RegulatorNotReady => -errno::EAGAIN,
+            PowerError::BackupDomainLocked => -errno::EACCES,
+            PowerError::WakeupSourceUnavailable => -errno::ENODEV,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for PowerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PowerError::RegulatorNotReady => f.write_str("regulator not ready"),
+            PowerError::VoltageScalingFailed => f.write_str("voltage scaling failed"),
+            PowerError::WakeupSourceUnavailable => f.write_str("wakeup source unavailable"),
+            PowerError::ModeTransitionFailed => f.write_str("power mode transition failed"),
+            PowerError::BackupDomainLocked => f.write_str("backup domain locked"),
+            PowerError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for PowerError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            PowerError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(PowerError, impl_error);
+
+impl PeripheralError for PowerError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a PowerError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a PowerError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[PowerError] = &[
+        PowerError::RegulatorNotReady,
+        PowerError::VoltageScalingFailed,
+        PowerError::WakeupSourceUnavailable,
+        PowerError::ModeTransitionFailed,
+        PowerError::BackupDomainLocked,
+        PowerError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(PowerError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (PowerError::RegulatorNotReady, "regulator not ready"),
+            (PowerError::VoltageScalingFailed, "voltage scaling failed"),
+            (
+                PowerError::WakeupSourceUnavailable,
+                "wakeup source unavailable",
+            ),
+            (
+                PowerError::ModeTransitionFailed,
+                "power mode transition failed",
+            ),
+            (PowerError::BackupDomainLocked, "backup domain locked"),
+            (
+                PowerError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(retryable, [&PowerError::RegulatorNotReady]);
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(codes, [0x1701, 0x1702, 0x1703, 0x1704, 0x1705, 0x0001,]);
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Fatal,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "power");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(millis, [Some(1), None, None, None, None, None,]);
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::EAGAIN,
+                -errno::EIO,
+                -errno::ENODEV,
+                -errno::EIO,
+                -errno::EACCES,
+                -errno::EIO,
+            ]
+        );
+    }
+}
@@ -0,0 +1,107 @@
+//! Convenience re-export of all error types.
+//!
+//! Drivers touching several peripherals can bring all error types into scope at once:
+//!
+//! ```
+//! use embedded_error::prelude::*;
+//!
+//! fn report(e: &dyn PeripheralError) -> &'static str {
+//!     e.peripheral()
+//! }
+//!
+//! assert_eq!(report(&ImplError::Disconnected), "impl");
+//! ```
+
+#[cfg(feature = "adc")]
+pub use crate::adc::AdcError;
+#[cfg(feature = "battery")]
+pub use crate::battery::BatteryError;
+#[cfg(feature = "ble")]
+pub use crate::ble::BleError;
+#[cfg(feature = "can")]
+pub use crate::can::{CanError, CanFdError};
+#[cfg(feature = "cec")]
+pub use crate::cec::CecError;
+#[cfg(feature = "clock")]
+pub use crate::clock::ClockError;
+#[cfg(feature = "comparator")]
+pub use crate::comparator::ComparatorError;
+#[cfg(feature = "crc")]
+pub use crate::crc::CrcUnitError;
+#[cfg(feature = "crypto")]
+pub use crate::crypto::CryptoError;
+#[cfg(feature = "dac")]
+pub use crate::dac::DacError;
+#[cfg(feature = "display")]
+pub use crate::display::DisplayError;
+#[cfg(feature = "dma")]
+pub use crate::dma::DmaError;
+#[cfg(feature = "encoder")]
+pub use crate::encoder::EncoderError;
+#[cfg(feature = "env_sensor")]
+pub use crate::env_sensor::EnvSensorError;
+#[cfg(feature = "ethernet")]
+pub use crate::ethernet::EthernetError;
+#[cfg(feature = "flash")]
+pub use crate::flash::FlashError;
+#[cfg(feature = "fmc")]
+pub use crate::fmc::FmcError;
+#[cfg(feature = "gnss")]
+pub use crate::gnss::GnssError;
+#[cfg(feature = "i2s")]
+pub use crate::i2s::I2sError;
+#[cfg(feature = "imu")]
+pub use crate::imu::ImuError;
+#[cfg(feature = "ir")]
+pub use crate::ir::IrError;
+#[cfg(feature = "keypad")]
+pub use crate::keypad::KeypadError;
+#[cfg(feature = "lin")]
+pub use crate::lin::LinError;
+#[cfg(feature = "lora")]
+pub use crate::lora::LoRaError;
+#[cfg(feature = "mci")]
+pub use crate::mci::{CommandOrDataError, MciError, SetupError};
+#[cfg(feature = "modem")]
+pub use crate::modem::ModemError;
+#[cfg(feature = "one_wire")]
+pub use crate::one_wire::OneWireError;
+#[cfg(feature = "opamp")]
+pub use crate::opamp::OpAmpError;
+#[cfg(feature = "pdm")]
+pub use crate::pdm::PdmError;
+#[cfg(feature = "power")]
+pub use crate::power::PowerError;
+#[cfg(feature = "pwm")]
+pub use crate::pwm::PwmError;
+#[cfg(feature = "qspi")]
+pub use crate::qspi::QspiError;
+#[cfg(feature = "rng")]
+pub use crate::rng::RngError;
+#[cfg(feature = "rtc")]
+pub use crate::rtc::RtcError;
+#[cfg(feature = "servo")]
+pub use crate::servo::ServoError;
+#[cfg(feature = "smartcard")]
+pub use crate::smartcard::SmartCardError;
+#[cfg(feature = "stepper")]
+pub use crate::stepper::StepperError;
+#[cfg(feature = "timer")]
+pub use crate::timer::TimerError;
+#[cfg(feature = "touch")]
+pub use crate::touch::TouchError;
+#[cfg(feature = "watchdog")]
+pub use crate::watchdog::WatchdogError;
+#[cfg(feature = "wifi")]
+pub use crate::wifi::WifiError;
+#[cfg(feature = "gpio")]
+pub use crate::GpioError;
+#[cfg(feature = "spi")]
+pub use crate::SpiError;
+#[cfg(feature = "usb")]
+pub use crate::UsbError;
+pub use crate::{AnyError, ImplError, PeripheralError, ResultExt, Severity};
+#[cfg(feature = "i2c")]
+pub use crate::{I2cError, NackSource};
+#[cfg(feature = "serial")]
+pub use crate::{NoiseKind, SerialError};
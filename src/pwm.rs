@@ -0,0 +1,330 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// A PWM specific error.
+///
+/// This error type contains errors specific to PWM (Pulse Width Modulation) peripherals. Also it
+/// has an `Impl` kind to pass through implementation specific errors occurring while trying to use
+/// a PWM peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum PwmError {
+    /// The requested frequency is higher than the timer clock allows
+    FrequencyTooHigh,
+    /// The requested frequency is lower than the prescaler and counter width allow
+    FrequencyTooLow,
+    /// The requested duty cycle exceeds the maximum duty of the channel
+    DutyOutOfRange,
+    /// The requested channel does not exist or is not available
+    ChannelUnavailable,
+    /// Dead time insertion is not supported by the peripheral or channel
+    DeadTimeUnsupported,
+    /// The requested counter alignment (e.g. center-aligned) is not supported
+    AlignmentUnsupported,
+    /// The channel output is disabled, e.g. by a break input or missing pin configuration
+    OutputDisabled,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl PwmError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `Impl` errors are retryable if the wrapped [`ImplError`] is. All other kinds are hard
+    /// faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            PwmError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`], the PWM specific kinds are not
+    /// retryable and return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            PwmError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`PwmError::Impl`] and `false` for the PWM specific kinds, e.g.
+    /// [`PwmError::FrequencyTooHigh`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, PwmError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`PwmError::Impl`] with `f`.
+    ///
+    /// The PWM specific kinds, e.g. [`PwmError::FrequencyTooHigh`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            PwmError::Impl(e) => PwmError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// PWM errors use the high byte `0x0a` and the index of the error kind as low byte, e.g.
+    /// `0x0a01` for `FrequencyTooHigh`. See the [crate level documentation](crate#error-codes) for
+    /// details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            PwmError::FrequencyTooHigh => 0x0a01,
+            PwmError::FrequencyTooLow => 0x0a02,
+            PwmError::DutyOutOfRange => 0x0a03,
+            PwmError::ChannelUnavailable => 0x0a04,
+            PwmError::DeadTimeUnsupported => 0x0a05,
+            PwmError::AlignmentUnsupported => 0x0a06,
+            PwmError::OutputDisabled => 0x0a07,
+            PwmError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`PwmError::to_code`], e.g. `0x0a01` to
+    /// [`PwmError::FrequencyTooHigh`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the PWM error kinds nor to an
+    /// [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x0a01 => Some(PwmError::FrequencyTooHigh),
+            0x0a02 => Some(PwmError::FrequencyTooLow),
+            0x0a03 => Some(PwmError::DutyOutOfRange),
+            0x0a04 => Some(PwmError::ChannelUnavailable),
+            0x0a05 => Some(PwmError::DeadTimeUnsupported),
+            0x0a06 => Some(PwmError::AlignmentUnsupported),
+            0x0a07 => Some(PwmError::OutputDisabled),
+            0x0000..=0x00ff => ImplError::from_code(code).map(PwmError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. `ChannelUnavailable`, `DeadTimeUnsupported` and
+    /// `AlignmentUnsupported` are [`Severity::Fatal`]. All other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            PwmError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            PwmError::ChannelUnavailable
+            | PwmError::DeadTimeUnsupported
+            | PwmError::AlignmentUnsupported => Severity::Fatal,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"pwm"`, the stable lowercase name of the PWM peripheral, for all kinds including
+    /// `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "pwm"
+    }
+
+    /// Returns the name of the error kind, e.g. `"FrequencyTooHigh"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            PwmError::FrequencyTooHigh => "FrequencyTooHigh",
+            PwmError::FrequencyTooLow => "FrequencyTooLow",
+            PwmError::DutyOutOfRange => "DutyOutOfRange",
+            PwmError::ChannelUnavailable => "ChannelUnavailable",
+            PwmError::DeadTimeUnsupported => "DeadTimeUnsupported",
+            PwmError::AlignmentUnsupported => "AlignmentUnsupported",
+            PwmError::OutputDisabled => "OutputDisabled",
+            PwmError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `ChannelUnavailable` maps to
+    /// `-ENODEV`. `FrequencyTooHigh`, `FrequencyTooLow` and `DutyOutOfRange` map to `-ERANGE`. All
+    /// other kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            PwmError::Impl(e) => e.to_errno(),
+            PwmError::ChannelUnavailable => -errno::ENODEV,
+            PwmError::FrequencyTooHigh | PwmError::FrequencyTooLow | PwmError::DutyOutOfRange => {
+                -errno::ERANGE
+            }
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for PwmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PwmError::FrequencyTooHigh => f.write_str("frequency too high"),
+            PwmError::FrequencyTooLow => f.write_str("frequency too low"),
+            PwmError::DutyOutOfRange => f.write_str("duty cycle out of range"),
+            PwmError::ChannelUnavailable => f.write_str("channel unavailable"),
+            PwmError::DeadTimeUnsupported => f.write_str("dead time unsupported"),
+            PwmError::AlignmentUnsupported => f.write_str("alignment unsupported"),
+            PwmError::OutputDisabled => f.write_str("output disabled"),
+            PwmError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for PwmError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            PwmError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(PwmError, impl_error);
+
+impl PeripheralError for PwmError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a PwmError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a PwmError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[PwmError] = &[
+        PwmError::FrequencyTooHigh,
+        PwmError::FrequencyTooLow,
+        PwmError::DutyOutOfRange,
+        PwmError::ChannelUnavailable,
+        PwmError::DeadTimeUnsupported,
+        PwmError::AlignmentUnsupported,
+        PwmError::OutputDisabled,
+        PwmError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(PwmError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (PwmError::FrequencyTooHigh, "frequency too high"),
+            (PwmError::FrequencyTooLow, "frequency too low"),
+            (PwmError::DutyOutOfRange, "duty cycle out of range"),
+            (PwmError::ChannelUnavailable, "channel unavailable"),
+            (PwmError::DeadTimeUnsupported, "dead time unsupported"),
+            (PwmError::AlignmentUnsupported, "alignment unsupported"),
+            (PwmError::OutputDisabled, "output disabled"),
+            (
+                PwmError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        assert!(!KINDS.iter().any(|e| e.is_retryable()));
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(
+            codes,
+            [0x0a01, 0x0a02, 0x0a03, 0x0a04, 0x0a05, 0x0a06, 0x0a07, 0x0001,]
+        );
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Fatal,
+                Severity::Fatal,
+                Severity::Fatal,
+                Severity::Degraded,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "pwm");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(millis, [None, None, None, None, None, None, None, None,]);
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::ERANGE,
+                -errno::ERANGE,
+                -errno::ERANGE,
+                -errno::ENODEV,
+                -errno::EIO,
+                -errno::EIO,
+                -errno::EIO,
+                -errno::EIO,
+            ]
+        );
+    }
+}
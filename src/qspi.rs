@@ -0,0 +1,321 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// A QSPI specific error.
+///
+/// This error type contains errors specific to QSPI/OSPI (Quad/Octo SPI) external memory controller
+/// peripherals. Also it has an `Impl` kind to pass through implementation specific errors occurring
+/// while trying to use a QSPI peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum QspiError {
+    /// The memory did not respond in time, e.g. while polling the status register
+    Timeout,
+    /// An invalid address was accessed or the transfer was aborted
+    TransferError,
+    /// The command or its phase configuration is not supported
+    InvalidCommand,
+    /// The operation cannot be performed while memory mapped mode is active
+    MemoryMappedBusy,
+    /// The FIFO threshold configuration is invalid or was not served in time
+    FifoThresholdError,
+    /// The address exceeds the configured memory size
+    AddressOutOfRange,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl QspiError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `Timeout` and `MemoryMappedBusy` are transient and thus retryable. `Impl` errors are
+    /// retryable if the wrapped [`ImplError`] is. All other kinds are hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            QspiError::Timeout | QspiError::MemoryMappedBusy => true,
+            QspiError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`]. `Timeout` and `MemoryMappedBusy`
+    /// suggest 1 ms, all other kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            QspiError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`QspiError::Impl`] and `false` for the QSPI specific kinds, e.g.
+    /// [`QspiError::Timeout`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, QspiError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`QspiError::Impl`] with `f`.
+    ///
+    /// The QSPI specific kinds, e.g. [`QspiError::Timeout`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            QspiError::Impl(e) => QspiError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// QSPI errors use the high byte `0x12` and the index of the error kind as low byte, e.g.
+    /// `0x1201` for `Timeout`. See the [crate level documentation](crate#error-codes) for details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            QspiError::Timeout => 0x1201,
+            QspiError::TransferError => 0x1202,
+            QspiError::InvalidCommand => 0x1203,
+            QspiError::MemoryMappedBusy => 0x1204,
+            QspiError::FifoThresholdError => 0x1205,
+            QspiError::AddressOutOfRange => 0x1206,
+            QspiError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`QspiError::to_code`], e.g. `0x1201` to [`QspiError::Timeout`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the QSPI error kinds nor to an
+    /// [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x1201 => Some(QspiError::Timeout),
+            0x1202 => Some(QspiError::TransferError),
+            0x1203 => Some(QspiError::InvalidCommand),
+            0x1204 => Some(QspiError::MemoryMappedBusy),
+            0x1205 => Some(QspiError::FifoThresholdError),
+            0x1206 => Some(QspiError::AddressOutOfRange),
+            0x0000..=0x00ff => ImplError::from_code(code).map(QspiError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. `InvalidCommand` is [`Severity::Fatal`]. All other
+    /// kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            QspiError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            QspiError::InvalidCommand => Severity::Fatal,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"qspi"`, the stable lowercase name of the QSPI peripheral, for all kinds including
+    /// `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "qspi"
+    }
+
+    /// Returns the name of the error kind, e.g. `"Timeout"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            QspiError::Timeout => "Timeout",
+            QspiError::TransferError => "TransferError",
+            QspiError::InvalidCommand => "InvalidCommand",
+            QspiError::MemoryMappedBusy => "MemoryMappedBusy",
+            QspiError::FifoThresholdError => "FifoThresholdError",
+            QspiError::AddressOutOfRange => "AddressOutOfRange",
+            QspiError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `MemoryMappedBusy` maps to
+    /// `-EBUSY`. `InvalidCommand` maps to `-EINVAL`. `AddressOutOfRange` maps to `-ERANGE`.
+    /// `Timeout` maps to `-ETIMEDOUT`. All other kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            QspiError::Impl(e) => e.to_errno(),
+            QspiError::MemoryMappedBusy => -errno::EBUSY,
+            QspiError::InvalidCommand => -errno::EINVAL,
+            QspiError::AddressOutOfRange => -errno::ERANGE,
+            QspiError::Timeout => -errno::ETIMEDOUT,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for QspiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QspiError::Timeout => f.write_str("timeout"),
+            QspiError::TransferError => f.write_str("transfer error"),
+            QspiError::InvalidCommand => f.write_str("invalid command"),
+            QspiError::MemoryMappedBusy => f.write_str("memory mapped mode busy"),
+            QspiError::FifoThresholdError => f.write_str("FIFO threshold error"),
+            QspiError::AddressOutOfRange => f.write_str("address out of range"),
+            QspiError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for QspiError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            QspiError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(QspiError, impl_error);
+
+impl PeripheralError for QspiError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a QspiError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a QspiError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[QspiError] = &[
+        QspiError::Timeout,
+        QspiError::TransferError,
+        QspiError::InvalidCommand,
+        QspiError::MemoryMappedBusy,
+        QspiError::FifoThresholdError,
+        QspiError::AddressOutOfRange,
+        QspiError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(QspiError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (QspiError::Timeout, "timeout"),
+            (QspiError::TransferError, "transfer error"),
+            (QspiError::InvalidCommand, "invalid command"),
+            (QspiError::MemoryMappedBusy, "memory mapped mode busy"),
+            (QspiError::FifoThresholdError, "FIFO threshold error"),
+            (QspiError::AddressOutOfRange, "address out of range"),
+            (
+                QspiError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(
+            retryable,
+            [&QspiError::Timeout, &QspiError::MemoryMappedBusy]
+        );
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(
+            codes,
+            [0x1201, 0x1202, 0x1203, 0x1204, 0x1205, 0x1206, 0x0001,]
+        );
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Fatal,
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "qspi");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(millis, [Some(1), None, None, Some(1), None, None, None,]);
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::ETIMEDOUT,
+                -errno::EIO,
+                -errno::EINVAL,
+                -errno::EBUSY,
+                -errno::EIO,
+                -errno::ERANGE,
+                -errno::EIO,
+            ]
+        );
+    }
+}
@@ -0,0 +1,292 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// An RNG specific error.
+///
+/// This error type contains errors specific to RNG/TRNG (True Random Number Generator) peripherals.
+/// Also it has an `Impl` kind to pass through implementation specific errors occurring while trying
+/// to use an RNG peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum RngError {
+    /// The entropy source did not provide enough entropy
+    SeedError,
+    /// The RNG clock is missing or too slow
+    ClockError,
+    /// No random data is available yet
+    NotReady,
+    /// The continuous health test of the entropy source failed
+    HealthTestFailed,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl RngError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `SeedError` and `NotReady` are transient and thus retryable. `Impl` errors are retryable if
+    /// the wrapped [`ImplError`] is. All other kinds are hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            RngError::SeedError | RngError::NotReady => true,
+            RngError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`]. `SeedError` and `NotReady`
+    /// suggest 1 ms, all other kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            RngError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`RngError::Impl`] and `false` for the RNG specific kinds, e.g.
+    /// [`RngError::SeedError`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, RngError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`RngError::Impl`] with `f`.
+    ///
+    /// The RNG specific kinds, e.g. [`RngError::SeedError`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            RngError::Impl(e) => RngError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// RNG errors use the high byte `0x13` and the index of the error kind as low byte, e.g.
+    /// `0x1301` for `SeedError`. See the [crate level documentation](crate#error-codes) for
+    /// details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            RngError::SeedError => 0x1301,
+            RngError::ClockError => 0x1302,
+            RngError::NotReady => 0x1303,
+            RngError::HealthTestFailed => 0x1304,
+            RngError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`RngError::to_code`], e.g. `0x1301` to [`RngError::SeedError`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the RNG error kinds nor to an
+    /// [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x1301 => Some(RngError::SeedError),
+            0x1302 => Some(RngError::ClockError),
+            0x1303 => Some(RngError::NotReady),
+            0x1304 => Some(RngError::HealthTestFailed),
+            0x0000..=0x00ff => ImplError::from_code(code).map(RngError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. `HealthTestFailed` is [`Severity::Fatal`]. All other
+    /// kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            RngError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            RngError::HealthTestFailed => Severity::Fatal,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"rng"`, the stable lowercase name of the RNG peripheral, for all kinds including
+    /// `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "rng"
+    }
+
+    /// Returns the name of the error kind, e.g. `"SeedError"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            RngError::SeedError => "SeedError",
+            RngError::ClockError => "ClockError",
+            RngError::NotReady => "NotReady",
+            RngError::HealthTestFailed => "HealthTestFailed",
+            RngError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `NotReady` maps to `-EAGAIN`.
+    /// All other kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            RngError::Impl(e) => e.to_errno(),
+            RngError::NotReady => -errno::EAGAIN,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for RngError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RngError::SeedError => f.write_str("seed error"),
+            RngError::ClockError => f.write_str("RNG clock error"),
+            RngError::NotReady => f.write_str("no random data ready"),
+            RngError::HealthTestFailed => f.write_str("health test failed"),
+            RngError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for RngError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            RngError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(RngError, impl_error);
+
+impl PeripheralError for RngError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a RngError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a RngError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[RngError] = &[
+        RngError::SeedError,
+        RngError::ClockError,
+        RngError::NotReady,
+        RngError::HealthTestFailed,
+        RngError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(RngError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (RngError::SeedError, "seed error"),
+            (RngError::ClockError, "RNG clock error"),
+            (RngError::NotReady, "no random data ready"),
+            (RngError::HealthTestFailed, "health test failed"),
+            (
+                RngError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(retryable, [&RngError::SeedError, &RngError::NotReady]);
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(codes, [0x1301, 0x1302, 0x1303, 0x1304, 0x0001,]);
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Recoverable,
+                Severity::Fatal,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "rng");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(millis, [Some(1), None, Some(1), None, None,]);
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::EIO,
+                -errno::EIO,
+                -errno::EAGAIN,
+                -errno::EIO,
+                -errno::EIO,
+            ]
+        );
+    }
+}
@@ -0,0 +1,319 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// An RTC specific error.
+///
+/// This error type contains errors specific to RTC (Real-Time Clock) peripherals. Also it has an
+/// `Impl` kind to pass through implementation specific errors occurring while trying to use an RTC
+/// peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum RtcError {
+    /// The supplied or stored date and time is not valid
+    InvalidDateTime,
+    /// The RTC oscillator has not started or is not stable yet
+    OscillatorNotReady,
+    /// The requested alarm time has already passed
+    AlarmInThePast,
+    /// The requested calibration value exceeds the supported correction range
+    CalibrationOutOfRange,
+    /// The backup domain is write protected and the RTC cannot be configured
+    BackupDomainLocked,
+    /// The RTC has not been initialized, e.g. after a loss of backup power
+    NotInitialized,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl RtcError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `OscillatorNotReady` is transient and thus retryable. `Impl` errors are retryable if the
+    /// wrapped [`ImplError`] is. All other kinds are hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            RtcError::OscillatorNotReady => true,
+            RtcError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`]. `OscillatorNotReady` suggests 1
+    /// ms, all other kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            RtcError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`RtcError::Impl`] and `false` for the RTC specific kinds, e.g.
+    /// [`RtcError::InvalidDateTime`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, RtcError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`RtcError::Impl`] with `f`.
+    ///
+    /// The RTC specific kinds, e.g. [`RtcError::InvalidDateTime`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            RtcError::Impl(e) => RtcError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// RTC errors use the high byte `0x0c` and the index of the error kind as low byte, e.g.
+    /// `0x0c01` for `InvalidDateTime`. See the [crate level documentation](crate#error-codes) for
+    /// details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            RtcError::InvalidDateTime => 0x0c01,
+            RtcError::OscillatorNotReady => 0x0c02,
+            RtcError::AlarmInThePast => 0x0c03,
+            RtcError::CalibrationOutOfRange => 0x0c04,
+            RtcError::BackupDomainLocked => 0x0c05,
+            RtcError::NotInitialized => 0x0c06,
+            RtcError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`RtcError::to_code`], e.g. `0x0c01` to
+    /// [`RtcError::InvalidDateTime`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the RTC error kinds nor to an
+    /// [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x0c01 => Some(RtcError::InvalidDateTime),
+            0x0c02 => Some(RtcError::OscillatorNotReady),
+            0x0c03 => Some(RtcError::AlarmInThePast),
+            0x0c04 => Some(RtcError::CalibrationOutOfRange),
+            0x0c05 => Some(RtcError::BackupDomainLocked),
+            0x0c06 => Some(RtcError::NotInitialized),
+            0x0000..=0x00ff => ImplError::from_code(code).map(RtcError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. All other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            RtcError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"rtc"`, the stable lowercase name of the RTC peripheral, for all kinds including
+    /// `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "rtc"
+    }
+
+    /// Returns the name of the error kind, e.g. `"InvalidDateTime"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            RtcError::InvalidDateTime => "InvalidDateTime",
+            RtcError::OscillatorNotReady => "OscillatorNotReady",
+            RtcError::AlarmInThePast => "AlarmInThePast",
+            RtcError::CalibrationOutOfRange => "CalibrationOutOfRange",
+            RtcError::BackupDomainLocked => "BackupDomainLocked",
+            RtcError::NotInitialized => "NotInitialized",
+            RtcError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `OscillatorNotReady` maps to
+    /// `-EAGAIN`. `BackupDomainLocked` maps to `-EACCES`. `NotInitialized` maps to `-ENODEV`.
+    /// `InvalidDateTime` and `AlarmInThePast` map to `-EINVAL`. `CalibrationOutOfRange` maps to
+    /// `-ERANGE`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            RtcError::Impl(e) => e.to_errno(),
+            RtcError::OscillatorNotReady => -errno::EAGAIN,
+            RtcError::BackupDomainLocked => -errno::EACCES,
+            RtcError::NotInitialized => -errno::ENODEV,
+            RtcError::InvalidDateTime | RtcError::AlarmInThePast => -errno::EINVAL,
+            RtcError::CalibrationOutOfRange => -errno::ERANGE,
+        }
+    }
+}
+
+impl fmt::Display for RtcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RtcError::InvalidDateTime => f.write_str("invalid date or time"),
+            RtcError::OscillatorNotReady => f.write_str("oscillator not ready"),
+            RtcError::AlarmInThePast => f.write_str("alarm time is in the past"),
+            RtcError::CalibrationOutOfRange => f.write_str("calibration out of range"),
+            RtcError::BackupDomainLocked => f.write_str("backup domain locked"),
+            RtcError::NotInitialized => f.write_str("RTC not initialized"),
+            RtcError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for RtcError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            RtcError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(RtcError, impl_error);
+
+impl PeripheralError for RtcError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a RtcError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a RtcError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[RtcError] = &[
+        RtcError::InvalidDateTime,
+        RtcError::OscillatorNotReady,
+        RtcError::AlarmInThePast,
+        RtcError::CalibrationOutOfRange,
+        RtcError::BackupDomainLocked,
+        RtcError::NotInitialized,
+        RtcError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(RtcError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (RtcError::InvalidDateTime, "invalid date or time"),
+            (RtcError::OscillatorNotReady, "oscillator not ready"),
+            (RtcError::AlarmInThePast, "alarm time is in the past"),
+            (RtcError::CalibrationOutOfRange, "calibration out of range"),
+            (RtcError::BackupDomainLocked, "backup domain locked"),
+            (RtcError::NotInitialized, "RTC not initialized"),
+            (
+                RtcError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(retryable, [&RtcError::OscillatorNotReady]);
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(
+            codes,
+            [0x0c01, 0x0c02, 0x0c03, 0x0c04, 0x0c05, 0x0c06, 0x0001,]
+        );
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Degraded,
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "rtc");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(millis, [None, Some(1), None, None, None, None, None,]);
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::EINVAL,
+                -errno::EAGAIN,
+                -errno::EINVAL,
+                -errno::ERANGE,
+                -errno::EACCES,
+                -errno::ENODEV,
+                -errno::EIO,
+            ]
+        );
+    }
+}
@@ -0,0 +1,405 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// A Serial specific error.
+///
+/// This error type contains errors specific to Serial peripherals. Also it has an `Impl` kind to pass
+/// through implementation specific errors occurring while trying to use a Serial peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum SerialError {
+    /// The peripheral receive buffer was overrun.
+    ///
+    /// This is a hardware condition: a new word arrived before the previous one was read from the
+    /// peripheral. See `BufferFull` for the software equivalent.
+    Overrun,
+    /// Received data does not conform to the peripheral configuration.
+    /// Can be caused by a misconfigured device on either end of the serial line.
+    FrameFormat,
+    /// Parity check failed.
+    Parity,
+    /// Serial line is too noisy to read valid data.
+    ///
+    /// The [`NoiseKind`] tells apart a single sampled glitch from persistent line noise which
+    /// warrants a re-synchronisation.
+    Noise(NoiseKind),
+    /// A break condition was received, i.e. the line was held low for longer than a full frame.
+    Break,
+    /// Hardware flow control failed, e.g. the remote did not release CTS in time.
+    FlowControl,
+    /// The software receive buffer is full and received data was discarded.
+    ///
+    /// Unlike `Overrun` the data was read from the peripheral in time but could not be stored
+    /// because the application did not consume the buffered data fast enough.
+    BufferFull,
+    /// Implementation specific error (shared across all peripheral specific error kinds).
+    Impl(ImplError),
+}
+
+impl SerialError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `Overrun`, `Parity`, `Noise(NoiseKind::Glitch)` and `BufferFull` are transient and thus
+    /// retryable. `Impl` errors are retryable if the wrapped [`ImplError`] is. All other kinds are
+    /// hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            SerialError::Overrun
+            | SerialError::Parity
+            | SerialError::Noise(NoiseKind::Glitch)
+            | SerialError::BufferFull => true,
+            SerialError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`]. `BufferFull` suggests 1 ms, all
+    /// other kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            SerialError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`SerialError::Impl`] and `false` for the serial specific kinds, e.g.
+    /// [`SerialError::Overrun`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, SerialError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`SerialError::Impl`] with `f`.
+    ///
+    /// The serial specific kinds, e.g. [`SerialError::Overrun`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            SerialError::Impl(e) => SerialError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// Serial errors use the high byte `0x04` and the index of the error kind as low byte, e.g.
+    /// `0x0401` for `Overrun`. See the [crate level documentation](crate#error-codes) for details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            SerialError::Overrun => 0x0401,
+            SerialError::FrameFormat => 0x0402,
+            SerialError::Parity => 0x0403,
+            SerialError::Noise(NoiseKind::Glitch) => 0x0404,
+            SerialError::Break => 0x0405,
+            SerialError::FlowControl => 0x0406,
+            SerialError::BufferFull => 0x0407,
+            SerialError::Noise(NoiseKind::Sustained) => 0x0408,
+            SerialError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`SerialError::to_code`], e.g. `0x0401` to
+    /// [`SerialError::Overrun`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the serial error kinds nor to
+    /// an [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x0401 => Some(SerialError::Overrun),
+            0x0402 => Some(SerialError::FrameFormat),
+            0x0403 => Some(SerialError::Parity),
+            0x0404 => Some(SerialError::Noise(NoiseKind::Glitch)),
+            0x0405 => Some(SerialError::Break),
+            0x0406 => Some(SerialError::FlowControl),
+            0x0407 => Some(SerialError::BufferFull),
+            0x0408 => Some(SerialError::Noise(NoiseKind::Sustained)),
+            0x0000..=0x00ff => ImplError::from_code(code).map(SerialError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. All other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            SerialError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"serial"`, the stable lowercase name of the serial peripheral, for all kinds
+    /// including `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "serial"
+    }
+
+    /// Returns the name of the error kind, e.g. `"Overrun"`.
+    ///
+    /// The data carried by `Noise` is not included. `Impl` errors return `"Impl"` instead of the
+    /// name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            SerialError::Overrun => "Overrun",
+            SerialError::FrameFormat => "FrameFormat",
+            SerialError::Parity => "Parity",
+            SerialError::Noise(_) => "Noise",
+            SerialError::Break => "Break",
+            SerialError::FlowControl => "FlowControl",
+            SerialError::BufferFull => "BufferFull",
+            SerialError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `FrameFormat` and `Parity` map
+    /// to `-EPROTO`. `Overrun` maps to `-EOVERFLOW`. `BufferFull` maps to `-ENOBUFS`. All other
+    /// kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            SerialError::Impl(e) => e.to_errno(),
+            SerialError::FrameFormat | SerialError::Parity => -errno::EPROTO,
+            SerialError::Overrun => -errno::EOVERFLOW,
+            SerialError::BufferFull => -errno::ENOBUFS,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for SerialError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SerialError::Overrun => f.write_str("receive buffer overrun"),
+            SerialError::FrameFormat => {
+                f.write_str("received data does not match the frame format")
+            }
+            SerialError::Parity => f.write_str("parity check failed"),
+            SerialError::Noise(NoiseKind::Glitch) => f.write_str("noise glitch on the line"),
+            SerialError::Noise(NoiseKind::Sustained) => {
+                f.write_str("line too noisy to read valid data")
+            }
+            SerialError::Break => f.write_str("break condition received"),
+            SerialError::FlowControl => f.write_str("flow control error"),
+            SerialError::BufferFull => f.write_str("receive buffer full"),
+            SerialError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for SerialError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            SerialError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(SerialError, impl_error);
+
+impl PeripheralError for SerialError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a SerialError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a SerialError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+/// The kind of noise detected on a serial line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum NoiseKind {
+    /// A single sample disagreed with the others, the data may still be valid
+    Glitch,
+    /// The line is persistently noisy, the receiver should re-synchronise
+    Sustained,
+}
+
+impl NoiseKind {
+    /// All noise kinds.
+    pub const ALL: &'static [Self] = &[NoiseKind::Glitch, NoiseKind::Sustained];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[SerialError] = &[
+        SerialError::Overrun,
+        SerialError::FrameFormat,
+        SerialError::Parity,
+        SerialError::Noise(NoiseKind::Glitch),
+        SerialError::Noise(NoiseKind::Sustained),
+        SerialError::Break,
+        SerialError::FlowControl,
+        SerialError::BufferFull,
+        SerialError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(SerialError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (SerialError::Overrun, "receive buffer overrun"),
+            (
+                SerialError::FrameFormat,
+                "received data does not match the frame format",
+            ),
+            (SerialError::Parity, "parity check failed"),
+            (
+                SerialError::Noise(NoiseKind::Glitch),
+                "noise glitch on the line",
+            ),
+            (
+                SerialError::Noise(NoiseKind::Sustained),
+                "line too noisy to read valid data",
+            ),
+            (SerialError::Break, "break condition received"),
+            (SerialError::FlowControl, "flow control error"),
+            (SerialError::BufferFull, "receive buffer full"),
+            (
+                SerialError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(
+            retryable,
+            [
+                &SerialError::Overrun,
+                &SerialError::Parity,
+                &SerialError::Noise(NoiseKind::Glitch),
+                &SerialError::BufferFull
+            ]
+        );
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(
+            codes,
+            [0x0401, 0x0402, 0x0403, 0x0404, 0x0408, 0x0405, 0x0406, 0x0407, 0x0001,]
+        );
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Recoverable,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "serial");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(
+            millis,
+            [
+                Some(1),
+                None,
+                Some(1),
+                Some(1),
+                None,
+                None,
+                None,
+                Some(1),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::EOVERFLOW,
+                -errno::EPROTO,
+                -errno::EPROTO,
+                -errno::EIO,
+                -errno::EIO,
+                -errno::EIO,
+                -errno::EIO,
+                -errno::ENOBUFS,
+                -errno::EIO,
+            ]
+        );
+    }
+
+    #[test]
+    fn noise_kinds() {
+        let glitch = SerialError::Noise(NoiseKind::Glitch);
+        assert_ne!(glitch, SerialError::Noise(NoiseKind::Sustained));
+        assert_eq!(SerialError::from_code(0x0404), Some(glitch));
+    }
+}
@@ -0,0 +1,276 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// A servo specific error.
+///
+/// This error type contains errors specific to PWM driven servo peripherals. Also it has an `Impl`
+/// kind to pass through implementation specific errors occurring while trying to use a servo
+/// peripheral.
+///
+/// Unlike `PwmError` these kinds model faults of the actuator on top of the PWM peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum ServoError {
+    /// The requested angle is outside of the range supported by the servo
+    AngleOutOfRange,
+    /// The requested pulse width is outside of the range supported by the servo
+    PulseWidthOutOfRange,
+    /// The servo is not attached to a PWM channel
+    NotAttached,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl ServoError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `Impl` errors are retryable if the wrapped [`ImplError`] is. All other kinds are hard
+    /// faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ServoError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`], the servo specific kinds are not
+    /// retryable and return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            ServoError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`ServoError::Impl`] and `false` for the servo specific kinds, e.g.
+    /// [`ServoError::AngleOutOfRange`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, ServoError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`ServoError::Impl`] with `f`.
+    ///
+    /// The servo specific kinds, e.g. [`ServoError::AngleOutOfRange`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            ServoError::Impl(e) => ServoError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// Servo errors use the high byte `0x2b` and the index of the error kind as low byte, e.g.
+    /// `0x2b01` for `AngleOutOfRange`. See the [crate level documentation](crate#error-codes) for
+    /// details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            ServoError::AngleOutOfRange => 0x2b01,
+            ServoError::PulseWidthOutOfRange => 0x2b02,
+            ServoError::NotAttached => 0x2b03,
+            ServoError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`ServoError::to_code`], e.g. `0x2b01` to
+    /// [`ServoError::AngleOutOfRange`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the servo error kinds nor to
+    /// an [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x2b01 => Some(ServoError::AngleOutOfRange),
+            0x2b02 => Some(ServoError::PulseWidthOutOfRange),
+            0x2b03 => Some(ServoError::NotAttached),
+            0x0000..=0x00ff => ImplError::from_code(code).map(ServoError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. All other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            ServoError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"servo"`, the stable lowercase name of the servo peripheral, for all kinds
+    /// including `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "servo"
+    }
+
+    /// Returns the name of the error kind, e.g. `"AngleOutOfRange"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            ServoError::AngleOutOfRange => "AngleOutOfRange",
+            ServoError::PulseWidthOutOfRange => "PulseWidthOutOfRange",
+            ServoError::NotAttached => "NotAttached",
+            ServoError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `NotAttached` maps to
+    /// `-ENODEV`. `AngleOutOfRange` and `PulseWidthOutOfRange` map to `-ERANGE`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            ServoError::Impl(e) => e.to_errno(),
+            ServoError::NotAttached => -errno::ENODEV,
+            ServoError::AngleOutOfRange | ServoError::PulseWidthOutOfRange => -errno::ERANGE,
+        }
+    }
+}
+
+impl fmt::Display for ServoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServoError::AngleOutOfRange => f.write_str("angle out of range"),
+            ServoError::PulseWidthOutOfRange => f.write_str("pulse width out of range"),
+            ServoError::NotAttached => f.write_str("servo not attached"),
+            ServoError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for ServoError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            ServoError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(ServoError, impl_error);
+
+impl PeripheralError for ServoError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a ServoError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a ServoError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[ServoError] = &[
+        ServoError::AngleOutOfRange,
+        ServoError::PulseWidthOutOfRange,
+        ServoError::NotAttached,
+        ServoError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(ServoError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (ServoError::AngleOutOfRange, "angle out of range"),
+            (ServoError::PulseWidthOutOfRange, "pulse width out of range"),
+            (ServoError::NotAttached, "servo not attached"),
+            (
+                ServoError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        assert!(!KINDS.iter().any(|e| e.is_retryable()));
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(codes, [0x2b01, 0x2b02, 0x2b03, 0x0001,]);
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "servo");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(millis, [None, None, None, None,]);
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [-errno::ERANGE, -errno::ERANGE, -errno::ENODEV, -errno::EIO,]
+        );
+    }
+}
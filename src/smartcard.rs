@@ -0,0 +1,303 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// A smart card specific error.
+///
+/// This error type contains errors specific to smart card (ISO 7816) peripherals. Also it has an
+/// `Impl` kind to pass through implementation specific errors occurring while trying to use a smart
+/// card peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum SmartCardError {
+    /// No card is inserted
+    NoCard,
+    /// The card did not answer, e.g. no answer to reset was received
+    Mute,
+    /// A character was received with a parity error and its repetition failed
+    ParityError,
+    /// The card violated the transmission protocol, e.g. with an invalid procedure byte
+    ProtocolError,
+    /// The contacts of the card could not be deactivated properly
+    DeactivationFailed,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl SmartCardError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `ParityError` is transient and thus retryable. `Impl` errors are retryable if the wrapped
+    /// [`ImplError`] is. All other kinds are hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            SmartCardError::ParityError => true,
+            SmartCardError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`]. `ParityError` suggests 1 ms, all
+    /// other kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            SmartCardError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`SmartCardError::Impl`] and `false` for the smart card specific kinds,
+    /// e.g. [`SmartCardError::NoCard`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, SmartCardError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`SmartCardError::Impl`] with `f`.
+    ///
+    /// The smart card specific kinds, e.g. [`SmartCardError::NoCard`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            SmartCardError::Impl(e) => SmartCardError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// Smart card errors use the high byte `0x1c` and the index of the error kind as low byte, e.g.
+    /// `0x1c01` for `NoCard`. See the [crate level documentation](crate#error-codes) for details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            SmartCardError::NoCard => 0x1c01,
+            SmartCardError::Mute => 0x1c02,
+            SmartCardError::ParityError => 0x1c03,
+            SmartCardError::ProtocolError => 0x1c04,
+            SmartCardError::DeactivationFailed => 0x1c05,
+            SmartCardError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`SmartCardError::to_code`], e.g. `0x1c01` to
+    /// [`SmartCardError::NoCard`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the smart card error kinds nor
+    /// to an [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x1c01 => Some(SmartCardError::NoCard),
+            0x1c02 => Some(SmartCardError::Mute),
+            0x1c03 => Some(SmartCardError::ParityError),
+            0x1c04 => Some(SmartCardError::ProtocolError),
+            0x1c05 => Some(SmartCardError::DeactivationFailed),
+            0x0000..=0x00ff => ImplError::from_code(code).map(SmartCardError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. All other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            SmartCardError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"smartcard"`, the stable lowercase name of the smart card peripheral, for all kinds
+    /// including `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "smartcard"
+    }
+
+    /// Returns the name of the error kind, e.g. `"NoCard"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            SmartCardError::NoCard => "NoCard",
+            SmartCardError::Mute => "Mute",
+            SmartCardError::ParityError => "ParityError",
+            SmartCardError::ProtocolError => "ProtocolError",
+            SmartCardError::DeactivationFailed => "DeactivationFailed",
+            SmartCardError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `ParityError` and
+    /// `ProtocolError` map to `-EPROTO`. `Mute` maps to `-ETIMEDOUT`. `NoCard` maps to
+    /// `-ENOMEDIUM`. All other kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            SmartCardError::Impl(e) => e.to_errno(),
+            SmartCardError::ParityError | SmartCardError::ProtocolError => -errno::EPROTO,
+            SmartCardError::Mute => -errno::ETIMEDOUT,
+            SmartCardError::NoCard => -errno::ENOMEDIUM,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for SmartCardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SmartCardError::NoCard => f.write_str("no card inserted"),
+            SmartCardError::Mute => f.write_str("card is mute"),
+            SmartCardError::ParityError => f.write_str("parity error"),
+            SmartCardError::ProtocolError => f.write_str("protocol error"),
+            SmartCardError::DeactivationFailed => f.write_str("deactivation failed"),
+            SmartCardError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for SmartCardError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            SmartCardError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(SmartCardError, impl_error);
+
+impl PeripheralError for SmartCardError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a SmartCardError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a SmartCardError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[SmartCardError] = &[
+        SmartCardError::NoCard,
+        SmartCardError::Mute,
+        SmartCardError::ParityError,
+        SmartCardError::ProtocolError,
+        SmartCardError::DeactivationFailed,
+        SmartCardError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(SmartCardError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (SmartCardError::NoCard, "no card inserted"),
+            (SmartCardError::Mute, "card is mute"),
+            (SmartCardError::ParityError, "parity error"),
+            (SmartCardError::ProtocolError, "protocol error"),
+            (SmartCardError::DeactivationFailed, "deactivation failed"),
+            (
+                SmartCardError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(retryable, [&SmartCardError::ParityError]);
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(codes, [0x1c01, 0x1c02, 0x1c03, 0x1c04, 0x1c05, 0x0001,]);
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "smartcard");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(millis, [None, None, Some(1), None, None, None,]);
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::ENOMEDIUM,
+                -errno::ETIMEDOUT,
+                -errno::EPROTO,
+                -errno::EPROTO,
+                -errno::EIO,
+                -errno::EIO,
+            ]
+        );
+    }
+}
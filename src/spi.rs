@@ -0,0 +1,363 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// A SPI specific error.
+///
+/// This error type contains errors specific to SPI peripherals. Also it has an `Impl` kind to pass
+/// through implementation specific errors occuring while trying to use a SPI peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum SpiError {
+    /// The peripheral receive buffer was overrun
+    Overrun,
+    /// Multiple devices on the SPI bus are trying across each other, e.g. in a multi-master setup
+    ModeFault,
+    /// CRC does not match the received data
+    CRCError,
+    /// Received data does not conform to the peripheral configuration
+    FrameFormat,
+    /// Asserting or deasserting the chip select line failed, e.g. on a shared bus
+    ChipSelectError,
+    /// The transfer did not complete in time
+    Timeout,
+    /// The read and write buffers of a bidirectional transfer differ in length
+    TransferSizeMismatch {
+        /// Number of words expected for the transfer
+        expected: usize,
+        /// Number of words actually provided
+        actual: usize,
+    },
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl SpiError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `Overrun`, `CRCError` and `Timeout` are transient and thus retryable. `Impl` errors are
+    /// retryable if the wrapped [`ImplError`] is. All other kinds are hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            SpiError::Overrun | SpiError::CRCError | SpiError::Timeout => true,
+            SpiError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`]. `Overrun`, `CRCError` and
+    /// `Timeout` suggest 1 ms, all other kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            SpiError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`SpiError::Impl`] and `false` for the SPI specific kinds, e.g.
+    /// [`SpiError::Overrun`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, SpiError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`SpiError::Impl`] with `f`.
+    ///
+    /// The SPI specific kinds, e.g. [`SpiError::Overrun`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            SpiError::Impl(e) => SpiError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// SPI errors use the high byte `0x03` and the index of the error kind as low byte, e.g.
+    /// `0x0301` for `Overrun`. See the [crate level documentation](crate#error-codes) for details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            SpiError::Overrun => 0x0301,
+            SpiError::ModeFault => 0x0302,
+            SpiError::CRCError => 0x0303,
+            SpiError::FrameFormat => 0x0304,
+            SpiError::ChipSelectError => 0x0305,
+            SpiError::Timeout => 0x0306,
+            SpiError::TransferSizeMismatch { .. } => 0x0307,
+            SpiError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`SpiError::to_code`], e.g. `0x0301` to [`SpiError::Overrun`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the SPI error kinds nor to an
+    /// [`ImplError`]. The sizes of `TransferSizeMismatch` are not encoded in the code and thus
+    /// restored as `0`.
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x0301 => Some(SpiError::Overrun),
+            0x0302 => Some(SpiError::ModeFault),
+            0x0303 => Some(SpiError::CRCError),
+            0x0304 => Some(SpiError::FrameFormat),
+            0x0305 => Some(SpiError::ChipSelectError),
+            0x0306 => Some(SpiError::Timeout),
+            0x0307 => Some(SpiError::TransferSizeMismatch {
+                expected: 0,
+                actual: 0,
+            }),
+            0x0000..=0x00ff => ImplError::from_code(code).map(SpiError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. `TransferSizeMismatch` is [`Severity::Fatal`]. All
+    /// other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            SpiError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            SpiError::TransferSizeMismatch { .. } => Severity::Fatal,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"spi"`, the stable lowercase name of the SPI peripheral, for all kinds including
+    /// `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "spi"
+    }
+
+    /// Returns the name of the error kind, e.g. `"Overrun"`.
+    ///
+    /// The data carried by `TransferSizeMismatch` is not included. `Impl` errors return `"Impl"`
+    /// instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            SpiError::Overrun => "Overrun",
+            SpiError::ModeFault => "ModeFault",
+            SpiError::CRCError => "CRCError",
+            SpiError::FrameFormat => "FrameFormat",
+            SpiError::ChipSelectError => "ChipSelectError",
+            SpiError::Timeout => "Timeout",
+            SpiError::TransferSizeMismatch { .. } => "TransferSizeMismatch",
+            SpiError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `TransferSizeMismatch` maps to
+    /// `-EINVAL`. `FrameFormat` maps to `-EPROTO`. `Overrun` maps to `-EOVERFLOW`. `CRCError` maps
+    /// to `-EILSEQ`. `Timeout` maps to `-ETIMEDOUT`. All other kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            SpiError::Impl(e) => e.to_errno(),
+            SpiError::TransferSizeMismatch { .. } => -errno::EINVAL,
+            SpiError::FrameFormat => -errno::EPROTO,
+            SpiError::Overrun => -errno::EOVERFLOW,
+            SpiError::CRCError => -errno::EILSEQ,
+            SpiError::Timeout => -errno::ETIMEDOUT,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for SpiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpiError::Overrun => f.write_str("receive buffer overrun"),
+            SpiError::ModeFault => f.write_str("mode fault, multiple devices driving the bus"),
+            SpiError::CRCError => f.write_str("CRC does not match the received data"),
+            SpiError::FrameFormat => f.write_str("received data does not match the frame format"),
+            SpiError::ChipSelectError => f.write_str("chip select error"),
+            SpiError::Timeout => f.write_str("transfer timed out"),
+            SpiError::TransferSizeMismatch { expected, actual } => write!(
+                f,
+                "transfer size mismatch, expected {} words but got {}",
+                expected, actual
+            ),
+            SpiError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for SpiError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            SpiError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(SpiError, impl_error);
+
+impl PeripheralError for SpiError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a SpiError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a SpiError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[SpiError] = &[
+        SpiError::Overrun,
+        SpiError::ModeFault,
+        SpiError::CRCError,
+        SpiError::FrameFormat,
+        SpiError::ChipSelectError,
+        SpiError::Timeout,
+        SpiError::TransferSizeMismatch {
+            expected: 0,
+            actual: 0,
+        },
+        SpiError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(SpiError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (SpiError::Overrun, "receive buffer overrun"),
+            (
+                SpiError::ModeFault,
+                "mode fault, multiple devices driving the bus",
+            ),
+            (SpiError::CRCError, "CRC does not match the received data"),
+            (
+                SpiError::FrameFormat,
+                "received data does not match the frame format",
+            ),
+            (SpiError::ChipSelectError, "chip select error"),
+            (SpiError::Timeout, "transfer timed out"),
+            (
+                SpiError::TransferSizeMismatch {
+                    expected: 4,
+                    actual: 2,
+                },
+                "transfer size mismatch, expected 4 words but got 2",
+            ),
+            (
+                SpiError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(
+            retryable,
+            [&SpiError::Overrun, &SpiError::CRCError, &SpiError::Timeout]
+        );
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(
+            codes,
+            [0x0301, 0x0302, 0x0303, 0x0304, 0x0305, 0x0306, 0x0307, 0x0001,]
+        );
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Recoverable,
+                Severity::Fatal,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "spi");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(
+            millis,
+            [Some(1), None, Some(1), None, None, Some(1), None, None,]
+        );
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::EOVERFLOW,
+                -errno::EIO,
+                -errno::EILSEQ,
+                -errno::EPROTO,
+                -errno::EIO,
+                -errno::ETIMEDOUT,
+                -errno::EINVAL,
+                -errno::EIO,
+            ]
+        );
+    }
+}
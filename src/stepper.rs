@@ -0,0 +1,302 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// A stepper motor specific error.
+///
+/// This error type contains errors specific to stepper motor driver peripherals. Also it has an
+/// `Impl` kind to pass through implementation specific errors occurring while trying to use a
+/// stepper motor peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum StepperError {
+    /// The driver shut down due to overheating
+    OverTemperature,
+    /// The driver detected an overcurrent or short circuit on a motor coil
+    OverCurrent,
+    /// A motor coil is not connected
+    OpenLoad,
+    /// The motor stalled and lost steps
+    StallDetected,
+    /// The requested step rate exceeds what the driver or motor supports
+    StepTooFast,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl StepperError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `Impl` errors are retryable if the wrapped [`ImplError`] is. All other kinds are hard
+    /// faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            StepperError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`], the stepper motor specific kinds
+    /// are not retryable and return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            StepperError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`StepperError::Impl`] and `false` for the stepper motor specific kinds,
+    /// e.g. [`StepperError::OverTemperature`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, StepperError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`StepperError::Impl`] with `f`.
+    ///
+    /// The stepper motor specific kinds, e.g. [`StepperError::OverTemperature`], are returned
+    /// unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            StepperError::Impl(e) => StepperError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// Stepper motor errors use the high byte `0x2a` and the index of the error kind as low byte,
+    /// e.g. `0x2a01` for `OverTemperature`. See the [crate level documentation](crate#error-codes)
+    /// for details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            StepperError::OverTemperature => 0x2a01,
+            StepperError::OverCurrent => 0x2a02,
+            StepperError::OpenLoad => 0x2a03,
+            StepperError::StallDetected => 0x2a04,
+            StepperError::StepTooFast => 0x2a05,
+            StepperError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`StepperError::to_code`], e.g. `0x2a01` to
+    /// [`StepperError::OverTemperature`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the stepper motor error kinds
+    /// nor to an [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x2a01 => Some(StepperError::OverTemperature),
+            0x2a02 => Some(StepperError::OverCurrent),
+            0x2a03 => Some(StepperError::OpenLoad),
+            0x2a04 => Some(StepperError::StallDetected),
+            0x2a05 => Some(StepperError::StepTooFast),
+            0x0000..=0x00ff => ImplError::from_code(code).map(StepperError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. `OpenLoad` is [`Severity::Fatal`]. All other kinds are
+    /// [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            StepperError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            StepperError::OpenLoad => Severity::Fatal,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"stepper"`, the stable lowercase name of the stepper motor peripheral, for all
+    /// kinds including `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "stepper"
+    }
+
+    /// Returns the name of the error kind, e.g. `"OverTemperature"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            StepperError::OverTemperature => "OverTemperature",
+            StepperError::OverCurrent => "OverCurrent",
+            StepperError::OpenLoad => "OpenLoad",
+            StepperError::StallDetected => "StallDetected",
+            StepperError::StepTooFast => "StepTooFast",
+            StepperError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `StepTooFast` maps to
+    /// `-ERANGE`. All other kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            StepperError::Impl(e) => e.to_errno(),
+            StepperError::StepTooFast => -errno::ERANGE,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for StepperError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StepperError::OverTemperature => f.write_str("driver over temperature"),
+            StepperError::OverCurrent => f.write_str("coil overcurrent"),
+            StepperError::OpenLoad => f.write_str("open load"),
+            StepperError::StallDetected => f.write_str("motor stalled"),
+            StepperError::StepTooFast => f.write_str("step rate too high"),
+            StepperError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for StepperError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            StepperError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(StepperError, impl_error);
+
+impl PeripheralError for StepperError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a StepperError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a StepperError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[StepperError] = &[
+        StepperError::OverTemperature,
+        StepperError::OverCurrent,
+        StepperError::OpenLoad,
+        StepperError::StallDetected,
+        StepperError::StepTooFast,
+        StepperError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(StepperError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (StepperError::OverTemperature, "driver over temperature"),
+            (StepperError::OverCurrent, "coil overcurrent"),
+            (StepperError::OpenLoad, "open load"),
+            (StepperError::StallDetected, "motor stalled"),
+            (StepperError::StepTooFast, "step rate too high"),
+            (
+                StepperError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        assert!(!KINDS.iter().any(|e| e.is_retryable()));
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(codes, [0x2a01, 0x2a02, 0x2a03, 0x2a04, 0x2a05, 0x0001,]);
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Fatal,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "stepper");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(millis, [None, None, None, None, None, None,]);
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::EIO,
+                -errno::EIO,
+                -errno::EIO,
+                -errno::EIO,
+                -errno::ERANGE,
+                -errno::EIO,
+            ]
+        );
+    }
+}
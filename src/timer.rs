@@ -0,0 +1,336 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// A Timer specific error.
+///
+/// This error type contains errors specific to Timer/Counter peripherals. Also it has an `Impl`
+/// kind to pass through implementation specific errors occurring while trying to use a Timer
+/// peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum TimerError {
+    /// The timer is already running and cannot be reconfigured or started again
+    AlreadyRunning,
+    /// The operation requires the timer to be running
+    NotRunning,
+    /// The requested period cannot be achieved with the timer clock and counter width
+    PeriodOutOfRange,
+    /// The requested prescaler value is not supported
+    PrescalerOutOfRange,
+    /// The requested capture/compare channel does not exist or is not available
+    ChannelUnavailable,
+    /// A new capture occurred before the previous capture value was read
+    CaptureOverrun,
+    /// The requested trigger source cannot be connected to this timer
+    TriggerSourceUnavailable,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl TimerError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `CaptureOverrun` is transient and thus retryable. `Impl` errors are retryable if the wrapped
+    /// [`ImplError`] is. All other kinds are hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            TimerError::CaptureOverrun => true,
+            TimerError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`]. `CaptureOverrun` suggests 1 ms,
+    /// all other kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            TimerError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`TimerError::Impl`] and `false` for the timer specific kinds, e.g.
+    /// [`TimerError::AlreadyRunning`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, TimerError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`TimerError::Impl`] with `f`.
+    ///
+    /// The timer specific kinds, e.g. [`TimerError::AlreadyRunning`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            TimerError::Impl(e) => TimerError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// Timer errors use the high byte `0x0b` and the index of the error kind as low byte, e.g.
+    /// `0x0b01` for `AlreadyRunning`. See the [crate level documentation](crate#error-codes) for
+    /// details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            TimerError::AlreadyRunning => 0x0b01,
+            TimerError::NotRunning => 0x0b02,
+            TimerError::PeriodOutOfRange => 0x0b03,
+            TimerError::PrescalerOutOfRange => 0x0b04,
+            TimerError::ChannelUnavailable => 0x0b05,
+            TimerError::CaptureOverrun => 0x0b06,
+            TimerError::TriggerSourceUnavailable => 0x0b07,
+            TimerError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`TimerError::to_code`], e.g. `0x0b01` to
+    /// [`TimerError::AlreadyRunning`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the timer error kinds nor to
+    /// an [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x0b01 => Some(TimerError::AlreadyRunning),
+            0x0b02 => Some(TimerError::NotRunning),
+            0x0b03 => Some(TimerError::PeriodOutOfRange),
+            0x0b04 => Some(TimerError::PrescalerOutOfRange),
+            0x0b05 => Some(TimerError::ChannelUnavailable),
+            0x0b06 => Some(TimerError::CaptureOverrun),
+            0x0b07 => Some(TimerError::TriggerSourceUnavailable),
+            0x0000..=0x00ff => ImplError::from_code(code).map(TimerError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. `ChannelUnavailable` and `TriggerSourceUnavailable` are
+    /// [`Severity::Fatal`]. All other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            TimerError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            TimerError::ChannelUnavailable | TimerError::TriggerSourceUnavailable => {
+                Severity::Fatal
+            }
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"timer"`, the stable lowercase name of the timer peripheral, for all kinds
+    /// including `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "timer"
+    }
+
+    /// Returns the name of the error kind, e.g. `"AlreadyRunning"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            TimerError::AlreadyRunning => "AlreadyRunning",
+            TimerError::NotRunning => "NotRunning",
+            TimerError::PeriodOutOfRange => "PeriodOutOfRange",
+            TimerError::PrescalerOutOfRange => "PrescalerOutOfRange",
+            TimerError::ChannelUnavailable => "ChannelUnavailable",
+            TimerError::CaptureOverrun => "CaptureOverrun",
+            TimerError::TriggerSourceUnavailable => "TriggerSourceUnavailable",
+            TimerError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `AlreadyRunning` maps to
+    /// `-EBUSY`. `ChannelUnavailable` and `TriggerSourceUnavailable` map to `-ENODEV`.
+    /// `PeriodOutOfRange` and `PrescalerOutOfRange` map to `-ERANGE`. `CaptureOverrun` maps to
+    /// `-EOVERFLOW`. All other kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            TimerError::Impl(e) => e.to_errno(),
+            TimerError::AlreadyRunning => -errno::EBUSY,
+            TimerError::ChannelUnavailable | TimerError::TriggerSourceUnavailable => -errno::ENODEV,
+            TimerError::PeriodOutOfRange | TimerError::PrescalerOutOfRange => -errno::ERANGE,
+            TimerError::CaptureOverrun => -errno::EOVERFLOW,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for TimerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimerError::AlreadyRunning => f.write_str("timer already running"),
+            TimerError::NotRunning => f.write_str("timer not running"),
+            TimerError::PeriodOutOfRange => f.write_str("period out of range"),
+            TimerError::PrescalerOutOfRange => f.write_str("prescaler out of range"),
+            TimerError::ChannelUnavailable => f.write_str("channel unavailable"),
+            TimerError::CaptureOverrun => f.write_str("capture overrun"),
+            TimerError::TriggerSourceUnavailable => f.write_str("trigger source unavailable"),
+            TimerError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for TimerError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            TimerError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(TimerError, impl_error);
+
+impl PeripheralError for TimerError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a TimerError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a TimerError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[TimerError] = &[
+        TimerError::AlreadyRunning,
+        TimerError::NotRunning,
+        TimerError::PeriodOutOfRange,
+        TimerError::PrescalerOutOfRange,
+        TimerError::ChannelUnavailable,
+        TimerError::CaptureOverrun,
+        TimerError::TriggerSourceUnavailable,
+        TimerError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(TimerError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (TimerError::AlreadyRunning, "timer already running"),
+            (TimerError::NotRunning, "timer not running"),
+            (TimerError::PeriodOutOfRange, "period out of range"),
+            (TimerError::PrescalerOutOfRange, "prescaler out of range"),
+            (TimerError::ChannelUnavailable, "channel unavailable"),
+            (TimerError::CaptureOverrun, "capture overrun"),
+            (
+                TimerError::TriggerSourceUnavailable,
+                "trigger source unavailable",
+            ),
+            (
+                TimerError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(retryable, [&TimerError::CaptureOverrun]);
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(
+            codes,
+            [0x0b01, 0x0b02, 0x0b03, 0x0b04, 0x0b05, 0x0b06, 0x0b07, 0x0001,]
+        );
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Fatal,
+                Severity::Recoverable,
+                Severity::Fatal,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "timer");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(millis, [None, None, None, None, None, Some(1), None, None,]);
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::EBUSY,
+                -errno::EIO,
+                -errno::ERANGE,
+                -errno::ERANGE,
+                -errno::ENODEV,
+                -errno::EOVERFLOW,
+                -errno::ENODEV,
+                -errno::EIO,
+            ]
+        );
+    }
+}
@@ -0,0 +1,309 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// A touch sensing specific error.
+///
+/// This error type contains errors specific to touch sensing controller peripherals. Also it has an
+/// `Impl` kind to pass through implementation specific errors occurring while trying to use a touch
+/// sensing peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum TouchError {
+    /// An acquisition did not complete in time
+    AcquisitionTimeout,
+    /// The controller could not determine a stable reference
+    CalibrationFailed,
+    /// The counter reached its maximum value before the electrode was charged
+    MaxCountReached,
+    /// The requested channel does not exist or is not available
+    ChannelUnavailable,
+    /// The measurement was disturbed by noise
+    NoiseDetected,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl TouchError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `AcquisitionTimeout` and `NoiseDetected` are transient and thus retryable. `Impl` errors are
+    /// retryable if the wrapped [`ImplError`] is. All other kinds are hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            TouchError::AcquisitionTimeout | TouchError::NoiseDetected => true,
+            TouchError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`]. `AcquisitionTimeout` and
+    /// `NoiseDetected` suggest 1 ms, all other kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            TouchError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`TouchError::Impl`] and `false` for the touch specific kinds, e.g.
+    /// [`TouchError::AcquisitionTimeout`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, TouchError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`TouchError::Impl`] with `f`.
+    ///
+    /// The touch specific kinds, e.g. [`TouchError::AcquisitionTimeout`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            TouchError::Impl(e) => TouchError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// Touch errors use the high byte `0x1a` and the index of the error kind as low byte, e.g.
+    /// `0x1a01` for `AcquisitionTimeout`. See the [crate level documentation](crate#error-codes)
+    /// for details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            TouchError::AcquisitionTimeout => 0x1a01,
+            TouchError::CalibrationFailed => 0x1a02,
+            TouchError::MaxCountReached => 0x1a03,
+            TouchError::ChannelUnavailable => 0x1a04,
+            TouchError::NoiseDetected => 0x1a05,
+            TouchError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`TouchError::to_code`], e.g. `0x1a01` to
+    /// [`TouchError::AcquisitionTimeout`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the touch error kinds nor to
+    /// an [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x1a01 => Some(TouchError::AcquisitionTimeout),
+            0x1a02 => Some(TouchError::CalibrationFailed),
+            0x1a03 => Some(TouchError::MaxCountReached),
+            0x1a04 => Some(TouchError::ChannelUnavailable),
+            0x1a05 => Some(TouchError::NoiseDetected),
+            0x0000..=0x00ff => ImplError::from_code(code).map(TouchError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. `ChannelUnavailable` is [`Severity::Fatal`]. All other
+    /// kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            TouchError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            TouchError::ChannelUnavailable => Severity::Fatal,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"touch"`, the stable lowercase name of the touch peripheral, for all kinds
+    /// including `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "touch"
+    }
+
+    /// Returns the name of the error kind, e.g. `"AcquisitionTimeout"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            TouchError::AcquisitionTimeout => "AcquisitionTimeout",
+            TouchError::CalibrationFailed => "CalibrationFailed",
+            TouchError::MaxCountReached => "MaxCountReached",
+            TouchError::ChannelUnavailable => "ChannelUnavailable",
+            TouchError::NoiseDetected => "NoiseDetected",
+            TouchError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `ChannelUnavailable` maps to
+    /// `-ENODEV`. `MaxCountReached` maps to `-EOVERFLOW`. `AcquisitionTimeout` maps to
+    /// `-ETIMEDOUT`. All other kinds map to `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            TouchError::Impl(e) => e.to_errno(),
+            TouchError::ChannelUnavailable => -errno::ENODEV,
+            TouchError::MaxCountReached => -errno::EOVERFLOW,
+            TouchError::AcquisitionTimeout => -errno::ETIMEDOUT,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for TouchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TouchError::AcquisitionTimeout => f.write_str("acquisition timeout"),
+            TouchError::CalibrationFailed => f.write_str("calibration failed"),
+            TouchError::MaxCountReached => f.write_str("max count reached"),
+            TouchError::ChannelUnavailable => f.write_str("channel unavailable"),
+            TouchError::NoiseDetected => f.write_str("noise detected"),
+            TouchError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for TouchError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            TouchError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(TouchError, impl_error);
+
+impl PeripheralError for TouchError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a TouchError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a TouchError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[TouchError] = &[
+        TouchError::AcquisitionTimeout,
+        TouchError::CalibrationFailed,
+        TouchError::MaxCountReached,
+        TouchError::ChannelUnavailable,
+        TouchError::NoiseDetected,
+        TouchError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(TouchError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (TouchError::AcquisitionTimeout, "acquisition timeout"),
+            (TouchError::CalibrationFailed, "calibration failed"),
+            (TouchError::MaxCountReached, "max count reached"),
+            (TouchError::ChannelUnavailable, "channel unavailable"),
+            (TouchError::NoiseDetected, "noise detected"),
+            (
+                TouchError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(
+            retryable,
+            [&TouchError::AcquisitionTimeout, &TouchError::NoiseDetected]
+        );
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(codes, [0x1a01, 0x1a02, 0x1a03, 0x1a04, 0x1a05, 0x0001,]);
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Fatal,
+                Severity::Recoverable,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "touch");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(millis, [Some(1), None, None, None, Some(1), None,]);
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::ETIMEDOUT,
+                -errno::EIO,
+                -errno::EOVERFLOW,
+                -errno::ENODEV,
+                -errno::EIO,
+                -errno::EIO,
+            ]
+        );
+    }
+}
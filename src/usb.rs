@@ -0,0 +1,478 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// A USB specific error.
+///
+/// This error type contains errors specific to USB peripherals. Also it has an `Impl` kind to pass
+/// through implementation specific errors occuring while trying to use a USB peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum UsbError {
+    /// An operation would block because the device is currently busy or there is no data available.
+    WouldBlock,
+    /// Parsing failed due to invalid input.
+    ParseError,
+    /// A buffer too short for the data to read was passed, or provided data cannot fit within
+    /// length constraints.
+    BufferOverflow {
+        /// Number of bytes required
+        needed: usize,
+        /// Number of bytes available
+        available: usize,
+    },
+    /// Classes attempted to allocate more endpoints than the peripheral supports.
+    EndpointOverflow,
+    /// Classes attempted to allocate more packet buffer memory than the peripheral supports. This
+    /// can be caused by either a single class trying to allocate a packet buffer larger than the
+    /// peripheral supports per endpoint, or multiple allocated endpoints together using more memory
+    /// than the peripheral has available for the buffers.
+    EndpointMemoryOverflow,
+    /// The endpoint address is invalid or already used.
+    InvalidEndpoint {
+        /// The offending endpoint address
+        address: u8,
+    },
+    /// Operation is not supported by device or configuration.
+    Unsupported,
+    /// Operation is not valid in the current state of the object.
+    InvalidState,
+    /// The endpoint is halted and responded with a STALL handshake.
+    Stalled,
+    /// The host signalled a bus reset, pending transfers were aborted.
+    BusReset,
+    /// The bus is suspended, the host has to resume it before any transfer can take place.
+    Suspended,
+    /// The data toggle (DATA0/DATA1) of a received packet did not match the expected value.
+    DataToggleError,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl UsbError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `WouldBlock` and `DataToggleError` are transient and thus retryable. `Impl` errors are
+    /// retryable if the wrapped [`ImplError`] is. All other kinds are hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            UsbError::WouldBlock | UsbError::DataToggleError => true,
+            UsbError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`]. `WouldBlock` and
+    /// `DataToggleError` suggest 1 ms, all other kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            UsbError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`UsbError::Impl`] and `false` for the USB specific kinds, e.g.
+    /// [`UsbError::WouldBlock`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, UsbError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`UsbError::Impl`] with `f`.
+    ///
+    /// The USB specific kinds, e.g. [`UsbError::WouldBlock`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            UsbError::Impl(e) => UsbError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// USB errors use the high byte `0x02` and the index of the error kind as low byte, e.g.
+    /// `0x0201` for `WouldBlock`. See the [crate level documentation](crate#error-codes) for
+    /// details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            UsbError::WouldBlock => 0x0201,
+            UsbError::ParseError => 0x0202,
+            UsbError::BufferOverflow { .. } => 0x0203,
+            UsbError::EndpointOverflow => 0x0204,
+            UsbError::EndpointMemoryOverflow => 0x0205,
+            UsbError::InvalidEndpoint { .. } => 0x0206,
+            UsbError::Unsupported => 0x0207,
+            UsbError::InvalidState => 0x0208,
+            UsbError::Stalled => 0x0209,
+            UsbError::BusReset => 0x020a,
+            UsbError::Suspended => 0x020b,
+            UsbError::DataToggleError => 0x020c,
+            UsbError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`UsbError::to_code`], e.g. `0x0201` to [`UsbError::WouldBlock`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the USB error kinds nor to an
+    /// [`ImplError`]. The sizes of `BufferOverflow` and the address of `InvalidEndpoint` are not
+    /// encoded in the code and thus restored as `0`.
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x0201 => Some(UsbError::WouldBlock),
+            0x0202 => Some(UsbError::ParseError),
+            0x0203 => Some(UsbError::BufferOverflow {
+                needed: 0,
+                available: 0,
+            }),
+            0x0204 => Some(UsbError::EndpointOverflow),
+            0x0205 => Some(UsbError::EndpointMemoryOverflow),
+            0x0206 => Some(UsbError::InvalidEndpoint { address: 0 }),
+            0x0207 => Some(UsbError::Unsupported),
+            0x0208 => Some(UsbError::InvalidState),
+            0x0209 => Some(UsbError::Stalled),
+            0x020a => Some(UsbError::BusReset),
+            0x020b => Some(UsbError::Suspended),
+            0x020c => Some(UsbError::DataToggleError),
+            0x0000..=0x00ff => ImplError::from_code(code).map(UsbError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. `EndpointOverflow`, `EndpointMemoryOverflow` and
+    /// `Unsupported` are [`Severity::Fatal`]. All other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            UsbError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            UsbError::EndpointOverflow
+            | UsbError::EndpointMemoryOverflow
+            | UsbError::Unsupported => Severity::Fatal,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"usb"`, the stable lowercase name of the USB peripheral, for all kinds including
+    /// `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "usb"
+    }
+
+    /// Returns the name of the error kind, e.g. `"WouldBlock"`.
+    ///
+    /// The data carried by `BufferOverflow` and `InvalidEndpoint` is not included. `Impl` errors
+    /// return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            UsbError::WouldBlock => "WouldBlock",
+            UsbError::ParseError => "ParseError",
+            UsbError::BufferOverflow { .. } => "BufferOverflow",
+            UsbError::EndpointOverflow => "EndpointOverflow",
+            UsbError::EndpointMemoryOverflow => "EndpointMemoryOverflow",
+            UsbError::InvalidEndpoint { .. } => "InvalidEndpoint",
+            UsbError::Unsupported => "Unsupported",
+            UsbError::InvalidState => "InvalidState",
+            UsbError::Stalled => "Stalled",
+            UsbError::BusReset => "BusReset",
+            UsbError::Suspended => "Suspended",
+            UsbError::DataToggleError => "DataToggleError",
+            UsbError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `WouldBlock` maps to `-EAGAIN`.
+    /// `InvalidEndpoint` and `InvalidState` map to `-EINVAL`. `Stalled` maps to `-EPIPE`.
+    /// `ParseError` maps to `-EBADMSG`. `DataToggleError` maps to `-EILSEQ`. `Unsupported` maps to
+    /// `-EOPNOTSUPP`. `BusReset` maps to `-ECONNRESET`. `BufferOverflow`, `EndpointOverflow` and
+    /// `EndpointMemoryOverflow` map to `-ENOBUFS`. `Suspended` maps to `-ENOTCONN`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            UsbError::Impl(e) => e.to_errno(),
+            UsbError::WouldBlock => -errno::EAGAIN,
+            UsbError::InvalidEndpoint { .. } | UsbError::InvalidState => -errno::EINVAL,
+            UsbError::Stalled => -errno::EPIPE,
+            UsbError::ParseError => -errno::EBADMSG,
+            UsbError::DataToggleError => -errno::EILSEQ,
+            UsbError::Unsupported => -errno::EOPNOTSUPP,
+            UsbError::BusReset => -errno::ECONNRESET,
+            UsbError::BufferOverflow { .. }
+            | UsbError::EndpointOverflow
+            | UsbError::EndpointMemoryOverflow => -errno::ENOBUFS,
+            UsbError::Suspended => -errno::ENOTCONN,
+        }
+    }
+
+    /// Checks that a buffer of `available` bytes can hold `needed` bytes.
+    ///
+    /// Returns a [`UsbError::BufferOverflow`] carrying both sizes if the buffer is too short.
+    pub fn check_buffer(needed: usize, available: usize) -> Result<(), UsbError> {
+        if available < needed {
+            Err(UsbError::BufferOverflow { needed, available })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl fmt::Display for UsbError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UsbError::WouldBlock => f.write_str("operation would block"),
+            UsbError::ParseError => f.write_str("parsing failed due to invalid input"),
+            UsbError::BufferOverflow { needed, available } => write!(
+                f,
+                "buffer too short for the data, needed {} bytes but only {} available",
+                needed, available
+            ),
+            UsbError::EndpointOverflow => f.write_str("too many endpoints allocated"),
+            UsbError::EndpointMemoryOverflow => f.write_str("too much endpoint memory allocated"),
+            UsbError::InvalidEndpoint { address } => write!(
+                f,
+                "invalid or already used endpoint address {:#04x}",
+                address
+            ),
+            UsbError::Unsupported => f.write_str("operation not supported"),
+            UsbError::InvalidState => f.write_str("operation not valid in the current state"),
+            UsbError::Stalled => f.write_str("endpoint stalled"),
+            UsbError::BusReset => f.write_str("bus reset"),
+            UsbError::Suspended => f.write_str("bus suspended"),
+            UsbError::DataToggleError => f.write_str("data toggle mismatch"),
+            UsbError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for UsbError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            UsbError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(UsbError, impl_error);
+
+impl PeripheralError for UsbError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a UsbError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a UsbError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[UsbError] = &[
+        UsbError::WouldBlock,
+        UsbError::ParseError,
+        UsbError::BufferOverflow {
+            needed: 0,
+            available: 0,
+        },
+        UsbError::EndpointOverflow,
+        UsbError::EndpointMemoryOverflow,
+        UsbError::InvalidEndpoint { address: 0 },
+        UsbError::Unsupported,
+        UsbError::InvalidState,
+        UsbError::Stalled,
+        UsbError::BusReset,
+        UsbError::Suspended,
+        UsbError::DataToggleError,
+        UsbError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(UsbError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (UsbError::WouldBlock, "operation would block"),
+            (UsbError::ParseError, "parsing failed due to invalid input"),
+            (
+                UsbError::BufferOverflow {
+                    needed: 64,
+                    available: 32,
+                },
+                "buffer too short for the data, needed 64 bytes but only 32 available",
+            ),
+            (UsbError::EndpointOverflow, "too many endpoints allocated"),
+            (
+                UsbError::EndpointMemoryOverflow,
+                "too much endpoint memory allocated",
+            ),
+            (
+                UsbError::InvalidEndpoint { address: 0x81 },
+                "invalid or already used endpoint address 0x81",
+            ),
+            (UsbError::Unsupported, "operation not supported"),
+            (
+                UsbError::InvalidState,
+                "operation not valid in the current state",
+            ),
+            (UsbError::Stalled, "endpoint stalled"),
+            (UsbError::BusReset, "bus reset"),
+            (UsbError::Suspended, "bus suspended"),
+            (UsbError::DataToggleError, "data toggle mismatch"),
+            (
+                UsbError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(
+            retryable,
+            [&UsbError::WouldBlock, &UsbError::DataToggleError]
+        );
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(
+            codes,
+            [
+                0x0201, 0x0202, 0x0203, 0x0204, 0x0205, 0x0206, 0x0207, 0x0208, 0x0209, 0x020a,
+                0x020b, 0x020c, 0x0001,
+            ]
+        );
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Fatal,
+                Severity::Fatal,
+                Severity::Degraded,
+                Severity::Fatal,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Recoverable,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "usb");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(
+            millis,
+            [
+                Some(1),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(1),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::EAGAIN,
+                -errno::EBADMSG,
+                -errno::ENOBUFS,
+                -errno::ENOBUFS,
+                -errno::ENOBUFS,
+                -errno::EINVAL,
+                -errno::EOPNOTSUPP,
+                -errno::EINVAL,
+                -errno::EPIPE,
+                -errno::ECONNRESET,
+                -errno::ENOTCONN,
+                -errno::EILSEQ,
+                -errno::EIO,
+            ]
+        );
+    }
+
+    #[test]
+    fn check_buffer() {
+        assert_eq!(UsbError::check_buffer(32, 64), Ok(()));
+        assert_eq!(UsbError::check_buffer(64, 64), Ok(()));
+        assert_eq!(
+            UsbError::check_buffer(64, 32),
+            Err(UsbError::BufferOverflow {
+                needed: 64,
+                available: 32
+            })
+        );
+    }
+}
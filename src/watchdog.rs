@@ -0,0 +1,307 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// A Watchdog specific error.
+///
+/// This error type contains errors specific to Watchdog peripherals. Also it has an `Impl` kind to
+/// pass through implementation specific errors occurring while trying to use a Watchdog peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum WatchdogError {
+    /// The requested timeout cannot be achieved with the watchdog clock and counter
+    TimeoutOutOfRange,
+    /// The watchdog has already been started and cannot be reconfigured
+    AlreadyStarted,
+    /// The watchdog cannot be disabled once it has been started
+    CannotDisable,
+    /// The requested refresh window is not supported
+    WindowOutOfRange,
+    /// The clock source required by the watchdog is not available
+    ClockSourceUnavailable,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl WatchdogError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `Impl` errors are retryable if the wrapped [`ImplError`] is. All other kinds are hard
+    /// faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            WatchdogError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`], the watchdog specific kinds are
+    /// not retryable and return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            WatchdogError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`WatchdogError::Impl`] and `false` for the watchdog specific kinds, e.g.
+    /// [`WatchdogError::TimeoutOutOfRange`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, WatchdogError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`WatchdogError::Impl`] with `f`.
+    ///
+    /// The watchdog specific kinds, e.g. [`WatchdogError::TimeoutOutOfRange`], are returned
+    /// unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            WatchdogError::Impl(e) => WatchdogError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// Watchdog errors use the high byte `0x0d` and the index of the error kind as low byte, e.g.
+    /// `0x0d01` for `TimeoutOutOfRange`. See the [crate level documentation](crate#error-codes) for
+    /// details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            WatchdogError::TimeoutOutOfRange => 0x0d01,
+            WatchdogError::AlreadyStarted => 0x0d02,
+            WatchdogError::CannotDisable => 0x0d03,
+            WatchdogError::WindowOutOfRange => 0x0d04,
+            WatchdogError::ClockSourceUnavailable => 0x0d05,
+            WatchdogError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`WatchdogError::to_code`], e.g. `0x0d01` to
+    /// [`WatchdogError::TimeoutOutOfRange`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the watchdog error kinds nor
+    /// to an [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x0d01 => Some(WatchdogError::TimeoutOutOfRange),
+            0x0d02 => Some(WatchdogError::AlreadyStarted),
+            0x0d03 => Some(WatchdogError::CannotDisable),
+            0x0d04 => Some(WatchdogError::WindowOutOfRange),
+            0x0d05 => Some(WatchdogError::ClockSourceUnavailable),
+            0x0000..=0x00ff => ImplError::from_code(code).map(WatchdogError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. `CannotDisable` and `ClockSourceUnavailable` are
+    /// [`Severity::Fatal`]. All other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            WatchdogError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            WatchdogError::CannotDisable | WatchdogError::ClockSourceUnavailable => Severity::Fatal,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"watchdog"`, the stable lowercase name of the watchdog peripheral, for all kinds
+    /// including `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "watchdog"
+    }
+
+    /// Returns the name of the error kind, e.g. `"TimeoutOutOfRange"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            WatchdogError::TimeoutOutOfRange => "TimeoutOutOfRange",
+            WatchdogError::AlreadyStarted => "AlreadyStarted",
+            WatchdogError::CannotDisable => "CannotDisable",
+            WatchdogError::WindowOutOfRange => "WindowOutOfRange",
+            WatchdogError::ClockSourceUnavailable => "ClockSourceUnavailable",
+            WatchdogError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `AlreadyStarted` maps to
+    /// `-EBUSY`. `ClockSourceUnavailable` maps to `-ENODEV`. `TimeoutOutOfRange` and
+    /// `WindowOutOfRange` map to `-ERANGE`. `CannotDisable` maps to `-EOPNOTSUPP`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            WatchdogError::Impl(e) => e.to_errno(),
+            WatchdogError::AlreadyStarted => -errno::EBUSY,
+            WatchdogError::ClockSourceUnavailable => -errno::ENODEV,
+            WatchdogError::TimeoutOutOfRange | WatchdogError::WindowOutOfRange => -errno::ERANGE,
+            WatchdogError::CannotDisable => -errno::EOPNOTSUPP,
+        }
+    }
+}
+
+impl fmt::Display for WatchdogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WatchdogError::TimeoutOutOfRange => f.write_str("timeout out of range"),
+            WatchdogError::AlreadyStarted => f.write_str("watchdog already started"),
+            WatchdogError::CannotDisable => f.write_str("watchdog cannot be disabled"),
+            WatchdogError::WindowOutOfRange => f.write_str("window out of range"),
+            WatchdogError::ClockSourceUnavailable => f.write_str("clock source unavailable"),
+            WatchdogError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for WatchdogError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            WatchdogError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(WatchdogError, impl_error);
+
+impl PeripheralError for WatchdogError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a WatchdogError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a WatchdogError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[WatchdogError] = &[
+        WatchdogError::TimeoutOutOfRange,
+        WatchdogError::AlreadyStarted,
+        WatchdogError::CannotDisable,
+        WatchdogError::WindowOutOfRange,
+        WatchdogError::ClockSourceUnavailable,
+        WatchdogError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(WatchdogError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (WatchdogError::TimeoutOutOfRange, "timeout out of range"),
+            (WatchdogError::AlreadyStarted, "watchdog already started"),
+            (WatchdogError::CannotDisable, "watchdog cannot be disabled"),
+            (WatchdogError::WindowOutOfRange, "window out of range"),
+            (
+                WatchdogError::ClockSourceUnavailable,
+                "clock source unavailable",
+            ),
+            (
+                WatchdogError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        assert!(!KINDS.iter().any(|e| e.is_retryable()));
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(codes, [0x0d01, 0x0d02, 0x0d03, 0x0d04, 0x0d05, 0x0001,]);
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Degraded,
+                Severity::Degraded,
+                Severity::Fatal,
+                Severity::Degraded,
+                Severity::Fatal,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "watchdog");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(millis, [None, None, None, None, None, None,]);
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::ERANGE,
+                -errno::EBUSY,
+                -errno::EOPNOTSUPP,
+                -errno::ERANGE,
+                -errno::ENODEV,
+                -errno::EIO,
+            ]
+        );
+    }
+}
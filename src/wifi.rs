@@ -0,0 +1,335 @@
+use crate::{errno, ImplError, PeripheralError, Severity};
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+
+/// A Wi-Fi specific error.
+///
+/// This error type contains errors specific to Wi-Fi station and access point peripherals. Also it
+/// has an `Impl` kind to pass through implementation specific errors occurring while trying to use
+/// a Wi-Fi peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[must_use]
+pub enum WifiError {
+    /// Authentication with the access point failed, e.g. due to a wrong passphrase
+    AuthenticationFailed,
+    /// The access point rejected the association
+    AssociationFailed,
+    /// No network with the requested SSID was found
+    NoNetworkFound,
+    /// The connection to the access point was lost
+    Disconnected,
+    /// No IP address could be obtained via DHCP
+    DhcpFailed,
+    /// The Wi-Fi stack is busy with another operation, e.g. a scan
+    Busy,
+    /// Implementation specific error (shared across all peripheral specific error kinds)
+    Impl(ImplError),
+}
+
+impl WifiError {
+    /// Returns whether retrying the failed operation may succeed.
+    ///
+    /// `AssociationFailed`, `NoNetworkFound`, `DhcpFailed` and `Busy` are transient and thus
+    /// retryable. `Impl` errors are retryable if the wrapped [`ImplError`] is. All other kinds are
+    /// hard faults.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            WifiError::AssociationFailed
+            | WifiError::NoNetworkFound
+            | WifiError::DhcpFailed
+            | WifiError::Busy => true,
+            WifiError::Impl(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a suggested delay before retrying the failed operation.
+    ///
+    /// `Impl` errors return the hint of the wrapped [`ImplError`]. `AssociationFailed`,
+    /// `NoNetworkFound`, `DhcpFailed` and `Busy` suggest 1 ms, all other kinds return `None`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            WifiError::Impl(e) => e.retry_after(),
+            _ if self.is_retryable() => Some(Duration::from_millis(1)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`WifiError::Impl`] and `false` for the Wi-Fi specific kinds, e.g.
+    /// [`WifiError::AuthenticationFailed`].
+    ///
+    /// Unlike [`impl_error`](Self::impl_error) this can be evaluated in const contexts.
+    pub const fn is_impl(&self) -> bool {
+        matches!(self, WifiError::Impl(_))
+    }
+
+    /// Transforms the [`ImplError`] wrapped by [`WifiError::Impl`] with `f`.
+    ///
+    /// The Wi-Fi specific kinds, e.g. [`WifiError::AuthenticationFailed`], are returned unchanged.
+    pub fn map_impl(self, f: impl FnOnce(ImplError) -> ImplError) -> Self {
+        match self {
+            WifiError::Impl(e) => WifiError::Impl(f(e)),
+            e => e,
+        }
+    }
+
+    /// Returns the stable numeric code of this error kind.
+    ///
+    /// Wi-Fi errors use the high byte `0x2d` and the index of the error kind as low byte, e.g.
+    /// `0x2d01` for `AuthenticationFailed`. See the [crate level documentation](crate#error-codes)
+    /// for details.
+    ///
+    /// `Impl` errors return the code of the wrapped [`ImplError`].
+    pub fn to_code(&self) -> u16 {
+        match self {
+            WifiError::AuthenticationFailed => 0x2d01,
+            WifiError::AssociationFailed => 0x2d02,
+            WifiError::NoNetworkFound => 0x2d03,
+            WifiError::Disconnected => 0x2d04,
+            WifiError::DhcpFailed => 0x2d05,
+            WifiError::Busy => 0x2d06,
+            WifiError::Impl(e) => e.to_code(),
+        }
+    }
+
+    /// Decodes a code returned by [`WifiError::to_code`], e.g. `0x2d01` to
+    /// [`WifiError::AuthenticationFailed`].
+    ///
+    /// Returns `None` for codes which are neither assigned to one of the Wi-Fi error kinds nor to
+    /// an [`ImplError`].
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0x2d01 => Some(WifiError::AuthenticationFailed),
+            0x2d02 => Some(WifiError::AssociationFailed),
+            0x2d03 => Some(WifiError::NoNetworkFound),
+            0x2d04 => Some(WifiError::Disconnected),
+            0x2d05 => Some(WifiError::DhcpFailed),
+            0x2d06 => Some(WifiError::Busy),
+            0x0000..=0x00ff => ImplError::from_code(code).map(WifiError::Impl),
+            _ => None,
+        }
+    }
+
+    /// Returns the severity of the error.
+    ///
+    /// `Impl` errors are classified by the wrapped [`ImplError`]. [Retryable](Self::is_retryable)
+    /// kinds are [`Severity::Recoverable`]. All other kinds are [`Severity::Degraded`].
+    pub fn severity(&self) -> Severity {
+        match self {
+            WifiError::Impl(e) => e.severity(),
+            _ if self.is_retryable() => Severity::Recoverable,
+            _ => Severity::Degraded,
+        }
+    }
+
+    /// Returns `"wifi"`, the stable lowercase name of the Wi-Fi peripheral, for all kinds including
+    /// `Impl`.
+    pub fn peripheral(&self) -> &'static str {
+        "wifi"
+    }
+
+    /// Returns the name of the error kind, e.g. `"AuthenticationFailed"`.
+    ///
+    /// `Impl` errors return `"Impl"` instead of the name of the wrapped [`ImplError`].
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            WifiError::AuthenticationFailed => "AuthenticationFailed",
+            WifiError::AssociationFailed => "AssociationFailed",
+            WifiError::NoNetworkFound => "NoNetworkFound",
+            WifiError::Disconnected => "Disconnected",
+            WifiError::DhcpFailed => "DhcpFailed",
+            WifiError::Busy => "Busy",
+            WifiError::Impl(_) => "Impl",
+        }
+    }
+
+    /// Returns a conventional negative POSIX `errno` value for the error, e.g. to pass it on to a
+    /// POSIX-like RTOS.
+    ///
+    /// `Impl` errors return the value of the wrapped [`ImplError`]. `AuthenticationFailed` maps to
+    /// `-EACCES`. `Busy` maps to `-EBUSY`. `Disconnected` maps to `-ENOTCONN`. `AssociationFailed`
+    /// maps to `-ECONNREFUSED`. `NoNetworkFound` maps to `-EHOSTUNREACH`. All other kinds map to
+    /// `-EIO`.
+    pub fn to_errno(&self) -> i32 {
+        match self {
+            WifiError::Impl(e) => e.to_errno(),
+            WifiError::AuthenticationFailed => -errno::EACCES,
+            WifiError::Busy => -errno::EBUSY,
+            WifiError::Disconnected => -errno::ENOTCONN,
+            WifiError::AssociationFailed => -errno::ECONNREFUSED,
+            WifiError::NoNetworkFound => -errno::EHOSTUNREACH,
+            _ => -errno::EIO,
+        }
+    }
+}
+
+impl fmt::Display for WifiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WifiError::AuthenticationFailed => f.write_str("authentication failed"),
+            WifiError::AssociationFailed => f.write_str("association failed"),
+            WifiError::NoNetworkFound => f.write_str("no network found"),
+            WifiError::Disconnected => f.write_str("disconnected from network"),
+            WifiError::DhcpFailed => f.write_str("DHCP failed"),
+            WifiError::Busy => f.write_str("Wi-Fi busy"),
+            WifiError::Impl(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for WifiError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            WifiError::Impl(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+crate::impl_error_passthrough!(WifiError, impl_error);
+
+impl PeripheralError for WifiError {
+    fn impl_error(&self) -> Option<&ImplError> {
+        self.impl_error()
+    }
+
+    fn peripheral(&self) -> &'static str {
+        self.peripheral()
+    }
+
+    fn variant_name(&self) -> &'static str {
+        self.variant_name()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity()
+    }
+}
+
+impl<'a> TryFrom<&'a WifiError> for &'a ImplError {
+    type Error = ();
+
+    fn try_from(e: &'a WifiError) -> Result<Self, Self::Error> {
+        e.impl_error().ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    const KINDS: &[WifiError] = &[
+        WifiError::AuthenticationFailed,
+        WifiError::AssociationFailed,
+        WifiError::NoNetworkFound,
+        WifiError::Disconnected,
+        WifiError::DhcpFailed,
+        WifiError::Busy,
+        WifiError::Impl(ImplError::Internal),
+    ];
+
+    crate::tests::peripheral_error_tests!(WifiError, KINDS);
+
+    #[test]
+    fn display() {
+        let cases = [
+            (WifiError::AuthenticationFailed, "authentication failed"),
+            (WifiError::AssociationFailed, "association failed"),
+            (WifiError::NoNetworkFound, "no network found"),
+            (WifiError::Disconnected, "disconnected from network"),
+            (WifiError::DhcpFailed, "DHCP failed"),
+            (WifiError::Busy, "Wi-Fi busy"),
+            (
+                WifiError::Impl(ImplError::TimedOut {
+                    after_ms: Some(100),
+                }),
+                "operation timed out after 100 ms",
+            ),
+        ];
+        for (e, display) in cases.iter() {
+            assert_eq!(e.to_string(), *display);
+        }
+    }
+
+    #[test]
+    fn is_retryable() {
+        let retryable: Vec<_> = KINDS.iter().filter(|e| e.is_retryable()).collect();
+        assert_eq!(
+            retryable,
+            [
+                &WifiError::AssociationFailed,
+                &WifiError::NoNetworkFound,
+                &WifiError::DhcpFailed,
+                &WifiError::Busy
+            ]
+        );
+    }
+
+    #[test]
+    fn to_code() {
+        let codes: Vec<_> = KINDS.iter().map(|e| e.to_code()).collect();
+        assert_eq!(
+            codes,
+            [0x2d01, 0x2d02, 0x2d03, 0x2d04, 0x2d05, 0x2d06, 0x0001,]
+        );
+    }
+
+    #[test]
+    fn severity() {
+        let severities: Vec<_> = KINDS.iter().map(|e| e.severity()).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Degraded,
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Degraded,
+                Severity::Recoverable,
+                Severity::Recoverable,
+                Severity::Fatal,
+            ]
+        );
+    }
+
+    #[test]
+    fn peripheral() {
+        for e in KINDS {
+            assert_eq!(e.peripheral(), "wifi");
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let millis: Vec<_> = KINDS
+            .iter()
+            .map(|e| e.retry_after().map(|d| d.as_millis()))
+            .collect();
+        assert_eq!(
+            millis,
+            [None, Some(1), Some(1), None, Some(1), Some(1), None,]
+        );
+    }
+
+    #[test]
+    fn to_errno() {
+        let errnos: Vec<_> = KINDS.iter().map(|e| e.to_errno()).collect();
+        assert_eq!(
+            errnos,
+            [
+                -errno::EACCES,
+                -errno::ECONNREFUSED,
+                -errno::EHOSTUNREACH,
+                -errno::ENOTCONN,
+                -errno::EIO,
+                -errno::EBUSY,
+                -errno::EIO,
+            ]
+        );
+    }
+}
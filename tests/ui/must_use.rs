@@ -0,0 +1,11 @@
+#![deny(unused_must_use)]
+
+use embedded_error::ImplError;
+
+fn open() -> ImplError {
+    ImplError::CouldNotOpen
+}
+
+fn main() {
+    open();
+}